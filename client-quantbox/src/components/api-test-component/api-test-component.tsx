@@ -13,7 +13,7 @@ const ApiTestComponent = (): JSX.Element => {
     queryKey: ["healthCheck"], // unique key for this query
     queryFn: async (): Promise<string> => {
       const response: Response = await fetch(
-        "http://localhost:8080/api/health",
+        "http://localhost:8080/api/v1/health",
       );
       if (!response.ok) {
         throw new Error(`health check failed: ${response.status}`);
@@ -1,8 +1,9 @@
 "use client";
 
 import { QueryClient, QueryClientProvider } from "@tanstack/react-query";
-import React, { JSX } from "react";
+import React, { JSX, useEffect } from "react";
 import { QuantboxSidebar } from "@/components/quantbox-sidebar/quantbox-sidebar";
+import { loadQuantboxWasm } from "@/lib/quantbox-wasm";
 
 const queryClient: QueryClient = new QueryClient();
 
@@ -11,6 +12,11 @@ export default function ClientLayoutWrapper({
 }: {
   children: React.ReactNode;
 }): JSX.Element {
+  // start fetching the wasm pricer early; the black-scholes pages use it once it's ready
+  useEffect((): void => {
+    void loadQuantboxWasm();
+  }, []);
+
   return (
     <>
       <QueryClientProvider client={queryClient}>
@@ -2,7 +2,8 @@
  * black-scholes option pricing model utilities
  *
  * file contains functions for option pricing and greeks calculations
- * based on the black-scholes model
+ * based on the black-scholes model. once the quantbox-wasm module has loaded, the pricing
+ * functions below run quantbox-core's implementation instead, so the numbers match the server
  */
 
 import {
@@ -10,6 +11,8 @@ import {
   OptionType,
 } from "@/types/black-scholes-fields";
 import GreeksFields from "@/types/greeks-fields";
+import { getQuantboxWasm } from "@/lib/quantbox-wasm";
+import { QuantboxWasm } from "@/types/quantbox-wasm";
 
 /**
  * standard normal cumulative distribution function
@@ -88,6 +91,9 @@ export function calculateCallPrice(
   v: number,
   T: number,
 ): number {
+  const wasm: QuantboxWasm | null = getQuantboxWasm();
+  if (wasm) return wasm.calculateCallPrice(S, K, r, v, T);
+
   // special case handling
   if (T <= 0) return Math.max(0, S - K);
   if (v <= 0) return Math.max(0, S - K * Math.exp(-r * T));
@@ -115,6 +121,9 @@ export function calculatePutPrice(
   v: number,
   T: number,
 ): number {
+  const wasm: QuantboxWasm | null = getQuantboxWasm();
+  if (wasm) return wasm.calculatePutPrice(S, K, r, v, T);
+
   // special case handling
   if (T <= 0) return Math.max(0, K - S);
   if (v <= 0) return Math.max(0, K * Math.exp(-r * T) - S);
@@ -143,6 +152,9 @@ export function calculateGreeks(
   T: number,
   type: OptionType,
 ): GreeksFields {
+  const wasm: QuantboxWasm | null = getQuantboxWasm();
+  if (wasm) return wasm.calculateGreeks(S, K, r, v, T, type);
+
   // special case handling
   if (T <= 0 || v <= 0) {
     return {
@@ -210,6 +222,16 @@ export function generateHeatmapData(
   r: number,
   T: number,
 ): BlackScholesHeatmapData {
+  const wasm: QuantboxWasm | null = getQuantboxWasm();
+  if (wasm)
+    return wasm.generateHeatmapData(
+      baseSpotPrice,
+      baseStrikePrice,
+      baseVolatility,
+      r,
+      T,
+    );
+
   const spotSteps = 10;
   const volSteps = 10;
 
@@ -0,0 +1,43 @@
+/**
+ * loader for the quantbox-wasm black-scholes pricer
+ *
+ * the module is built into public/wasm by `npm run build:wasm` and fetched at runtime, so the
+ * app still builds and runs without it -- black-scholes-utils falls back to its typescript
+ * implementation until (or unless) the module has loaded
+ */
+
+import { QuantboxWasm } from "@/types/quantbox-wasm";
+
+const WASM_MODULE_URL: string = "/wasm/quantbox_wasm.js";
+
+let wasmModule: QuantboxWasm | null = null;
+let loading: Promise<QuantboxWasm | null> | null = null;
+
+/**
+ * fetch and instantiate the module once; later calls share the same promise
+ * @returns {Promise<QuantboxWasm | null>} - the module, or null if it couldn't be loaded
+ */
+export function loadQuantboxWasm(): Promise<QuantboxWasm | null> {
+  if (!loading) {
+    loading = import(
+      /* webpackIgnore: true */ /* turbopackIgnore: true */ WASM_MODULE_URL
+    )
+      .then(async (module: QuantboxWasm) => {
+        await module.default();
+        wasmModule = module;
+        return module;
+      })
+      .catch((error: unknown) => {
+        console.warn("wasm pricer unavailable, using typescript", error);
+        return null;
+      });
+  }
+  return loading;
+}
+
+/**
+ * @returns {QuantboxWasm | null} - the module if it has finished loading
+ */
+export function getQuantboxWasm(): QuantboxWasm | null {
+  return wasmModule;
+}
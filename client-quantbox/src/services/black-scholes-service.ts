@@ -22,7 +22,7 @@ export async function fetchOptionPrices(
   input: BlackScholesFields,
 ): Promise<OptionPricesResponse> {
   const response: Response = await fetch(
-    "http://localhost:8080/api/black-scholes/get-options-prices",
+    "http://localhost:8080/api/v1/options/price",
     {
       method: "POST",
       headers: { "Content-Type": "application/json" },
@@ -40,14 +40,19 @@ export async function fetchOptionPrices(
     throw new Error("Failed to fetch option prices");
   }
 
-  return response.json();
+  const rawResponse = await response.json();
+
+  return {
+    callPrice: rawResponse.call_price,
+    putPrice: rawResponse.put_price,
+  } as OptionPricesResponse;
 }
 
 export async function fetchGreeksPrices(
   input: BlackScholesFields,
 ): Promise<GreeksFields> {
   const response: Response = await fetch(
-    "http://localhost:8080/api/black-scholes/get-greeks-prices",
+    "http://localhost:8080/api/v1/options/greeks",
     {
       method: "POST",
       headers: { "Content-Type": "application/json" },
@@ -73,7 +78,7 @@ export async function fetchHeatmapData(
   input: BlackScholesFields,
 ): Promise<HeatmapResponse> {
   const response: Response = await fetch(
-    "http://localhost:8080/api/black-scholes/get-heatmap-prices",
+    "http://localhost:8080/api/v1/options/heatmap",
     {
       method: "POST",
       headers: { "Content-Type": "application/json" },
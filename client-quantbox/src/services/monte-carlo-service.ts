@@ -8,7 +8,7 @@ export async function fetchMonteCarloSimulation(
   input: MonteCarloParams,
 ): Promise<MonteCarloResult> {
   const response: Response = await fetch(
-    "http://localhost:8080/api/monte-carlo/get-price",
+    "http://localhost:8080/api/v1/monte-carlo/simulations",
     {
       method: "POST",
       headers: { "Content-Type": "application/json" },
@@ -19,6 +19,7 @@ export async function fetchMonteCarloSimulation(
         risk_free_rate: input.riskFreeRate,
         volatility: input.volatility,
         num_simulations: input.numSimulations,
+        engine: "sequential",
       }),
     },
   );
@@ -34,7 +35,7 @@ export async function fetchMonteCarloSimulationParallel(
   input: MonteCarloParams,
 ): Promise<MonteCarloResult> {
   const response: Response = await fetch(
-    "http://localhost:8080/api/monte-carlo/get-price-parallel",
+    "http://localhost:8080/api/v1/monte-carlo/simulations",
     {
       method: "POST",
       headers: { "Content-Type": "application/json" },
@@ -45,6 +46,7 @@ export async function fetchMonteCarloSimulationParallel(
         risk_free_rate: input.riskFreeRate,
         volatility: input.volatility,
         num_simulations: input.numSimulations,
+        engine: "parallel",
       }),
     },
   );
@@ -60,7 +62,7 @@ export async function fetchComparison(
   input: MonteCarloParams,
 ): Promise<ComparisonResult> {
   const response: Response = await fetch(
-    "http://localhost:8080/api/monte-carlo/get-comparison",
+    "http://localhost:8080/api/v1/monte-carlo/comparisons",
     {
       method: "POST",
       headers: { "Content-Type": "application/json" },
@@ -84,7 +86,7 @@ export async function fetchComparison(
 
 export async function fetchConvergenceAnalysis(input: MonteCarloParams) {
   const response: Response = await fetch(
-    "http://localhost:8080/api/monte-carlo/get-convergence",
+    "http://localhost:8080/api/v1/monte-carlo/convergence",
     {
       method: "POST",
       headers: { "Content-Type": "application/json" },
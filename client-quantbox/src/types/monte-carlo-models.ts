@@ -7,6 +7,43 @@ export interface MonteCarloParams {
   numSimulations?: number;
 }
 
+export interface InstrumentStatistics {
+  name: string;
+  strike: number;
+  price: number;
+  standard_error: number;
+  confidence_interval: [number, number];
+  relative_error: number | null;
+  effective_sample_size: number;
+}
+
+export interface ParityDiagnostics {
+  simulated_call_minus_put: number;
+  theoretical_call_minus_put: number;
+  difference: number;
+  standard_error: number;
+  z_score: number;
+}
+
+export interface WorkerStats {
+  thread: number;
+  chunks: number;
+  paths: number;
+  busy_ms: number;
+}
+
+export interface ExecutionStats {
+  num_threads: number;
+  chunk_size: number;
+  auto_tuned: boolean;
+  num_chunks: number;
+  wall_time_ms: number;
+  busy_time_ms: number;
+  parallel_efficiency: number;
+  load_imbalance: number;
+  threads: WorkerStats[];
+}
+
 export interface MonteCarloResult {
   call_price: number;
   put_price: number;
@@ -14,6 +51,11 @@ export interface MonteCarloResult {
   confidence_interval_95: [number, number];
   num_simulations: number;
   computation_time_ms: number;
+  confidence_level: number;
+  interval_method: "normal" | "student_t";
+  instruments: InstrumentStatistics[];
+  put_call_parity: ParityDiagnostics | null;
+  execution: ExecutionStats | null;
 }
 
 export interface ComparisonResult {
@@ -33,12 +75,27 @@ export interface ComparisonResult {
 export interface ConvergencePoint {
   num_simulations: number;
   call_price: number;
+  put_price: number;
   standard_error: number;
+  put_standard_error: number;
+  call_error: number;
+  put_error: number;
   time_ms: number;
 }
 
+export interface ConvergenceRate {
+  slope: number;
+  intercept: number;
+  r_squared: number;
+  num_points: number;
+}
+
 export interface ConvergenceResult {
   convergence_data: ConvergencePoint[];
   black_scholes_reference: number;
+  black_scholes_put_reference: number;
   final_difference: number;
+  final_put_difference: number;
+  call_convergence_rate: ConvergenceRate | null;
+  put_convergence_rate: ConvergenceRate | null;
 }
@@ -0,0 +1,58 @@
+import {
+  BlackScholesHeatmapData,
+  OptionPricesResponse,
+  OptionType,
+} from "@/types/black-scholes-fields";
+import GreeksFields from "@/types/greeks-fields";
+
+/**
+ * exports of the quantbox-wasm module (see quantbox_wasm.d.ts in its build output)
+ * the functions share quantbox-core's numerics with the server
+ */
+export type QuantboxWasm = {
+  default: () => Promise<unknown>;
+  calculateCallPrice: (
+    spotPrice: number,
+    strikePrice: number,
+    riskFreeRate: number,
+    volatility: number,
+    timeToMaturity: number,
+  ) => number;
+  calculatePutPrice: (
+    spotPrice: number,
+    strikePrice: number,
+    riskFreeRate: number,
+    volatility: number,
+    timeToMaturity: number,
+  ) => number;
+  calculateOptionsPrices: (
+    spotPrice: number,
+    strikePrice: number,
+    riskFreeRate: number,
+    volatility: number,
+    timeToMaturity: number,
+  ) => OptionPricesResponse;
+  calculateGreeks: (
+    spotPrice: number,
+    strikePrice: number,
+    riskFreeRate: number,
+    volatility: number,
+    timeToMaturity: number,
+    optionType: OptionType,
+  ) => GreeksFields;
+  generateHeatmapData: (
+    spotPrice: number,
+    strikePrice: number,
+    volatility: number,
+    riskFreeRate: number,
+    timeToMaturity: number,
+  ) => BlackScholesHeatmapData;
+  calculateImpliedVolatility: (
+    price: number,
+    spotPrice: number,
+    strikePrice: number,
+    riskFreeRate: number,
+    timeToMaturity: number,
+    optionType: OptionType,
+  ) => number | undefined;
+};
@@ -0,0 +1,66 @@
+// scalar vs SIMD path kernel throughput for the parallel Monte Carlo engine
+//
+// run with:
+//   cargo bench --bench kernel_throughput
+// or, to let `wide` use every instruction set the build machine supports:
+//   RUSTFLAGS="-C target-cpu=native" cargo bench --bench kernel_throughput
+//
+// each kernel prices the same at-the-money option on a single chunk (no rayon) so the
+// numbers are per-core throughput. the estimates are printed for a quick look; the
+// statistical agreement check lives in simd_kernel's unit tests.
+//
+// sample results (x86_64, default target features i.e. SSE2 only, 2,000,000 paths per run):
+//   scalar      30.05 M paths/sec  (66.6 ms per 2000000 paths)
+//   simd        57.69 M paths/sec  (34.7 ms per 2000000 paths)
+//   speedup      1.92x
+//   call     10.4430 +/- 0.0104 vs 10.4443 +/- 0.0104, |diff| = 0.09 se
+//   put      5.5790 vs 5.5823, |diff| = 0.38 se
+use std::hint::black_box;
+use std::time::Instant;
+use quantbox_core::compute::accumulator::Accumulator;
+use quantbox_core::compute::parallel_monte_carlo_engine::ParallelMonteCarloEngine;
+use quantbox_core::compute::simd_kernel;
+use quantbox_core::models::monte_carlo_models::{MonteCarloRequest, MonteCarloResult};
+
+const PATHS: usize = 2_000_000;
+const RUNS: usize = 5;
+
+fn best_of(name: &str, run: impl Fn() -> Accumulator) -> (Accumulator, f64) {
+    // warm up caches and the branch predictor
+    black_box(run());
+
+    let mut best_secs: f64 = f64::INFINITY;
+    let mut last: Accumulator = Accumulator::default();
+    for _ in 0..RUNS {
+        let start: Instant = Instant::now();
+        last = black_box(run());
+        best_secs = best_secs.min(start.elapsed().as_secs_f64());
+    }
+
+    let paths_per_sec: f64 = PATHS as f64 / best_secs;
+    println!("{:<8} {:>8.2} M paths/sec  ({:.1} ms per {} paths)", name, paths_per_sec / 1e6, best_secs * 1e3, PATHS);
+    (last, paths_per_sec)
+}
+
+fn main() {
+    let params: MonteCarloRequest = MonteCarloRequest::new(100.0, 100.0, 1.0, 0.05, 0.2, PATHS);
+
+    let (scalar, scalar_rate) = best_of("scalar", || ParallelMonteCarloEngine::process_chunk_scalar(&params, PATHS));
+    let (simd, simd_rate) = best_of("simd", || simd_kernel::simulate_chunk(&params, PATHS));
+    println!("speedup  {:>8.2}x", simd_rate / scalar_rate);
+
+    let scalar_result: MonteCarloResult = scalar.to_result(&params);
+    let simd_result: MonteCarloResult = simd.to_result(&params);
+
+    let combined_se: f64 = (scalar_result.standard_error.powi(2) + simd_result.standard_error.powi(2)).sqrt();
+    let call_diff: f64 = (scalar_result.call_price - simd_result.call_price).abs() / combined_se;
+    println!(
+        "call     {:.4} +/- {:.4} vs {:.4} +/- {:.4}, |diff| = {:.2} se",
+        scalar_result.call_price, scalar_result.standard_error,
+        simd_result.call_price, simd_result.standard_error, call_diff
+    );
+
+    let put_se: f64 = (scalar.put.standard_error().powi(2) + simd.put.standard_error().powi(2)).sqrt();
+    let put_diff: f64 = (scalar_result.put_price - simd_result.put_price).abs() / put_se;
+    println!("put      {:.4} vs {:.4}, |diff| = {:.2} se", scalar_result.put_price, simd_result.put_price, put_diff);
+}
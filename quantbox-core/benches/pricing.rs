@@ -0,0 +1,108 @@
+// criterion suite for the pricing kernels
+//
+//   cargo bench --bench pricing                                   # run everything
+//   cargo bench --bench pricing -- black_scholes                  # filter by name
+//
+// regression tracking (baselines live under target/criterion/<bench>/<baseline>/):
+//   cargo bench --bench pricing -- --save-baseline main           # record a baseline, e.g. on main
+//   cargo bench --bench pricing -- --baseline main                # re-run and compare against it
+//   cargo run --release --example bench_regression -- --baseline main --threshold 5
+//
+// the last command reads criterion's estimates, prints the change of every benchmark against
+// the baseline plus the parallel engine's scaling efficiency, and exits non-zero if anything
+// got slower by more than the threshold (percent).
+use std::hint::black_box;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use quantbox_core::compute::black_scholes::{calculate_call_price, calculate_greeks, generate_heatmap_data, price_batch};
+use quantbox_core::compute::monte_carlo_engine::MonteCarloEngine;
+use quantbox_core::compute::parallel_monte_carlo_engine::ParallelMonteCarloEngine;
+use quantbox_core::models::black_scholes_models::{BatchColumns, OptionType};
+use quantbox_core::models::monte_carlo_models::MonteCarloRequest;
+
+const SIMULATION_COUNTS: [usize; 3] = [10_000, 100_000, 1_000_000];
+const SCALING_PATHS: usize = 1_000_000;
+const BATCH_OPTIONS: usize = 1_000_000;
+
+fn at_the_money(num_simulations: usize) -> MonteCarloRequest {
+    MonteCarloRequest::new(100.0, 100.0, 1.0, 0.05, 0.2, num_simulations)
+}
+
+fn black_scholes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("black_scholes");
+
+    group.bench_function("call_price", |b| {
+        b.iter(|| calculate_call_price(black_box(100.0), black_box(100.0), black_box(0.05), black_box(0.2), black_box(1.0)))
+    });
+    group.bench_function("greeks", |b| {
+        b.iter(|| calculate_greeks(black_box(100.0), black_box(100.0), black_box(0.05), black_box(0.2), black_box(1.0), OptionType::Call))
+    });
+    group.bench_function("heatmap", |b| {
+        b.iter(|| generate_heatmap_data(black_box(100.0), black_box(100.0), black_box(0.2), black_box(0.05), black_box(1.0)))
+    });
+
+    group.finish();
+}
+
+// a book of options spread over strikes, vols and maturities, priced with all Greeks
+fn black_scholes_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("black_scholes_batch");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(BATCH_OPTIONS as u64));
+
+    let columns: BatchColumns = BatchColumns {
+        spot_price: vec![100.0; BATCH_OPTIONS],
+        strike_price: (0..BATCH_OPTIONS).map(|i| 50.0 + (i % 100) as f64).collect(),
+        risk_free_rate: vec![0.05; BATCH_OPTIONS],
+        volatility: (0..BATCH_OPTIONS).map(|i| 0.1 + (i % 40) as f64 * 0.01).collect(),
+        time_to_maturity: (0..BATCH_OPTIONS).map(|i| 0.1 + (i % 20) as f64 * 0.1).collect(),
+    };
+    group.bench_function(BenchmarkId::new("price_batch", BATCH_OPTIONS), |b| b.iter(|| price_batch(&columns).unwrap()));
+
+    group.finish();
+}
+
+// throughput vs simulation count, sequential and parallel side by side
+fn monte_carlo(c: &mut Criterion) {
+    let mut group = c.benchmark_group("monte_carlo");
+    group.sample_size(10);
+
+    for num_simulations in SIMULATION_COUNTS {
+        let params: MonteCarloRequest = at_the_money(num_simulations);
+        group.throughput(Throughput::Elements(num_simulations as u64));
+
+        group.bench_with_input(BenchmarkId::new("sequential", num_simulations), &params, |b, params| {
+            b.iter(|| MonteCarloEngine::price_european_option(params))
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", num_simulations), &params, |b, params| {
+            b.iter(|| ParallelMonteCarloEngine::price_european_option(params))
+        });
+    }
+
+    group.finish();
+}
+
+// fixed workload on dedicated pools of 1, 2, 4, ... threads up to the core count.
+// efficiency at n threads is time(1) / (n * time(n)), reported by the bench_regression example
+fn thread_scaling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("monte_carlo_thread_scaling");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(SCALING_PATHS as u64));
+
+    let params: MonteCarloRequest = at_the_money(SCALING_PATHS);
+    let max_threads: usize = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+
+    let mut threads: usize = 1;
+    while threads <= max_threads {
+        let pool: ThreadPool = ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+        group.bench_with_input(BenchmarkId::new("parallel", threads), &params, |b, params| {
+            b.iter(|| pool.install(|| ParallelMonteCarloEngine::price_european_option(params)))
+        });
+        threads *= 2;
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, black_scholes, black_scholes_batch, monte_carlo, thread_scaling);
+criterion_main!(benches);
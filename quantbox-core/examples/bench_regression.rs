@@ -0,0 +1,158 @@
+// compares criterion results against a saved baseline and flags regressions
+//
+//   cargo bench --bench pricing -- --save-baseline main
+//   ... make changes ...
+//   cargo bench --bench pricing -- --baseline main
+//   cargo run --release --example bench_regression -- --baseline main --threshold 5
+//
+// options:
+//   --baseline <name>   baseline saved with --save-baseline (default: main)
+//   --threshold <pct>   allowed slowdown of the mean time in percent (default: 5)
+//   --dir <path>        criterion output directory (default: the workspace's target/criterion)
+//
+// exits with 1 if any benchmark regressed beyond the threshold, 2 if there was nothing to compare
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use serde_json::Value;
+
+const SCALING_GROUP: &str = "monte_carlo_thread_scaling";
+
+struct Measurement {
+    full_id: String,
+    group_id: String,
+    value: String,
+    current_ns: f64,
+    baseline_ns: Option<f64>,
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    let text: String = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+fn mean_ns(path: &Path) -> Option<f64> {
+    read_json(path)?.get("mean")?.get("point_estimate")?.as_f64()
+}
+
+// every benchmark directory holds a `new/` folder with its latest run
+fn collect(dir: &Path, baseline: &str, out: &mut Vec<Measurement>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+
+    for entry in entries.flatten() {
+        let path: PathBuf = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        let latest: PathBuf = path.join("new");
+        if let Some(info) = read_json(&latest.join("benchmark.json"))
+            && let Some(current_ns) = mean_ns(&latest.join("estimates.json"))
+        {
+            out.push(Measurement {
+                full_id: info["full_id"].as_str().unwrap_or_default().to_string(),
+                group_id: info["group_id"].as_str().unwrap_or_default().to_string(),
+                value: info["value_str"].as_str().unwrap_or_default().to_string(),
+                current_ns,
+                baseline_ns: mean_ns(&path.join(baseline).join("estimates.json")),
+            });
+        } else {
+            collect(&path, baseline, out);
+        }
+    }
+}
+
+fn format_ns(ns: f64) -> String {
+    match ns {
+        ns if ns >= 1e9 => format!("{:.3} s", ns / 1e9),
+        ns if ns >= 1e6 => format!("{:.3} ms", ns / 1e6),
+        ns if ns >= 1e3 => format!("{:.3} us", ns / 1e3),
+        ns => format!("{:.1} ns", ns),
+    }
+}
+
+fn print_scaling(measurements: &[Measurement]) {
+    let mut points: Vec<(usize, f64)> = measurements.iter()
+        .filter(|m| m.group_id == SCALING_GROUP)
+        .filter_map(|m| Some((m.value.parse::<usize>().ok()?, m.current_ns)))
+        .collect();
+    points.sort_by_key(|(threads, _)| *threads);
+
+    let Some(&(1, single_thread_ns)) = points.first() else { return };
+
+    println!("\nthread scaling ({})", SCALING_GROUP);
+    println!("{:>8} {:>12} {:>9} {:>11}", "threads", "time", "speedup", "efficiency");
+    for (threads, ns) in points {
+        let speedup: f64 = single_thread_ns / ns;
+        println!("{:>8} {:>12} {:>8.2}x {:>10.1}%", threads, format_ns(ns), speedup, 100.0 * speedup / threads as f64);
+    }
+}
+
+fn main() -> ExitCode {
+    let mut baseline: String = "main".to_string();
+    let mut threshold: f64 = 5.0;
+    let mut dir: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("target").join("criterion");
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value: Option<String> = args.next();
+        match (arg.as_str(), value) {
+            ("--baseline", Some(v)) => baseline = v,
+            ("--threshold", Some(v)) => match v.parse::<f64>() {
+                Ok(t) if t >= 0.0 => threshold = t,
+                _ => {
+                    eprintln!("--threshold must be a non-negative number, got {}", v);
+                    return ExitCode::from(2);
+                }
+            },
+            ("--dir", Some(v)) => dir = PathBuf::from(v),
+            (other, _) => {
+                eprintln!("unknown or incomplete option {}", other);
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    let mut measurements: Vec<Measurement> = Vec::new();
+    collect(&dir, &baseline, &mut measurements);
+    measurements.sort_by(|a, b| a.full_id.cmp(&b.full_id));
+
+    if measurements.is_empty() {
+        eprintln!("no criterion results under {}; run `cargo bench --bench pricing` first", dir.display());
+        return ExitCode::from(2);
+    }
+
+    println!("baseline '{}', threshold {:.1}%\n", baseline, threshold);
+    println!("{:<48} {:>12} {:>12} {:>9}", "benchmark", "baseline", "current", "change");
+
+    let mut compared: usize = 0;
+    let mut regressions: Vec<&str> = Vec::new();
+    for m in &measurements {
+        let Some(baseline_ns) = m.baseline_ns else {
+            println!("{:<48} {:>12} {:>12} {:>9}", m.full_id, "-", format_ns(m.current_ns), "new");
+            continue;
+        };
+
+        compared += 1;
+        let change: f64 = 100.0 * (m.current_ns / baseline_ns - 1.0);
+        let flag: &str = if change > threshold { "  REGRESSED" } else { "" };
+        if change > threshold {
+            regressions.push(&m.full_id);
+        }
+        println!("{:<48} {:>12} {:>12} {:>+8.1}%{}", m.full_id, format_ns(baseline_ns), format_ns(m.current_ns), change, flag);
+    }
+
+    print_scaling(&measurements);
+
+    if compared == 0 {
+        eprintln!("\nno results for baseline '{}'; save one with `cargo bench --bench pricing -- --save-baseline {}`", baseline, baseline);
+        return ExitCode::from(2);
+    }
+    if !regressions.is_empty() {
+        eprintln!("\n{} of {} benchmarks regressed by more than {:.1}%", regressions.len(), compared, threshold);
+        return ExitCode::FAILURE;
+    }
+
+    println!("\nno regressions beyond {:.1}% across {} benchmarks", threshold, compared);
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,274 @@
+use crate::compute::statistics::{normal_quantile, student_t_quantile};
+use crate::models::monte_carlo_models::{InstrumentStatistics, IntervalMethod, MonteCarloRequest, MonteCarloResult,
+                                        ParityDiagnostics, PayoffSpec};
+
+// streaming mean and variance of one payoff stream (Welford). constant memory, and the
+// variance is built from squared deviations so it can't go negative by cancellation the
+// way E[X²] - E[X]² does once the mean dominates the spread
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RunningStats {
+    count: usize,
+    mean: f64,
+    // sum of squared deviations from the current mean
+    m2: f64,
+}
+
+impl RunningStats {
+    // statistics of a batch computed elsewhere (e.g. a two-pass SIMD reduction)
+    pub fn from_parts(count: usize, mean: f64, m2: f64) -> Self {
+        RunningStats { count, mean, m2 }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta: f64 = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    // combines two independent samples (Chan et al.), so chunks computed on different
+    // threads can be reduced in any order
+    pub fn merge(&mut self, other: &RunningStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+
+        let count: usize = self.count + other.count;
+        let delta: f64 = other.mean - self.mean;
+        let other_weight: f64 = other.count as f64 / count as f64;
+
+        self.mean += delta * other_weight;
+        self.m2 += other.m2 + delta * delta * self.count as f64 * other_weight;
+        self.count = count;
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    // unbiased sample variance
+    pub fn variance(&self) -> f64 {
+        if self.count < 2 {
+            return 0.0;
+        }
+        self.m2 / (self.count - 1) as f64
+    }
+
+    pub fn standard_error(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        (self.variance() / self.count as f64).sqrt()
+    }
+}
+
+// per-request constants for turning a simulated terminal price into discounted payoffs
+pub struct PathPayoffs<'a> {
+    pub strike_price: f64,
+    pub discount_factor: f64,
+    pub extra: &'a [PayoffSpec],
+}
+
+impl<'a> PathPayoffs<'a> {
+    pub fn new(params: &'a MonteCarloRequest) -> Self {
+        PathPayoffs {
+            strike_price: params.strike_price,
+            discount_factor: (-params.risk_free_rate * params.time_to_expiry).exp(),
+            extra: &params.extra_payoffs,
+        }
+    }
+}
+
+// discounted call and put payoffs (plus any extra payoffs) from the same paths. this is
+// what both engines and the convergence analysis accumulate into
+#[derive(Debug, Default, Clone)]
+pub struct Accumulator {
+    pub call: RunningStats,
+    pub put: RunningStats,
+    // one per PayoffSpec in the request, same order
+    pub extra: Vec<RunningStats>,
+    // per-path call - put, for the put-call parity check
+    pub parity: RunningStats,
+}
+
+impl Accumulator {
+    pub fn record(&mut self, final_price: f64, payoffs: &PathPayoffs) {
+        let call_payoff: f64 = (final_price - payoffs.strike_price).max(0.0) * payoffs.discount_factor;
+        let put_payoff: f64 = (payoffs.strike_price - final_price).max(0.0) * payoffs.discount_factor;
+
+        self.call.push(call_payoff);
+        self.put.push(put_payoff);
+        self.parity.push(call_payoff - put_payoff);
+
+        if self.extra.len() < payoffs.extra.len() {
+            self.extra.resize(payoffs.extra.len(), RunningStats::default());
+        }
+        for (stats, spec) in self.extra.iter_mut().zip(payoffs.extra) {
+            stats.push(spec.payoff(final_price) * payoffs.discount_factor);
+        }
+    }
+
+    pub fn merge(&mut self, other: &Accumulator) {
+        self.call.merge(&other.call);
+        self.put.merge(&other.put);
+        self.parity.merge(&other.parity);
+
+        if self.extra.len() < other.extra.len() {
+            self.extra.resize(other.extra.len(), RunningStats::default());
+        }
+        for (stats, other_stats) in self.extra.iter_mut().zip(&other.extra) {
+            stats.merge(other_stats);
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.call.count()
+    }
+
+    pub fn to_result(&self, params: &MonteCarloRequest) -> MonteCarloResult {
+        let call_price: f64 = self.call.mean();
+        let put_price: f64 = self.put.mean();
+        let standard_error: f64 = self.call.standard_error();
+
+        // 95% confidence interval (±1.96 standard errors)
+        let margin_of_error: f64 = 1.96 * standard_error;
+        let confidence_interval_95: (f64, f64) = (
+            call_price - margin_of_error,
+            call_price + margin_of_error,
+        );
+
+        // two-sided interval at the requested level
+        let tail_probability: f64 = 0.5 + params.confidence_level / 2.0;
+        let quantile: f64 = match params.interval_method {
+            IntervalMethod::Normal => normal_quantile(tail_probability),
+            IntervalMethod::StudentT => student_t_quantile(tail_probability, self.count().saturating_sub(1)),
+        };
+
+        let mut instruments: Vec<InstrumentStatistics> = vec![
+            Self::instrument_statistics("call", params.strike_price, &self.call, quantile),
+            Self::instrument_statistics("put", params.strike_price, &self.put, quantile),
+        ];
+        for (stats, spec) in self.extra.iter().zip(&params.extra_payoffs) {
+            instruments.push(Self::instrument_statistics(spec.name(), spec.strike(), stats, quantile));
+        }
+
+        MonteCarloResult {
+            call_price,
+            put_price,
+            standard_error,
+            confidence_interval_95,
+            confidence_level: params.confidence_level,
+            interval_method: params.interval_method,
+            instruments,
+            put_call_parity: self.parity_diagnostics(params),
+            // filled in by the parallel engine, which knows how the work was split
+            execution: None,
+        }
+    }
+
+    fn instrument_statistics(name: &str, strike: f64, stats: &RunningStats, quantile: f64) -> InstrumentStatistics {
+        let price: f64 = stats.mean();
+        let standard_error: f64 = stats.standard_error();
+        let margin_of_error: f64 = quantile * standard_error;
+
+        InstrumentStatistics {
+            name: name.to_string(),
+            strike,
+            price,
+            standard_error,
+            confidence_interval: (price - margin_of_error, price + margin_of_error),
+            relative_error: if price != 0.0 { Some(standard_error / price.abs()) } else { None },
+            effective_sample_size: stats.count() as f64,
+        }
+    }
+
+    fn parity_diagnostics(&self, params: &MonteCarloRequest) -> Option<ParityDiagnostics> {
+        if self.parity.count() == 0 {
+            return None;
+        }
+
+        let discount_factor: f64 = (-params.risk_free_rate * params.time_to_expiry).exp();
+        let theoretical: f64 = params.spot_price - params.strike_price * discount_factor;
+        let simulated: f64 = self.parity.mean();
+        let difference: f64 = simulated - theoretical;
+        let standard_error: f64 = self.parity.standard_error();
+
+        Some(ParityDiagnostics {
+            simulated_call_minus_put: simulated,
+            theoretical_call_minus_put: theoretical,
+            difference,
+            standard_error,
+            z_score: if standard_error > 0.0 { difference / standard_error } else { 0.0 },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats_of(values: &[f64]) -> RunningStats {
+        let mut stats: RunningStats = RunningStats::default();
+        values.iter().for_each(|&value| stats.push(value));
+        stats
+    }
+
+    #[test]
+    fn running_stats_match_the_textbook_values() {
+        // mean 5, population variance 4, so sample variance 32 / 7
+        let stats: RunningStats = stats_of(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+
+        assert_eq!(stats.count(), 8);
+        assert!((stats.mean() - 5.0).abs() < 1e-12);
+        assert!((stats.variance() - 32.0 / 7.0).abs() < 1e-12);
+        assert!((stats.standard_error() - (32.0 / 7.0 / 8.0f64).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn merging_matches_one_pass_over_everything() {
+        let values: Vec<f64> = (0..1_000).map(|i| ((i * 7_919) % 1_009) as f64 * 0.37 - 50.0).collect();
+        let whole: RunningStats = stats_of(&values);
+
+        // uneven splits, merged in both orders
+        for split in [1, 10, 333, 999] {
+            let (left, right) = values.split_at(split);
+            for (mut first, second) in [(stats_of(left), stats_of(right)), (stats_of(right), stats_of(left))] {
+                first.merge(&second);
+                assert_eq!(first.count(), whole.count());
+                assert!((first.mean() - whole.mean()).abs() < 1e-10);
+                assert!((first.variance() - whole.variance()).abs() / whole.variance() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn merging_with_empty_stats_changes_nothing() {
+        let stats: RunningStats = stats_of(&[1.0, 2.0, 3.0]);
+
+        let mut into_empty: RunningStats = RunningStats::default();
+        into_empty.merge(&stats);
+        let mut with_empty: RunningStats = stats;
+        with_empty.merge(&RunningStats::default());
+
+        for merged in [into_empty, with_empty] {
+            assert_eq!(merged.count(), 3);
+            assert_eq!(merged.mean(), 2.0);
+            assert_eq!(merged.variance(), 1.0);
+        }
+    }
+
+    #[test]
+    fn variance_survives_a_large_mean() {
+        // E[X²] - E[X]² loses every digit here
+        let stats: RunningStats = stats_of(&[1e9 + 4.0, 1e9 + 7.0, 1e9 + 13.0, 1e9 + 16.0]);
+        assert!((stats.variance() - 30.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,453 @@
+use core::f64::consts::PI;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::math::{exp, ln, sqrt};
+use crate::models::black_scholes_models::{BatchColumns, BatchValuation, BlackScholesResult, Greeks, HeatmapData, HeatmapGrid, OptionType};
+use crate::models::validation::ValidationError;
+
+// options per rayon task in price_batch -- enough to amortise scheduling over ~100ns of work each
+#[cfg(feature = "parallel")]
+const BATCH_MIN_LEN: usize = 4_096;
+
+fn normal_pdf(x: f64) -> f64 {
+    exp(-0.5 * x * x) / sqrt(2.0 * PI)
+}
+
+fn normal_cdf(x: f64) -> f64 {
+    let t: f64 = 1.0 / (1.0 + 0.2316419 * x.abs());
+    let d: f64 = 0.3989423 * exp(-x * x / 2.0);
+    let p: f64 = d * t * (0.3193815 + t * (-0.3565638 + t * (1.781478 + t * (-1.821256 + t * 1.330274))));
+    if x > 0.0 {
+        1.0 - p
+    } else {
+        p
+    }
+}
+
+fn calculate_d1(s: f64, k: f64, r: f64, v: f64, t: f64) -> f64 {
+    (ln(s / k) + (r + 0.5 * v * v) * t) / (v * sqrt(t))
+}
+
+fn calculate_d2(d1: f64, v: f64, t: f64) -> f64 {
+    d1 - v * sqrt(t)
+}
+
+pub fn calculate_call_price(s: f64, k: f64, r: f64, v: f64, t: f64) -> f64 {
+    if t <= 0.0 {
+        return (s - k).max(0.0);
+    }
+    if v <= 0.0 {
+        return (s - k * exp(-r * t)).max(0.0);
+    }
+
+    let d1: f64 = calculate_d1(s, k, r, v, t);
+    let d2: f64 = calculate_d2(d1, v, t);
+
+    normal_cdf(d1) * s - normal_cdf(d2) * k * exp(-r * t)
+}
+
+pub fn calculate_put_price(s: f64, k: f64, r: f64, v: f64, t: f64) -> f64 {
+    if t <= 0.0 {
+        return (k - s).max(0.0);
+    }
+    if v <= 0.0 {
+        return (k * exp(-r * t) - s).max(0.0);
+    }
+
+    let d1 = calculate_d1(s, k, r, v, t);
+    let d2 = calculate_d2(d1, v, t);
+
+    k * exp(-r * t) * normal_cdf(-d2) - s * normal_cdf(-d1)
+}
+
+pub fn calculate_options_prices(s: f64, k: f64, r: f64, v: f64, t: f64) -> BlackScholesResult {
+    BlackScholesResult {
+        call_price: calculate_call_price(s, k, r, v, t),
+        put_price: calculate_put_price(s, k, r, v, t),
+    }
+}
+
+pub fn calculate_greeks(s: f64, k: f64, r: f64, v: f64, t: f64, option_type: OptionType) -> Greeks {
+    if t <= 0.0 || v <= 0.0 {
+        return Greeks {
+            delta: 0.0,
+            gamma: 0.0,
+            theta: 0.0,
+            vega: 0.0,
+            rho: 0.0,
+        };
+    }
+
+    let d1: f64 = calculate_d1(s, k, r, v, t);
+    let d2: f64 = calculate_d2(d1, v, t);
+    let pdf: f64 = normal_pdf(d1);
+
+    let delta: f64 = match option_type {
+        OptionType::Call => 1.0 - normal_cdf(-d1),
+        OptionType::Put => normal_cdf(-d1) - 1.0,
+    };
+
+    let gamma: f64 = pdf / (s * v * sqrt(t));
+
+    let theta: f64 = match option_type {
+        OptionType::Call => (-s * pdf * v / (2.0 * sqrt(t)) - r * k * exp(-r * t) * (1.0 - normal_cdf(-d2))) / 365.0,
+        OptionType::Put => (-s * pdf * v / (2.0 * sqrt(t)) + r * k * exp(-r * t) * normal_cdf(-d2)) / 365.0,
+    };
+
+    let vega: f64 = s * sqrt(t) * pdf / 100.0;
+
+    let rho: f64 = match option_type {
+        OptionType::Call => k * t * exp(-r * t) * (1.0 - normal_cdf(-d2)) / 100.0,
+        OptionType::Put => -k * t * exp(-r * t) * normal_cdf(-d2) / 100.0,
+    };
+
+    Greeks {
+        delta,
+        gamma,
+        theta,
+        vega,
+        rho,
+    }
+}
+
+// call and put prices over a 10x10 grid of spot (±40% of base_s) and volatility
+// (base_v -80%..+80%, floored at 5%), rows by spot and columns by volatility
+pub fn calculate_heatmap_grid(base_s: f64, base_k: f64, base_v: f64, r: f64, t: f64) -> HeatmapGrid {
+    let spot_steps: usize = 10;
+    let vol_steps: usize = 10;
+
+    let spot_range: f64 = 0.4;
+    let vol_range: f64 = 0.8;
+
+    let min_s: f64 = base_s * (1.0 - spot_range);
+    let max_s: f64 = base_s * (1.0 + spot_range);
+    let spot_step: f64 = (max_s - min_s) / (spot_steps as f64 - 1.0);
+
+    let min_v: f64 = (base_v * (1.0 - vol_range)).max(0.05);
+    let max_v: f64 = base_v * (1.0 + vol_range);
+    let vol_step: f64 = (max_v - min_v) / (vol_steps as f64 - 1.0);
+
+    let spot_prices: Vec<f64> = (0..spot_steps).map(|i| min_s + i as f64 * spot_step).collect();
+    let volatilities: Vec<f64> = (0..vol_steps).map(|k| min_v + k as f64 * vol_step).collect();
+
+    let mut call_prices: Vec<f64> = Vec::with_capacity(spot_steps * vol_steps);
+    let mut put_prices: Vec<f64> = Vec::with_capacity(spot_steps * vol_steps);
+    for &s in &spot_prices {
+        for &v in &volatilities {
+            call_prices.push(calculate_call_price(s, base_k, r, v, t));
+            put_prices.push(calculate_put_price(s, base_k, r, v, t));
+        }
+    }
+
+    HeatmapGrid {
+        spot_prices,
+        volatilities,
+        call_prices,
+        put_prices,
+    }
+}
+
+// the grid above formatted to two decimals, as the heatmap endpoint returns it
+pub fn generate_heatmap_data(base_s: f64, base_k: f64, base_v: f64, r: f64, t: f64) -> HeatmapData {
+    let grid: HeatmapGrid = calculate_heatmap_grid(base_s, base_k, base_v, r, t);
+    let format_all = |values: &[f64]| values.iter().map(|value| format!("{:.2}", value)).collect::<Vec<String>>();
+
+    HeatmapData {
+        spot_prices: format_all(&grid.spot_prices),
+        volatilities: format_all(&grid.volatilities),
+        call_data: grid.call_prices.chunks(grid.volatilities.len()).map(format_all).collect(),
+        put_data: grid.put_prices.chunks(grid.volatilities.len()).map(format_all).collect(),
+    }
+}
+
+// volatility that reproduces `price`, by Newton's method on vega, falling back to bisection
+// whenever a step would leave the bracket. None if the price is outside the no-arbitrage
+// bounds (so no volatility can reach it) or the search doesn't settle
+pub fn calculate_implied_volatility(price: f64, s: f64, k: f64, r: f64, t: f64, option_type: OptionType) -> Option<f64> {
+    const TOLERANCE: f64 = 1e-8;
+    const MAX_ITERATIONS: usize = 100;
+
+    if !price.is_finite() || t <= 0.0 {
+        return None;
+    }
+
+    let discounted_strike: f64 = k * exp(-r * t);
+    let (lower_bound, upper_bound) = match option_type {
+        OptionType::Call => ((s - discounted_strike).max(0.0), s),
+        OptionType::Put => ((discounted_strike - s).max(0.0), discounted_strike),
+    };
+    if price <= lower_bound || price >= upper_bound {
+        return None;
+    }
+
+    let model_price = |v: f64| match option_type {
+        OptionType::Call => calculate_call_price(s, k, r, v, t),
+        OptionType::Put => calculate_put_price(s, k, r, v, t),
+    };
+
+    // price is increasing in volatility, so [low, high] always brackets the answer
+    let mut low: f64 = 1e-6;
+    let mut high: f64 = 10.0;
+    // Brenner-Subrahmanyam at-the-money approximation as the starting point
+    let mut v: f64 = (sqrt(2.0 * PI / t) * price / s).clamp(low, high);
+
+    for _ in 0..MAX_ITERATIONS {
+        let diff: f64 = model_price(v) - price;
+        if diff.abs() < TOLERANCE {
+            return Some(v);
+        }
+        if diff > 0.0 {
+            high = v;
+        } else {
+            low = v;
+        }
+
+        let d1: f64 = calculate_d1(s, k, r, v, t);
+        let vega: f64 = s * sqrt(t) * normal_pdf(d1);
+        let newton: f64 = v - diff / vega;
+        v = if vega > 1e-12 && newton > low && newton < high { newton } else { 0.5 * (low + high) };
+
+        if high - low < TOLERANCE {
+            return Some(v);
+        }
+    }
+    None
+}
+
+// every output for one option. d1/d2, the discount factor, √t, the pdf and both cdfs are
+// computed once and shared by the call, the put and all the Greeks
+struct OptionValuation {
+    call_price: f64,
+    put_price: f64,
+    call_delta: f64,
+    put_delta: f64,
+    gamma: f64,
+    vega: f64,
+    call_theta: f64,
+    put_theta: f64,
+    call_rho: f64,
+    put_rho: f64,
+}
+
+fn value_option(s: f64, k: f64, r: f64, v: f64, t: f64) -> OptionValuation {
+    // expired or zero-vol inputs go through the single-option functions and their edge cases
+    if t <= 0.0 || v <= 0.0 {
+        let call: Greeks = calculate_greeks(s, k, r, v, t, OptionType::Call);
+        let put: Greeks = calculate_greeks(s, k, r, v, t, OptionType::Put);
+        return OptionValuation {
+            call_price: calculate_call_price(s, k, r, v, t),
+            put_price: calculate_put_price(s, k, r, v, t),
+            call_delta: call.delta,
+            put_delta: put.delta,
+            gamma: call.gamma,
+            vega: call.vega,
+            call_theta: call.theta,
+            put_theta: put.theta,
+            call_rho: call.rho,
+            put_rho: put.rho,
+        };
+    }
+
+    let sqrt_t: f64 = sqrt(t);
+    let vol_sqrt_t: f64 = v * sqrt_t;
+    let discount: f64 = exp(-r * t);
+    let d1: f64 = (ln(s / k) + (r + 0.5 * v * v) * t) / vol_sqrt_t;
+    let d2: f64 = d1 - vol_sqrt_t;
+
+    let pdf_d1: f64 = normal_pdf(d1);
+    let cdf_d1: f64 = normal_cdf(d1);
+    let cdf_d2: f64 = normal_cdf(d2);
+    let discounted_strike: f64 = k * discount;
+    let time_decay: f64 = -s * pdf_d1 * v / (2.0 * sqrt_t);
+
+    OptionValuation {
+        call_price: s * cdf_d1 - discounted_strike * cdf_d2,
+        put_price: discounted_strike * (1.0 - cdf_d2) - s * (1.0 - cdf_d1),
+        call_delta: cdf_d1,
+        put_delta: cdf_d1 - 1.0,
+        gamma: pdf_d1 / (s * vol_sqrt_t),
+        vega: s * sqrt_t * pdf_d1 / 100.0,
+        call_theta: (time_decay - r * discounted_strike * cdf_d2) / 365.0,
+        put_theta: (time_decay + r * discounted_strike * (1.0 - cdf_d2)) / 365.0,
+        call_rho: discounted_strike * t * cdf_d2 / 100.0,
+        put_rho: -discounted_strike * t * (1.0 - cdf_d2) / 100.0,
+    }
+}
+
+// prices and Greeks for every option in the batch, in parallel on the current rayon pool
+// with the `parallel` feature and on the calling thread without it
+pub fn price_batch(columns: &BatchColumns) -> Result<BatchValuation, ValidationError> {
+    price_batch_slices(
+        &columns.spot_price,
+        &columns.strike_price,
+        &columns.risk_free_rate,
+        &columns.volatility,
+        &columns.time_to_maturity,
+    )
+}
+
+// price_batch over borrowed columns, e.g. caller-owned arrays priced in place. all five must
+// be the same length
+pub fn price_batch_slices(
+    spot_price: &[f64],
+    strike_price: &[f64],
+    risk_free_rate: &[f64],
+    volatility: &[f64],
+    time_to_maturity: &[f64],
+) -> Result<BatchValuation, ValidationError> {
+    let len: usize = spot_price.len();
+    for (field, column) in [
+        ("strike_price", strike_price),
+        ("risk_free_rate", risk_free_rate),
+        ("volatility", volatility),
+        ("time_to_maturity", time_to_maturity),
+    ] {
+        if column.len() != len {
+            return Err(ValidationError::new(
+                "length_mismatch",
+                field,
+                format!("{} has {} values but spot_price has {}", field, column.len(), len),
+            ));
+        }
+    }
+    let value_row = |i: usize| value_option(spot_price[i], strike_price[i], risk_free_rate[i], volatility[i], time_to_maturity[i]);
+
+    #[cfg(feature = "parallel")]
+    let valuations: Vec<OptionValuation> = (0..len)
+        .into_par_iter()
+        .with_min_len(BATCH_MIN_LEN)
+        .map(value_row)
+        .collect();
+    #[cfg(not(feature = "parallel"))]
+    let valuations: Vec<OptionValuation> = (0..len).map(value_row).collect();
+
+    // row results back into the columnar response layout
+    let column = |field: fn(&OptionValuation) -> f64| valuations.iter().map(field).collect::<Vec<f64>>();
+    Ok(BatchValuation {
+        call_price: column(|o| o.call_price),
+        put_price: column(|o| o.put_price),
+        call_delta: column(|o| o.call_delta),
+        put_delta: column(|o| o.put_delta),
+        gamma: column(|o| o.gamma),
+        vega: column(|o| o.vega),
+        call_theta: column(|o| o.call_theta),
+        put_theta: column(|o| o.put_theta),
+        call_rho: column(|o| o.call_rho),
+        put_rho: column(|o| o.put_rho),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!((actual - expected).abs() < tolerance, "expected {} ± {}, got {}", expected, tolerance, actual);
+    }
+
+    #[test]
+    fn implied_volatility_recovers_the_reference_volatility() {
+        let call: Option<f64> = calculate_implied_volatility(10.4506, 100.0, 100.0, 0.05, 1.0, OptionType::Call);
+        let put: Option<f64> = calculate_implied_volatility(5.5735, 100.0, 100.0, 0.05, 1.0, OptionType::Put);
+        assert_close(call.unwrap(), 0.2, 1e-5);
+        assert_close(put.unwrap(), 0.2, 1e-5);
+    }
+
+    #[test]
+    fn implied_volatility_round_trips_across_strikes_and_volatilities() {
+        let (s, r, t): (f64, f64, f64) = (100.0, 0.03, 0.5);
+        for k in [60.0, 90.0, 100.0, 110.0, 150.0] {
+            for v in [0.05, 0.2, 0.6, 1.5] {
+                let discounted_strike: f64 = k * exp(-r * t);
+                for (option_type, price, intrinsic) in [
+                    (OptionType::Call, calculate_call_price(s, k, r, v, t), (s - discounted_strike).max(0.0)),
+                    (OptionType::Put, calculate_put_price(s, k, r, v, t), (discounted_strike - s).max(0.0)),
+                ] {
+                    // with no time value left the volatility can't be recovered
+                    if price - intrinsic < 1e-6 {
+                        continue;
+                    }
+                    let implied: f64 = calculate_implied_volatility(price, s, k, r, t, option_type).unwrap();
+                    assert_close(implied, v, 1e-5);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn implied_volatility_falls_back_to_bisection_where_newton_stalls() {
+        // far out of the money at high volatility: the at-the-money starting guess is ~0.08,
+        // where vega is effectively zero and a Newton step goes nowhere
+        let price: f64 = calculate_call_price(100.0, 200.0, 0.0, 1.5, 0.1);
+        let implied: f64 = calculate_implied_volatility(price, 100.0, 200.0, 0.0, 0.1, OptionType::Call).unwrap();
+        assert_close(implied, 1.5, 1e-6);
+    }
+
+    #[test]
+    fn implied_volatility_is_none_outside_the_no_arbitrage_bounds() {
+        // a call is worth at least S - K·e^(-rt) and at most S
+        let intrinsic: f64 = 100.0 - 90.0 * exp(-0.05);
+        assert_eq!(calculate_implied_volatility(intrinsic - 0.01, 100.0, 90.0, 0.05, 1.0, OptionType::Call), None);
+        assert_eq!(calculate_implied_volatility(100.0, 100.0, 90.0, 0.05, 1.0, OptionType::Call), None);
+        assert_eq!(calculate_implied_volatility(f64::NAN, 100.0, 90.0, 0.05, 1.0, OptionType::Put), None);
+        assert_eq!(calculate_implied_volatility(5.0, 100.0, 90.0, 0.05, 0.0, OptionType::Put), None);
+    }
+
+    #[test]
+    fn batch_matches_the_reference_values() {
+        // at the money: S=K=100, r=5%, σ=20%, one year
+        let valuation: BatchValuation = price_batch_slices(&[100.0], &[100.0], &[0.05], &[0.2], &[1.0]).unwrap();
+
+        assert_close(valuation.call_price[0], 10.4506, 1e-4);
+        assert_close(valuation.put_price[0], 5.5735, 1e-4);
+        assert_close(valuation.call_delta[0], 0.6368, 1e-4);
+        assert_close(valuation.put_delta[0], -0.3632, 1e-4);
+        assert_close(valuation.gamma[0], 0.018762, 1e-5);
+        assert_close(valuation.vega[0], 0.37524, 1e-4);
+    }
+
+    #[test]
+    fn batch_matches_the_single_option_functions() {
+        let columns: BatchColumns = BatchColumns {
+            spot_price: vec![80.0, 100.0, 120.0, 100.0],
+            strike_price: vec![100.0, 90.0, 100.0, 100.0],
+            risk_free_rate: vec![0.01, 0.05, -0.01, 0.03],
+            volatility: vec![0.3, 0.15, 0.5, 0.2],
+            time_to_maturity: vec![0.5, 2.0, 0.1, 0.0],
+        };
+        let valuation: BatchValuation = price_batch(&columns).unwrap();
+
+        for i in 0..columns.len() {
+            let (s, k, r, v, t) = (columns.spot_price[i], columns.strike_price[i], columns.risk_free_rate[i],
+                                   columns.volatility[i], columns.time_to_maturity[i]);
+            let call: Greeks = calculate_greeks(s, k, r, v, t, OptionType::Call);
+            let put: Greeks = calculate_greeks(s, k, r, v, t, OptionType::Put);
+
+            assert_close(valuation.call_price[i], calculate_call_price(s, k, r, v, t), 1e-9);
+            assert_close(valuation.put_price[i], calculate_put_price(s, k, r, v, t), 1e-9);
+            assert_close(valuation.call_delta[i], call.delta, 1e-9);
+            assert_close(valuation.gamma[i], call.gamma, 1e-9);
+            assert_close(valuation.call_theta[i], call.theta, 1e-9);
+            assert_close(valuation.put_theta[i], put.theta, 1e-9);
+            assert_close(valuation.call_rho[i], call.rho, 1e-9);
+            assert_close(valuation.put_rho[i], put.rho, 1e-9);
+        }
+        // expired: intrinsic value only
+        assert_eq!(valuation.call_price[3], 0.0);
+        assert_eq!(valuation.put_price[3], 0.0);
+    }
+
+    #[test]
+    fn batch_columns_of_different_lengths_are_rejected() {
+        let err: ValidationError = price_batch_slices(&[100.0, 110.0], &[100.0, 100.0], &[0.05, 0.05], &[0.2], &[1.0, 1.0])
+            .unwrap_err();
+
+        assert_eq!(err.code, "length_mismatch");
+        assert_eq!(err.field, "volatility");
+    }
+}
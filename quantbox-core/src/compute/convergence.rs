@@ -0,0 +1,178 @@
+use crate::compute::black_scholes::{calculate_options_prices};
+use crate::compute::accumulator::Accumulator;
+use crate::compute::parallel_monte_carlo_engine::ParallelMonteCarloEngine;
+use crate::models::black_scholes_models::{BlackScholesResult};
+use crate::models::monte_carlo_models::{ConvergencePoint, ConvergenceRate, ConvergenceRequest,
+                                        ConvergenceResponse, MonteCarloRequest};
+
+// prices the option at step_size, 2 * step_size, ... max_simulations paths and reports each
+// point to `on_point` (with points done / total) as soon as it's computed. returning false
+// from `on_point` stops the analysis, and the response covers the points computed so far.
+//
+// the sample is grown incrementally -- each point only simulates the step_size new paths
+// and merges them into the running statistics, so the whole analysis costs max_simulations paths
+pub fn run_convergence_analysis<F>(req: &ConvergenceRequest, mut on_point: F) -> ConvergenceResponse
+where
+    F: FnMut(&ConvergencePoint, usize, usize) -> bool,
+{
+    let mut convergence_data: Vec<ConvergencePoint> = Vec::new();
+    let total_points: usize = req.max_simulations / req.step_size;
+
+    // get BS reference price
+    let bs_result: BlackScholesResult = calculate_options_prices(
+        req.spot_price,
+        req.strike_price,
+        req.risk_free_rate,
+        req.volatility,
+        req.time_to_expiry,
+    );
+
+    let params: MonteCarloRequest = MonteCarloRequest {
+        kernel: req.kernel,
+        num_threads: req.num_threads,
+        chunk_size: req.chunk_size,
+        ..MonteCarloRequest::new(
+            req.spot_price,
+            req.strike_price,
+            req.time_to_expiry,
+            req.risk_free_rate,
+            req.volatility,
+            req.max_simulations,
+        )
+    };
+
+    let start_time: std::time::Instant = std::time::Instant::now();
+    let mut sample: Accumulator = Accumulator::default();
+
+    // test convergence at different simulation counts
+    for num_sims in (req.step_size..=req.max_simulations).step_by(req.step_size) {
+        sample.merge(&ParallelMonteCarloEngine::simulate(&params, num_sims - sample.count()));
+
+        let call_price: f64 = sample.call.mean();
+        let put_price: f64 = sample.put.mean();
+        let point: ConvergencePoint = ConvergencePoint {
+            num_simulations: sample.count(),
+            call_price,
+            put_price,
+            standard_error: sample.call.standard_error(),
+            put_standard_error: sample.put.standard_error(),
+            call_error: (call_price - bs_result.call_price).abs(),
+            put_error: (put_price - bs_result.put_price).abs(),
+            time_ms: start_time.elapsed().as_millis(),
+        };
+
+        let keep_going: bool = on_point(&point, convergence_data.len() + 1, total_points);
+        convergence_data.push(point);
+
+        if !keep_going {
+            break;
+        }
+    }
+
+    let (final_difference, final_put_difference) = match convergence_data.last() {
+        Some(last_point) => (last_point.call_error, last_point.put_error),
+        None => (0.0, 0.0),
+    };
+
+    let call_convergence_rate: Option<ConvergenceRate> = fit_convergence_rate(
+        convergence_data.iter().map(|p| (p.num_simulations, p.call_error))
+    );
+    let put_convergence_rate: Option<ConvergenceRate> = fit_convergence_rate(
+        convergence_data.iter().map(|p| (p.num_simulations, p.put_error))
+    );
+
+    ConvergenceResponse {
+        convergence_data,
+        black_scholes_reference: bs_result.call_price,
+        black_scholes_put_reference: bs_result.put_price,
+        final_difference,
+        final_put_difference,
+        call_convergence_rate,
+        put_convergence_rate,
+    }
+}
+
+// ordinary least squares on (ln N, ln error). points with zero error have no logarithm
+// and are skipped
+pub fn fit_convergence_rate<I>(points: I) -> Option<ConvergenceRate>
+where
+    I: IntoIterator<Item = (usize, f64)>,
+{
+    let logs: Vec<(f64, f64)> = points.into_iter()
+        .filter(|&(n, error)| n > 0 && error > 0.0 && error.is_finite())
+        .map(|(n, error)| ((n as f64).ln(), error.ln()))
+        .collect();
+
+    if logs.len() < 2 {
+        return None;
+    }
+
+    let count: f64 = logs.len() as f64;
+    let mean_x: f64 = logs.iter().map(|&(x, _)| x).sum::<f64>() / count;
+    let mean_y: f64 = logs.iter().map(|&(_, y)| y).sum::<f64>() / count;
+
+    let sxx: f64 = logs.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum();
+    let sxy: f64 = logs.iter().map(|&(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let syy: f64 = logs.iter().map(|&(_, y)| (y - mean_y).powi(2)).sum();
+
+    if sxx == 0.0 {
+        return None;
+    }
+
+    let slope: f64 = sxy / sxx;
+    let intercept: f64 = mean_y - slope * mean_x;
+    let r_squared: f64 = if syy == 0.0 { 1.0 } else { (sxy * sxy) / (sxx * syy) };
+
+    Some(ConvergenceRate {
+        slope,
+        intercept,
+        r_squared,
+        num_points: logs.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fits_an_exact_power_law() {
+        // error = 3 / √N
+        let rate: ConvergenceRate = fit_convergence_rate(
+            [1_000, 4_000, 16_000, 64_000].map(|n: usize| (n, 3.0 / (n as f64).sqrt()))
+        ).unwrap();
+
+        assert!((rate.slope + 0.5).abs() < 1e-12);
+        assert!((rate.intercept - 3.0f64.ln()).abs() < 1e-12);
+        assert!((rate.r_squared - 1.0).abs() < 1e-12);
+        assert_eq!(rate.num_points, 4);
+    }
+
+    #[test]
+    fn fits_scattered_points_by_least_squares() {
+        // ln N = 0, L, 2L against ln error = 0, -L, -L (L = ln 10): slope -1/2,
+        // intercept -L/6 and r² 3/4 by hand
+        let rate: ConvergenceRate = fit_convergence_rate([(1, 1.0), (10, 0.1), (100, 0.1)]).unwrap();
+        let l: f64 = 10.0f64.ln();
+
+        assert!((rate.slope + 0.5).abs() < 1e-12);
+        assert!((rate.intercept + l / 6.0).abs() < 1e-12);
+        assert!((rate.r_squared - 0.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn skips_points_without_a_logarithm() {
+        let points: [(usize, f64); 5] = [(0, 1.0), (100, 0.0), (200, f64::NAN), (400, 0.1), (1_600, 0.05)];
+        let rate: ConvergenceRate = fit_convergence_rate(points).unwrap();
+
+        assert_eq!(rate.num_points, 2);
+        assert!((rate.slope + 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn needs_two_distinct_sample_sizes() {
+        assert!(fit_convergence_rate([(100, 0.1)]).is_none());
+        assert!(fit_convergence_rate([(100, 0.1), (100, 0.2)]).is_none());
+        assert!(fit_convergence_rate(std::iter::empty()).is_none());
+    }
+}
@@ -0,0 +1,13 @@
+#[cfg(feature = "std")]
+pub mod accumulator;
+pub mod black_scholes;
+#[cfg(feature = "parallel")]
+pub mod convergence;
+#[cfg(feature = "std")]
+pub mod monte_carlo_engine;
+#[cfg(feature = "parallel")]
+pub mod parallel_monte_carlo_engine;
+#[cfg(feature = "parallel")]
+pub mod simd_kernel;
+#[cfg(feature = "std")]
+pub mod statistics;
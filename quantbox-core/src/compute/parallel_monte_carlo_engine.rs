@@ -0,0 +1,405 @@
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+use rand::prelude::*;
+use rand::rng;
+use rand_distr::{Distribution, Normal};
+use crate::compute::accumulator::{Accumulator, PathPayoffs};
+use crate::compute::simd_kernel;
+use crate::models::monte_carlo_models::{ChunkSize, ExecutionStats, MonteCarloRequest, MonteCarloResult, StopReason,
+                                        TargetPrecisionRequest, SimulationKernel, TargetPrecisionResult,
+                                        TargetType, WorkerStats};
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const CHUNK_SIZE: usize = 10_000;
+
+// auto-tuning aims for this many chunks per thread, so a thread that falls behind
+// (or gets descheduled) only holds up a small share of the work
+const AUTO_CHUNKS_PER_THREAD: usize = 8;
+const AUTO_MIN_CHUNK_SIZE: usize = 2_000;
+const AUTO_MAX_CHUNK_SIZE: usize = 250_000;
+
+static ENGINE_CONFIG: OnceLock<ParallelEngineConfig> = OnceLock::new();
+
+// server-wide defaults for requests that don't set num_threads / chunk_size
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default, deny_unknown_fields))]
+pub struct ParallelEngineConfig {
+    // None uses every thread in the pool the engine runs on
+    pub threads_per_request: Option<usize>,
+    pub chunk_size: ChunkSize,
+}
+
+impl Default for ParallelEngineConfig {
+    fn default() -> Self {
+        ParallelEngineConfig {
+            threads_per_request: None,
+            chunk_size: ChunkSize::Fixed(CHUNK_SIZE),
+        }
+    }
+}
+
+// threads and chunk size for one call to simulate, after applying defaults and caps
+struct ExecutionPlan {
+    threads: usize,
+    chunk_size: usize,
+    auto_tuned: bool,
+}
+
+impl ExecutionPlan {
+    fn resolve(params: &MonteCarloRequest, num_paths: usize) -> Self {
+        let config: &ParallelEngineConfig = ParallelMonteCarloEngine::config();
+
+        // never more workers than the pool we're running on has threads
+        let pool_threads: usize = rayon::current_num_threads();
+        let threads: usize = params.num_threads
+            .or(config.threads_per_request)
+            .unwrap_or(pool_threads)
+            .clamp(1, pool_threads);
+
+        match params.chunk_size.unwrap_or(config.chunk_size) {
+            ChunkSize::Fixed(chunk_size) => ExecutionPlan { threads, chunk_size, auto_tuned: false },
+            ChunkSize::Auto => {
+                let chunk_size: usize = num_paths
+                    .div_ceil(threads * AUTO_CHUNKS_PER_THREAD)
+                    .clamp(AUTO_MIN_CHUNK_SIZE, AUTO_MAX_CHUNK_SIZE);
+                ExecutionPlan { threads, chunk_size, auto_tuned: true }
+            }
+        }
+    }
+}
+
+pub struct ParallelMonteCarloEngine;
+
+impl ParallelMonteCarloEngine {
+    // sets the process-wide defaults. they can only be set once, and not after the first
+    // simulation has fixed them at ParallelEngineConfig::default(); a call after that gets its
+    // config back as the error rather than being silently ignored
+    pub fn configure(config: ParallelEngineConfig) -> Result<(), ParallelEngineConfig> {
+        ENGINE_CONFIG.set(config)
+    }
+
+    pub fn config() -> &'static ParallelEngineConfig {
+        ENGINE_CONFIG.get_or_init(ParallelEngineConfig::default)
+    }
+
+    pub fn price_european_option(params: &MonteCarloRequest) -> MonteCarloResult {
+        let (sample, stats) = Self::simulate_with_stats(params, params.num_simulations);
+
+        let mut result: MonteCarloResult = sample.to_result(params);
+        result.execution = Some(stats);
+        result
+    }
+
+    // same estimator, but chunks are run a round at a time and the running estimate is
+    // handed to `on_round` along with the paths simulated so far. returning false from
+    // `on_round` stops early, and the estimate over the paths already run is returned
+    pub fn price_european_option_with_progress<F>(params: &MonteCarloRequest, mut on_round: F) -> MonteCarloResult
+    where
+        F: FnMut(&MonteCarloResult, usize) -> bool,
+    {
+        let plan: ExecutionPlan = ExecutionPlan::resolve(params, params.num_simulations);
+        let paths_per_round: usize = plan.threads * 4 * plan.chunk_size;
+
+        let mut total: Accumulator = Accumulator::default();
+        let mut execution: Option<ExecutionStats> = None;
+        let mut estimate: MonteCarloResult = MonteCarloResult::default();
+
+        while total.count() < params.num_simulations {
+            let round_paths: usize = paths_per_round.min(params.num_simulations - total.count());
+            let (sample, stats) = Self::simulate_with_stats(params, round_paths);
+            total.merge(&sample);
+            Self::merge_stats(&mut execution, &stats);
+
+            estimate = total.to_result(params);
+            estimate.execution = execution.clone();
+            if !on_round(&estimate, total.count()) {
+                break;
+            }
+        }
+
+        estimate
+    }
+
+    // keeps adding rounds of paths until both the call's and the put's standard error reach
+    // the target, the time budget runs out, or max_simulations paths have been used.
+    // `on_round` sees the running estimate after every round, as in the progress variant.
+    //
+    // rounds are sized from the current error -- SE falls like 1/√N, so N·(error/target)²
+    // paths should be enough -- but never more than double the sample so far, and never
+    // more than the remaining time budget looks able to cover at the observed path rate
+    pub fn price_to_target<F>(req: &TargetPrecisionRequest, mut on_round: F) -> TargetPrecisionResult
+    where
+        F: FnMut(&MonteCarloResult, usize) -> bool,
+    {
+        let params: MonteCarloRequest = req.pricing_params();
+        let start_time: std::time::Instant = std::time::Instant::now();
+        let time_budget: Option<std::time::Duration> = req.time_budget_ms.map(std::time::Duration::from_millis);
+        // rounds start at one default-sized chunk per thread; with "auto" each round then
+        // tunes its own chunk size to the round's length
+        let threads: usize = ExecutionPlan::resolve(&params, req.max_simulations).threads;
+        let min_round: usize = threads * CHUNK_SIZE;
+
+        let mut total: Accumulator = Accumulator::default();
+        let mut execution: Option<ExecutionStats> = None;
+        let mut next_round: usize = min_round;
+
+        loop {
+            let round_paths: usize = next_round.min(req.max_simulations - total.count());
+            let (sample, stats) = Self::simulate_with_stats(&params, round_paths);
+            total.merge(&sample);
+            Self::merge_stats(&mut execution, &stats);
+
+            let mut result: MonteCarloResult = total.to_result(&params);
+            result.execution = execution.clone();
+            let error: f64 = Self::precision_error(&total, req.target_type);
+            let keep_going: bool = on_round(&result, total.count());
+            let elapsed: std::time::Duration = start_time.elapsed();
+
+            let stop_reason: Option<StopReason> = if error <= req.target_standard_error {
+                Some(StopReason::TargetMet)
+            } else if total.count() >= req.max_simulations {
+                Some(StopReason::SimulationCapReached)
+            } else if time_budget.is_some_and(|budget| elapsed >= budget) {
+                Some(StopReason::TimeBudgetExhausted)
+            } else if !keep_going {
+                Some(StopReason::Cancelled)
+            } else {
+                None
+            };
+
+            if let Some(stop_reason) = stop_reason {
+                return TargetPrecisionResult {
+                    result,
+                    num_simulations: total.count(),
+                    target_met: stop_reason == StopReason::TargetMet,
+                    stop_reason,
+                    achieved_error: error,
+                    elapsed,
+                };
+            }
+
+            let count: usize = total.count();
+            let predicted_total: f64 = count as f64 * (error / req.target_standard_error).powi(2);
+            let mut wanted: usize = (predicted_total.ceil() as usize).saturating_sub(count);
+            wanted = wanted.clamp(min_round, count.max(min_round));
+
+            if let Some(budget) = time_budget {
+                let secs_per_path: f64 = elapsed.as_secs_f64() / count as f64;
+                let remaining_secs: f64 = (budget - elapsed).as_secs_f64();
+                let affordable: usize = (remaining_secs / secs_per_path) as usize;
+                wanted = wanted.min(affordable.max(CHUNK_SIZE));
+            }
+            next_round = wanted;
+        }
+    }
+
+    // the larger of the call and put errors, in the target's units. a zero price
+    // (e.g. a deep OTM put before any path finished in the money) can't meet a relative target
+    fn precision_error(total: &Accumulator, target_type: TargetType) -> f64 {
+        let call_se: f64 = total.call.standard_error();
+        let put_se: f64 = total.put.standard_error();
+
+        match target_type {
+            TargetType::Absolute => call_se.max(put_se),
+            TargetType::Relative => {
+                let relative = |se: f64, price: f64| if price != 0.0 { se / price.abs() } else { f64::INFINITY };
+                relative(call_se, total.call.mean()).max(relative(put_se, total.put.mean()))
+            }
+        }
+    }
+
+    // simulates `num_paths` fresh paths across the pool, ignoring params.num_simulations.
+    // merge the result into an existing Accumulator to extend a sample
+    pub fn simulate(params: &MonteCarloRequest, num_paths: usize) -> Accumulator {
+        Self::simulate_with_stats(params, num_paths).0
+    }
+
+    // the paths are cut into chunks and `plan.threads` workers pull chunks off a shared
+    // counter until none are left, so a slow thread simply ends up doing fewer of them.
+    // each worker records which pool thread ran it and how long it was busy
+    pub fn simulate_with_stats(params: &MonteCarloRequest, num_paths: usize) -> (Accumulator, ExecutionStats) {
+        let plan: ExecutionPlan = ExecutionPlan::resolve(params, num_paths);
+        let num_chunks: usize = num_paths.div_ceil(plan.chunk_size);
+        let next_chunk: AtomicUsize = AtomicUsize::new(0);
+        let start_time: Instant = Instant::now();
+
+        let (sample, workers) = (0..plan.threads.min(num_chunks).max(1))
+            .into_par_iter()
+            .map(|worker_idx| {
+                let busy_since: Instant = Instant::now();
+                let mut accumulator: Accumulator = Accumulator::default();
+                let mut worker: WorkerStats = WorkerStats {
+                    thread: rayon::current_thread_index().unwrap_or(worker_idx),
+                    chunks: 0,
+                    paths: 0,
+                    busy_ms: 0.0,
+                };
+
+                loop {
+                    let chunk_idx: usize = next_chunk.fetch_add(1, Ordering::Relaxed);
+                    if chunk_idx >= num_chunks {
+                        break;
+                    }
+
+                    let start_sim: usize = chunk_idx * plan.chunk_size;
+                    let end_sim: usize = ((chunk_idx + 1) * plan.chunk_size).min(num_paths);
+                    let actual_chunk_size: usize = end_sim - start_sim;
+
+                    accumulator.merge(&Self::process_chunk(params, actual_chunk_size));
+                    worker.chunks += 1;
+                    worker.paths += actual_chunk_size;
+                }
+
+                worker.busy_ms = busy_since.elapsed().as_secs_f64() * 1000.0;
+                (accumulator, vec![worker])
+            })
+            .reduce(
+                || (Accumulator::default(), Vec::new()),
+                |(mut acc, mut workers), (chunk, chunk_workers)| {
+                    acc.merge(&chunk);
+                    workers.extend(chunk_workers);
+                    (acc, workers)
+                },
+            );
+
+        let wall_time_ms: f64 = start_time.elapsed().as_secs_f64() * 1000.0;
+        let stats: ExecutionStats = ExecutionStats::new(plan.threads, plan.chunk_size, plan.auto_tuned, num_chunks, wall_time_ms, workers);
+        (sample, stats)
+    }
+
+    fn merge_stats(total: &mut Option<ExecutionStats>, round: &ExecutionStats) {
+        match total {
+            Some(total) => total.merge(round),
+            None => *total = Some(round.clone()),
+        }
+    }
+
+    fn process_chunk(params: &MonteCarloRequest, chunk_size: usize) -> Accumulator {
+        match params.kernel {
+            SimulationKernel::Scalar => Self::process_chunk_scalar(params, chunk_size),
+            SimulationKernel::Simd => simd_kernel::simulate_chunk(params, chunk_size),
+        }
+    }
+
+    pub fn process_chunk_scalar(params: &MonteCarloRequest, chunk_size: usize) -> Accumulator {
+        Self::process_chunk_scalar_with_rng(params, chunk_size, &mut rng())
+    }
+
+    // process_chunk_scalar drawing from the given generator, e.g. a seeded one for reproducible runs
+    pub fn process_chunk_scalar_with_rng<R: Rng>(params: &MonteCarloRequest, chunk_size: usize, rng: &mut R) -> Accumulator {
+        let normal: Normal<f64> = Normal::new(0.0, 1.0).unwrap();
+
+        let mut accumulator: Accumulator = Accumulator::default();
+
+        // constants
+        let drift: f64 = (params.risk_free_rate - 0.5 * params.volatility.powi(2)) * params.time_to_expiry;
+        let diffusion: f64 = params.volatility * params.time_to_expiry.sqrt();
+        let payoffs: PathPayoffs = PathPayoffs::new(params);
+
+        for _i in 0..chunk_size {
+            let z: f64 = normal.sample(rng);
+            let final_price: f64 = params.spot_price * (drift + diffusion * z).exp();
+
+            accumulator.record(final_price, &payoffs);
+        }
+
+        accumulator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::monte_carlo_models::{InstrumentStatistics, IntervalMethod};
+
+    // at the money, where Black-Scholes gives 10.4506 for the call and 5.5735 for the put
+    fn request(target_standard_error: f64, max_simulations: usize) -> TargetPrecisionRequest {
+        TargetPrecisionRequest {
+            spot_price: 100.0,
+            strike_price: 100.0,
+            time_to_expiry: 1.0,
+            risk_free_rate: 0.05,
+            volatility: 0.2,
+            target_standard_error,
+            target_type: TargetType::Absolute,
+            time_budget_ms: None,
+            max_simulations,
+            confidence_level: 0.95,
+            interval_method: IntervalMethod::Normal,
+            extra_payoffs: Vec::new(),
+            kernel: SimulationKernel::default(),
+            num_threads: None,
+            chunk_size: None,
+        }
+    }
+
+    #[test]
+    fn stops_once_the_target_is_met() {
+        let result: TargetPrecisionResult = ParallelMonteCarloEngine::price_to_target(&request(0.05, 10_000_000), |_, _| true);
+
+        assert_eq!(result.stop_reason, StopReason::TargetMet);
+        assert!(result.target_met);
+        assert!(result.achieved_error <= 0.05);
+        assert!(result.result.standard_error <= 0.05);
+        // the call's payoffs have a standard deviation of ~14.7, so ~86k paths; rounds can
+        // overshoot by at most the sample so far
+        assert!(result.num_simulations < 4 * 90_000, "used {} paths", result.num_simulations);
+        assert!((result.result.call_price - 10.4506).abs() < 4.0 * result.result.standard_error);
+        assert!((result.result.put_price - 5.5735).abs() < 4.0 * 0.05);
+    }
+
+    #[test]
+    fn relative_targets_apply_to_both_prices() {
+        let req: TargetPrecisionRequest = TargetPrecisionRequest {
+            target_type: TargetType::Relative,
+            ..request(0.01, 10_000_000)
+        };
+        let result: TargetPrecisionResult = ParallelMonteCarloEngine::price_to_target(&req, |_, _| true);
+
+        assert_eq!(result.stop_reason, StopReason::TargetMet);
+        let put: &InstrumentStatistics = &result.result.instruments[1];
+        assert!(put.standard_error / put.price <= 0.01);
+        assert!(result.result.standard_error / result.result.call_price <= 0.01);
+    }
+
+    #[test]
+    fn stops_at_the_simulation_cap() {
+        let result: TargetPrecisionResult = ParallelMonteCarloEngine::price_to_target(&request(1e-4, 50_000), |_, _| true);
+
+        assert_eq!(result.stop_reason, StopReason::SimulationCapReached);
+        assert!(!result.target_met);
+        assert_eq!(result.num_simulations, 50_000);
+        assert!(result.achieved_error > 1e-4);
+    }
+
+    #[test]
+    fn stops_when_the_time_budget_or_the_caller_says_so() {
+        let no_time: TargetPrecisionRequest = TargetPrecisionRequest { time_budget_ms: Some(0), ..request(1e-4, 10_000_000) };
+        let result: TargetPrecisionResult = ParallelMonteCarloEngine::price_to_target(&no_time, |_, _| true);
+        assert_eq!(result.stop_reason, StopReason::TimeBudgetExhausted);
+
+        let mut rounds: usize = 0;
+        let result: TargetPrecisionResult = ParallelMonteCarloEngine::price_to_target(&request(1e-4, 10_000_000), |_, _| {
+            rounds += 1;
+            false
+        });
+        assert_eq!(result.stop_reason, StopReason::Cancelled);
+        assert_eq!(rounds, 1);
+        assert!(result.num_simulations < 10_000_000);
+    }
+
+    #[test]
+    fn configuring_after_the_defaults_are_fixed_is_reported() {
+        ParallelMonteCarloEngine::config();
+        let rejected: ParallelEngineConfig = ParallelMonteCarloEngine::configure(ParallelEngineConfig {
+            threads_per_request: Some(1),
+            chunk_size: ChunkSize::Auto,
+        }).unwrap_err();
+
+        assert_eq!(rejected.threads_per_request, Some(1));
+        assert_eq!(ParallelMonteCarloEngine::config().threads_per_request, None);
+    }
+}
@@ -0,0 +1,140 @@
+// quantile functions for confidence intervals
+
+// inverse of the standard normal CDF (Acklam's rational approximation,
+// relative error below 1.2e-9 over the whole range)
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01, 2.209460984245205e+02, -2.759285104469687e+02,
+        1.38357751867269e+02, -3.066479806614716e+01, 2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01, 1.615858368580409e+02, -1.556989798598866e+02,
+        6.680131188771972e+01, -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03, -3.223964580411365e-01, -2.400758277161838e+00,
+        -2.549732539343734e+00, 4.374664141464968e+00, 2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03, 3.224671290700398e-01, 2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    if p < P_LOW {
+        let q: f64 = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - P_LOW {
+        let q: f64 = p - 0.5;
+        let r: f64 = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        let q: f64 = (-2.0 * (1.0 - p).ln()).sqrt();
+        -(((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    }
+}
+
+// Student's t quantile. exact for 1 and 2 degrees of freedom, Hill's approximation
+// (CACM algorithm 396) above that, good to ~1e-6 relative even at 3 degrees of freedom
+pub fn student_t_quantile(p: f64, degrees_of_freedom: usize) -> f64 {
+    if degrees_of_freedom == 0 || p <= 0.0 || p >= 1.0 {
+        return normal_quantile(p);
+    }
+    if p < 0.5 {
+        return -student_t_quantile(1.0 - p, degrees_of_freedom);
+    }
+    if p == 0.5 {
+        return 0.0;
+    }
+
+    let n: f64 = degrees_of_freedom as f64;
+    match degrees_of_freedom {
+        1 => return (std::f64::consts::PI * (p - 0.5)).tan(),
+        2 => return (2.0 * p - 1.0) / (2.0 * p * (1.0 - p)).sqrt(),
+        _ => {}
+    }
+
+    // Hill works with the two-tailed probability
+    let two_tailed: f64 = 2.0 * (1.0 - p);
+    let a: f64 = 1.0 / (n - 0.5);
+    let b: f64 = 48.0 / (a * a);
+    let mut c: f64 = ((20700.0 * a / b - 98.0) * a - 16.0) * a + 96.36;
+    let d: f64 = ((94.5 / (b + c) - 3.0) / b + 1.0) * (a * std::f64::consts::FRAC_PI_2).sqrt() * n;
+    let mut x: f64 = d * two_tailed;
+    let mut y: f64 = x.powf(2.0 / n);
+
+    if y > 0.05 + a {
+        // far tail relative to n: start from the normal quantile
+        x = normal_quantile(0.5 * two_tailed);
+        y = x * x;
+        if degrees_of_freedom < 5 {
+            c += 0.3 * (n - 4.5) * (x + 0.6);
+        }
+        c += (((0.05 * d * x - 5.0) * x - 7.0) * x - 2.0) * x + b;
+        y = (((((0.4 * y + 6.3) * y + 36.0) * y + 94.5) / c - y - 3.0) / b + 1.0) * x;
+        y = a * y * y;
+        y = if y > 0.002 { y.exp_m1() } else { 0.5 * y * y + y };
+    } else {
+        y = ((1.0 / (((n + 6.0) / (n * y) - 0.089 * d - 0.822) * (n + 2.0) * 3.0) + 0.5 / (n + 4.0)) * y - 1.0)
+            * (n + 1.0) / (n + 2.0) + 1.0 / y;
+    }
+    (n * y).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_relative(actual: f64, expected: f64, tolerance: f64) {
+        assert!(((actual - expected) / expected).abs() < tolerance, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn normal_quantiles_match_the_tables() {
+        assert_eq!(normal_quantile(0.5), 0.0);
+        assert_relative(normal_quantile(0.975), 1.959963985, 1e-8);
+        assert_relative(normal_quantile(0.995), 2.575829304, 1e-8);
+        assert_relative(normal_quantile(0.01), -2.326347874, 1e-8);
+        assert_relative(normal_quantile(1e-9), -5.997807015, 1e-8);
+        assert_eq!(normal_quantile(0.0), f64::NEG_INFINITY);
+        assert_eq!(normal_quantile(1.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn student_t_quantiles_match_the_tables() {
+        // (p, degrees of freedom, quantile)
+        let table: [(f64, usize, f64); 10] = [
+            (0.975, 1, 12.706204736),
+            (0.95, 1, 6.313751515),
+            (0.975, 2, 4.302652730),
+            (0.975, 3, 3.182446305),
+            (0.995, 4, 4.604094871),
+            (0.975, 5, 2.570581836),
+            (0.975, 10, 2.228138852),
+            (0.9995, 3, 12.923978636),
+            (0.975, 30, 2.042272456),
+            (0.975, 1000, 1.962339081),
+        ];
+        for (p, degrees_of_freedom, expected) in table {
+            assert_relative(student_t_quantile(p, degrees_of_freedom), expected, 2e-6);
+            assert_relative(student_t_quantile(1.0 - p, degrees_of_freedom), -expected, 2e-6);
+        }
+        assert_eq!(student_t_quantile(0.5, 7), 0.0);
+    }
+
+    #[test]
+    fn student_t_approaches_the_normal() {
+        assert_relative(student_t_quantile(0.975, 50_000_000), normal_quantile(0.975), 1e-7);
+        assert_eq!(student_t_quantile(0.975, 0), normal_quantile(0.975));
+    }
+}
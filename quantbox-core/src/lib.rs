@@ -0,0 +1,30 @@
+//! Option pricing without the server: analytic Black-Scholes prices, Greeks and implied
+//! volatility, sequential and parallel Monte Carlo engines, and the request/response models
+//! and validation they share with the HTTP API.
+//!
+//! Features:
+//! - `std` (default): the Monte Carlo engines. Without it the crate is `no_std` + `alloc`
+//!   and offers the analytic pricing, the models and validation only.
+//! - `parallel` (default): the rayon-backed parallel engine and convergence studies, and
+//!   parallel batch pricing. Implies `std`.
+//! - `serde`: `Serialize`/`Deserialize` for the models, in the API's JSON shape.
+//! - `openapi`: utoipa `ToSchema` impls for the models, used for the server's OpenAPI spec.
+//!   Implies `std` and `serde`.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod math;
+pub mod compute;
+pub mod models;
+
+pub use compute::black_scholes::{calculate_call_price, calculate_greeks, calculate_heatmap_grid, calculate_implied_volatility,
+                                 calculate_options_prices, calculate_put_price, generate_heatmap_data, price_batch,
+                                 price_batch_slices};
+#[cfg(feature = "std")]
+pub use compute::monte_carlo_engine::MonteCarloEngine;
+#[cfg(feature = "parallel")]
+pub use compute::parallel_monte_carlo_engine::{ParallelEngineConfig, ParallelMonteCarloEngine};
+pub use models::black_scholes_models::{BatchColumns, BatchValuation, BlackScholesResult, Greeks, HeatmapData, HeatmapGrid, OptionType};
+pub use models::monte_carlo_models::{MonteCarloRequest, MonteCarloResult};
+pub use models::validation::{Validate, ValidationError};
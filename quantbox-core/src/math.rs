@@ -0,0 +1,31 @@
+// the few float functions the analytic pricing needs. f64's inherent methods only exist
+// with std, so no_std builds go through libm instead
+#[cfg(feature = "std")]
+pub(crate) fn exp(x: f64) -> f64 {
+    x.exp()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn exp(x: f64) -> f64 {
+    libm::exp(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn ln(x: f64) -> f64 {
+    x.ln()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn ln(x: f64) -> f64 {
+    libm::log(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    x.sqrt()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    libm::sqrt(x)
+}
@@ -0,0 +1,32 @@
+use alloc::vec::Vec;
+use crate::models::black_scholes_models::BlackScholesRequest;
+
+// option inputs laid out column by column; every column has the same length
+#[derive(Debug, Default)]
+pub struct BatchColumns {
+    pub spot_price: Vec<f64>,
+    pub strike_price: Vec<f64>,
+    pub risk_free_rate: Vec<f64>,
+    pub volatility: Vec<f64>,
+    pub time_to_maturity: Vec<f64>,
+}
+
+impl BatchColumns {
+    pub fn from_rows(rows: &[BlackScholesRequest]) -> Self {
+        BatchColumns {
+            spot_price: rows.iter().map(|o| o.spot_price).collect(),
+            strike_price: rows.iter().map(|o| o.strike_price).collect(),
+            risk_free_rate: rows.iter().map(|o| o.risk_free_rate).collect(),
+            volatility: rows.iter().map(|o| o.volatility).collect(),
+            time_to_maturity: rows.iter().map(|o| o.time_to_maturity).collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.spot_price.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spot_price.is_empty()
+    }
+}
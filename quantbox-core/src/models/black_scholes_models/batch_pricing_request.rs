@@ -0,0 +1,144 @@
+use alloc::format;
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+use crate::models::black_scholes_models::{BatchColumns, BlackScholesRequest};
+use crate::models::validation::{Validate, ValidationError, validate_option_inputs};
+
+// keeps one request to a bounded amount of memory and compute time.
+// a server can enforce a lower one of its own
+pub const MAX_BATCH_OPTIONS: usize = 1_000_000;
+
+// either {"options": [{spot_price, strike_price, ...}, ...]}
+// or columnar {"spot_price": [...], "strike_price": [...], "risk_free_rate": [...],
+//              "volatility": [...], "time_to_maturity": [...]}
+// a plain struct rather than an untagged enum, so large bodies aren't buffered twice
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BatchPricingRequest {
+    pub options: Option<Vec<BlackScholesRequest>>,
+    pub spot_price: Option<Vec<f64>>,
+    pub strike_price: Option<Vec<f64>>,
+    pub risk_free_rate: Option<Vec<f64>>,
+    pub volatility: Option<Vec<f64>>,
+    pub time_to_maturity: Option<Vec<f64>>,
+}
+
+impl BatchPricingRequest {
+    fn has_columns(&self) -> bool {
+        self.spot_price.is_some()
+            || self.strike_price.is_some()
+            || self.risk_free_rate.is_some()
+            || self.volatility.is_some()
+            || self.time_to_maturity.is_some()
+    }
+
+    // call after validate() -- missing columns come back empty
+    pub fn into_columns(self) -> BatchColumns {
+        match self.options {
+            Some(options) => BatchColumns::from_rows(&options),
+            None => BatchColumns {
+                spot_price: self.spot_price.unwrap_or_default(),
+                strike_price: self.strike_price.unwrap_or_default(),
+                risk_free_rate: self.risk_free_rate.unwrap_or_default(),
+                volatility: self.volatility.unwrap_or_default(),
+                time_to_maturity: self.time_to_maturity.unwrap_or_default(),
+            },
+        }
+    }
+
+    fn validate_columns(&self) -> Result<(), ValidationError> {
+        let columns: [(&'static str, &Option<Vec<f64>>); 5] = [
+            ("spot_price", &self.spot_price),
+            ("strike_price", &self.strike_price),
+            ("risk_free_rate", &self.risk_free_rate),
+            ("volatility", &self.volatility),
+            ("time_to_maturity", &self.time_to_maturity),
+        ];
+
+        let mut expected_len: Option<usize> = None;
+        for (field, column) in columns {
+            let Some(column) = column else {
+                return Err(ValidationError::new(
+                    "missing_column",
+                    field,
+                    format!("columnar batches need all five columns; {} is missing", field),
+                ));
+            };
+            match expected_len {
+                Some(len) if column.len() != len => {
+                    return Err(ValidationError::new(
+                        "length_mismatch",
+                        field,
+                        format!("{} has {} values but spot_price has {}", field, column.len(), len),
+                    ));
+                }
+                _ => expected_len = Some(column.len()),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Validate for BatchPricingRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        let count: usize = match &self.options {
+            Some(_) if self.has_columns() => {
+                return Err(ValidationError::new(
+                    "ambiguous_batch",
+                    "options",
+                    "send either an options array or the five columns, not both",
+                ));
+            }
+            Some(options) => options.len(),
+            None if self.has_columns() => {
+                self.validate_columns()?;
+                self.spot_price.as_ref().map_or(0, Vec::len)
+            }
+            None => {
+                return Err(ValidationError::new(
+                    "missing_field",
+                    "options",
+                    "send an options array or spot_price/strike_price/risk_free_rate/volatility/time_to_maturity columns",
+                ));
+            }
+        };
+
+        if count == 0 {
+            return Err(ValidationError::new("empty_batch", "options", "the batch has no options to price"));
+        }
+        if count > MAX_BATCH_OPTIONS {
+            return Err(ValidationError::new(
+                "too_many_options",
+                "options",
+                format!("at most {} options per batch, got {}", MAX_BATCH_OPTIONS, count),
+            ));
+        }
+
+        // same rules as the single-option endpoints, with the failing index in the message
+        let at = |index: usize| move |err: ValidationError| ValidationError {
+            message: format!("option {}: {}", index, err.message),
+            ..err
+        };
+        match &self.options {
+            Some(options) => {
+                for (index, option) in options.iter().enumerate() {
+                    option.validate().map_err(at(index))?;
+                }
+            }
+            None => {
+                let (Some(s), Some(k), Some(r), Some(v), Some(t)) =
+                    (&self.spot_price, &self.strike_price, &self.risk_free_rate, &self.volatility, &self.time_to_maturity)
+                else {
+                    unreachable!("validate_columns checked every column is present");
+                };
+                for index in 0..count {
+                    validate_option_inputs(s[index], k[index], r[index], v[index], ("time_to_maturity", t[index]))
+                        .map_err(at(index))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,14 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use crate::models::black_scholes_models::BatchValuation;
+
+// unlike the single-option endpoints, values are not rounded to cents
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BatchPricingResponse {
+    pub count: usize,
+    pub computation_time_ms: u128,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub valuation: BatchValuation,
+}
@@ -0,0 +1,37 @@
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// prices and Greeks for a batch, one array per quantity, in request order.
+// Greeks use the single-option conventions: theta per calendar day, vega and rho per 1%
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BatchValuation {
+    pub call_price: Vec<f64>,
+    pub put_price: Vec<f64>,
+    pub call_delta: Vec<f64>,
+    pub put_delta: Vec<f64>,
+    pub gamma: Vec<f64>,
+    pub vega: Vec<f64>,
+    pub call_theta: Vec<f64>,
+    pub put_theta: Vec<f64>,
+    pub call_rho: Vec<f64>,
+    pub put_rho: Vec<f64>,
+}
+
+impl BatchValuation {
+    // adds the options of a batch priced after this one
+    pub fn append(&mut self, mut other: BatchValuation) {
+        self.call_price.append(&mut other.call_price);
+        self.put_price.append(&mut other.put_price);
+        self.call_delta.append(&mut other.call_delta);
+        self.put_delta.append(&mut other.put_delta);
+        self.gamma.append(&mut other.gamma);
+        self.vega.append(&mut other.vega);
+        self.call_theta.append(&mut other.call_theta);
+        self.put_theta.append(&mut other.put_theta);
+        self.call_rho.append(&mut other.call_rho);
+        self.put_rho.append(&mut other.put_rho);
+    }
+}
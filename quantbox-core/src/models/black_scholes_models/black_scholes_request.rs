@@ -0,0 +1,26 @@
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+use crate::models::validation::{Validate, ValidationError, validate_option_inputs};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BlackScholesRequest {
+    pub spot_price: f64,
+    pub strike_price: f64,
+    pub risk_free_rate: f64,
+    pub volatility: f64,
+    pub time_to_maturity: f64,
+}
+
+impl Validate for BlackScholesRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        validate_option_inputs(
+            self.spot_price,
+            self.strike_price,
+            self.risk_free_rate,
+            self.volatility,
+            ("time_to_maturity", self.time_to_maturity),
+        )
+    }
+}
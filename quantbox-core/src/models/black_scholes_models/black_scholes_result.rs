@@ -0,0 +1,10 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BlackScholesResult {
+    pub call_price: f64,
+    pub put_price: f64,
+}
\ No newline at end of file
@@ -0,0 +1,19 @@
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct GreekRequest {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub scholes: BlackScholesRequest,
+    pub option_type: OptionType,
+}
+
+impl Validate for GreekRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.scholes.validate()
+    }
+}
+
+use crate::models::black_scholes_models::{BlackScholesRequest, OptionType};
+use crate::models::validation::{Validate, ValidationError};
@@ -0,0 +1,15 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct HeatmapData {
+    pub spot_prices: Vec<String>,
+    pub volatilities: Vec<String>,
+    pub call_data: Vec<Vec<String>>,
+    pub put_data: Vec<Vec<String>>,
+}
\ No newline at end of file
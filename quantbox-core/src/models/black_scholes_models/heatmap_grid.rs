@@ -0,0 +1,14 @@
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// unformatted heatmap prices. call_prices and put_prices are row-major, one row per spot
+// price and one column per volatility
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct HeatmapGrid {
+    pub spot_prices: Vec<f64>,
+    pub volatilities: Vec<f64>,
+    pub call_prices: Vec<f64>,
+    pub put_prices: Vec<f64>,
+}
@@ -0,0 +1,28 @@
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+use crate::models::black_scholes_models::OptionType;
+use crate::models::validation::{Validate, ValidationError, require_price, validate_contract_inputs};
+
+// the volatility at which Black-Scholes reproduces market_price
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+pub struct ImpliedVolatilityRequest {
+    pub market_price: f64,
+    pub spot_price: f64,
+    pub strike_price: f64,
+    pub risk_free_rate: f64,
+    pub time_to_maturity: f64,
+    pub option_type: OptionType,
+}
+
+impl Validate for ImpliedVolatilityRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        require_price("market_price", self.market_price)?;
+        validate_contract_inputs(
+            self.spot_price,
+            self.strike_price,
+            self.risk_free_rate,
+            ("time_to_maturity", self.time_to_maturity),
+        )
+    }
+}
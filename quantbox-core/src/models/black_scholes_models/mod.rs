@@ -0,0 +1,26 @@
+mod greeks;
+mod option_type;
+mod heatmap;
+mod heatmap_grid;
+mod black_scholes_request;
+mod greek_request;
+mod black_scholes_result;
+mod batch_pricing_request;
+mod batch_columns;
+mod batch_valuation;
+mod batch_pricing_response;
+mod implied_volatility_request;
+
+
+pub use greeks::Greeks;
+pub use heatmap::HeatmapData;
+pub use heatmap_grid::HeatmapGrid;
+pub use option_type::OptionType;
+pub use black_scholes_request::BlackScholesRequest;
+pub use greek_request::GreekRequest;
+pub use black_scholes_result::BlackScholesResult;
+pub use batch_pricing_request::{BatchPricingRequest, MAX_BATCH_OPTIONS};
+pub use batch_columns::BatchColumns;
+pub use batch_valuation::BatchValuation;
+pub use batch_pricing_response::BatchPricingResponse;
+pub use implied_volatility_request::ImpliedVolatilityRequest;
@@ -0,0 +1,3 @@
+pub mod black_scholes_models;
+pub mod monte_carlo_models;
+pub mod validation;
@@ -0,0 +1,68 @@
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// how many paths each parallel work item simulates. "auto" picks a size from the
+// simulation count and thread count; a number fixes it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkSize {
+    Auto,
+    Fixed(usize),
+}
+
+impl core::str::FromStr for ChunkSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "auto" => Ok(ChunkSize::Auto),
+            other => other.parse::<usize>()
+                .map(ChunkSize::Fixed)
+                .map_err(|_| format!("expected a chunk size or \"auto\", got {:?}", other)),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ChunkSize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ChunkSize::Auto => serializer.serialize_str("auto"),
+            ChunkSize::Fixed(n) => serializer.serialize_u64(*n as u64),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ChunkSize {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Fixed(usize),
+            Named(String),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Fixed(n) => Ok(ChunkSize::Fixed(n)),
+            Repr::Named(name) => name.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+// a positive integer or "auto", matching the serde impls above
+#[cfg(feature = "openapi")]
+impl utoipa::PartialSchema for ChunkSize {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        use utoipa::openapi::schema::{ObjectBuilder, OneOfBuilder, Type};
+
+        OneOfBuilder::new()
+            .item(ObjectBuilder::new().schema_type(Type::Integer).minimum(Some(1)))
+            .item(ObjectBuilder::new().schema_type(Type::String).enum_values(Some(["auto"])))
+            .into()
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl utoipa::ToSchema for ChunkSize {}
@@ -0,0 +1,32 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use crate::models::monte_carlo_models::{ComparisonResult, MonteCarloResponse, PriceDifferences};
+use crate::models::black_scholes_models::{BlackScholesResult};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ComparisonResponse {
+    pub monte_carlo: MonteCarloResponse,
+    pub black_scholes: BlackScholesResult,
+    pub differences: PriceDifferences,
+}
+
+impl ComparisonResponse {
+    pub fn from_comparison(comparison: ComparisonResult, num_simulations: usize, computation_time: core::time::Duration) -> Self {
+        ComparisonResponse {
+            monte_carlo: MonteCarloResponse::from_result(&comparison.monte_carlo, num_simulations, computation_time),
+            differences: PriceDifferences {
+                call_price_diff: comparison.call_price_diff,
+                put_price_diff: comparison.put_price_diff,
+                call_price_diff_percent: if comparison.black_scholes.call_price != 0.0 {
+                    (comparison.call_price_diff / comparison.black_scholes.call_price) * 100.0
+                } else { 0.0 },
+                put_price_diff_percent: if comparison.black_scholes.put_price != 0.0 {
+                    (comparison.put_price_diff / comparison.black_scholes.put_price) * 100.0
+                } else { 0.0 },
+            },
+            black_scholes: comparison.black_scholes,
+        }
+    }
+}
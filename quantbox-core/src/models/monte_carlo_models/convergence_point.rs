@@ -0,0 +1,18 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ConvergencePoint {
+    pub num_simulations: usize,
+    pub call_price: f64,
+    pub put_price: f64,
+    pub standard_error: f64,
+    pub put_standard_error: f64,
+    // absolute distance from the Black-Scholes price
+    pub call_error: f64,
+    pub put_error: f64,
+    // total time spent growing the sample to num_simulations paths
+    pub time_ms: u128,
+}
@@ -0,0 +1,14 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// least-squares fit of ln(error) = intercept + slope * ln(N) over the convergence points.
+// plain Monte Carlo should land near slope = -0.5
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ConvergenceRate {
+    pub slope: f64,
+    pub intercept: f64,
+    pub r_squared: f64,
+    pub num_points: usize,
+}
@@ -0,0 +1,126 @@
+use alloc::format;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+use crate::models::monte_carlo_models::{ChunkSize, SimulationKernel};
+use crate::models::validation::{MAX_CONVERGENCE_POINTS, MAX_STEP_SIZE, Validate, ValidationError, validate_execution_settings, validate_option_inputs,
+                                require_simulation_count};
+
+// the analysis a request gets when it leaves these out: 20 points
+pub const DEFAULT_CONVERGENCE_MAX_SIMULATIONS: usize = 10_000_000;
+pub const DEFAULT_CONVERGENCE_STEP_SIZE: usize = 500_000;
+
+#[cfg(feature = "serde")]
+fn default_max_simulations() -> usize { DEFAULT_CONVERGENCE_MAX_SIMULATIONS }
+
+#[cfg(feature = "serde")]
+fn default_step_size() -> usize { DEFAULT_CONVERGENCE_STEP_SIZE }
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ConvergenceRequest {
+    pub spot_price: f64,
+    pub strike_price: f64,
+    pub time_to_expiry: f64,
+    pub risk_free_rate: f64,
+    pub volatility: f64,
+    #[cfg_attr(feature = "serde", serde(default = "default_max_simulations"))]
+    pub max_simulations: usize,
+    #[cfg_attr(feature = "serde", serde(default = "default_step_size"))]
+    pub step_size: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kernel: SimulationKernel,
+    pub num_threads: Option<usize>,
+    pub chunk_size: Option<ChunkSize>,
+}
+
+impl Validate for ConvergenceRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        validate_option_inputs(
+            self.spot_price,
+            self.strike_price,
+            self.risk_free_rate,
+            self.volatility,
+            ("time_to_expiry", self.time_to_expiry),
+        )?;
+
+        if self.step_size == 0 {
+            return Err(ValidationError::new(
+                "not_positive",
+                "step_size",
+                "step_size must be greater than 0",
+            ));
+        }
+        // every convergence point runs step_size paths or more, so each needs a sample variance
+        require_simulation_count("step_size", self.step_size)?;
+        if self.step_size > MAX_STEP_SIZE {
+            return Err(ValidationError::new(
+                "out_of_range",
+                "step_size",
+                format!("step_size must be at most {}, got {}", MAX_STEP_SIZE, self.step_size),
+            ));
+        }
+        require_simulation_count("max_simulations", self.max_simulations)?;
+        validate_execution_settings(self.num_threads, self.chunk_size)?;
+        if self.max_simulations < self.step_size {
+            return Err(ValidationError::new(
+                "out_of_range",
+                "max_simulations",
+                format!(
+                    "max_simulations ({}) must be at least step_size ({})",
+                    self.max_simulations, self.step_size
+                ),
+            ));
+        }
+        // each point is a round of simulation and an entry in the response, so a tiny
+        // step_size is expensive long before the paths themselves are
+        let points: usize = self.max_simulations / self.step_size;
+        if points > MAX_CONVERGENCE_POINTS {
+            return Err(ValidationError::new(
+                "too_many_points",
+                "step_size",
+                format!(
+                    "max_simulations / step_size gives {} convergence points, at most {} are allowed; use a larger step_size",
+                    points, MAX_CONVERGENCE_POINTS
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(max_simulations: usize, step_size: usize) -> ConvergenceRequest {
+        ConvergenceRequest {
+            spot_price: 100.0,
+            strike_price: 100.0,
+            time_to_expiry: 1.0,
+            risk_free_rate: 0.05,
+            volatility: 0.2,
+            max_simulations,
+            step_size,
+            kernel: SimulationKernel::default(),
+            num_threads: None,
+            chunk_size: None,
+        }
+    }
+
+    #[test]
+    fn steps_and_points_are_capped() {
+        assert!(request(10_000_000, 10_000).validate().is_ok());
+
+        let cases: [(ConvergenceRequest, &str, &str); 4] = [
+            (request(10_000_000, 1), "too_few_simulations", "step_size"),
+            (request(2_000_000, MAX_STEP_SIZE + 1), "out_of_range", "step_size"),
+            (request(1_000, 2_000), "out_of_range", "max_simulations"),
+            (request(10_000_000, 50), "too_many_points", "step_size"),
+        ];
+        for (req, code, field) in cases {
+            let err: ValidationError = req.validate().unwrap_err();
+            assert_eq!((err.code, err.field), (code, field));
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use crate::models::monte_carlo_models::{ConvergencePoint, ConvergenceRate};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ConvergenceResponse {
+    pub convergence_data: Vec<ConvergencePoint>,
+    pub black_scholes_reference: f64,
+    pub black_scholes_put_reference: f64,
+    pub final_difference: f64,
+    pub final_put_difference: f64,
+    // None when fewer than two points have a non-zero error
+    pub call_convergence_rate: Option<ConvergenceRate>,
+    pub put_convergence_rate: Option<ConvergenceRate>,
+}
@@ -0,0 +1,81 @@
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use crate::models::monte_carlo_models::WorkerStats;
+
+// how the parallel engine split a request across the pool. summed over rounds for the
+// progress, target-precision and streaming variants
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ExecutionStats {
+    // workers the request was allowed, after capping at the pool size
+    pub num_threads: usize,
+    // paths per chunk in the latest round
+    pub chunk_size: usize,
+    pub auto_tuned: bool,
+    pub num_chunks: usize,
+    pub wall_time_ms: f64,
+    pub busy_time_ms: f64,
+    // busy time / (num_threads * wall time). well below 1 means threads sat idle,
+    // e.g. because other requests were holding the pool
+    pub parallel_efficiency: f64,
+    // slowest thread's busy time / mean busy time. 1 is a perfect split
+    pub load_imbalance: f64,
+    pub threads: Vec<WorkerStats>,
+}
+
+impl ExecutionStats {
+    pub fn new(num_threads: usize, chunk_size: usize, auto_tuned: bool, num_chunks: usize, wall_time_ms: f64, workers: Vec<WorkerStats>) -> Self {
+        let mut stats: ExecutionStats = ExecutionStats {
+            num_threads,
+            chunk_size,
+            auto_tuned,
+            num_chunks,
+            wall_time_ms,
+            busy_time_ms: 0.0,
+            parallel_efficiency: 0.0,
+            load_imbalance: 0.0,
+            threads: Vec::new(),
+        };
+        stats.add_workers(workers);
+        stats
+    }
+
+    // folds a later round into this one
+    pub fn merge(&mut self, other: &ExecutionStats) {
+        self.num_threads = self.num_threads.max(other.num_threads);
+        self.chunk_size = other.chunk_size;
+        self.auto_tuned = other.auto_tuned;
+        self.num_chunks += other.num_chunks;
+        self.wall_time_ms += other.wall_time_ms;
+        self.add_workers(other.threads.clone());
+    }
+
+    // combines entries for the same pool thread (a thread can pick up more than one
+    // worker when the pool is busy) and refreshes the derived figures
+    fn add_workers(&mut self, workers: Vec<WorkerStats>) {
+        for worker in workers {
+            match self.threads.iter_mut().find(|w| w.thread == worker.thread) {
+                Some(existing) => {
+                    existing.chunks += worker.chunks;
+                    existing.paths += worker.paths;
+                    existing.busy_ms += worker.busy_ms;
+                }
+                None => self.threads.push(worker),
+            }
+        }
+        self.threads.sort_by_key(|w| w.thread);
+
+        self.busy_time_ms = self.threads.iter().map(|w| w.busy_ms).sum();
+        self.parallel_efficiency = if self.wall_time_ms > 0.0 {
+            self.busy_time_ms / (self.num_threads as f64 * self.wall_time_ms)
+        } else {
+            0.0
+        };
+
+        let mean_busy: f64 = self.busy_time_ms / self.threads.len().max(1) as f64;
+        let max_busy: f64 = self.threads.iter().map(|w| w.busy_ms).fold(0.0, f64::max);
+        self.load_imbalance = if mean_busy > 0.0 { max_busy / mean_busy } else { 1.0 };
+    }
+}
@@ -0,0 +1,20 @@
+use alloc::string::String;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct InstrumentStatistics {
+    pub name: String,
+    pub strike: f64,
+    pub price: f64,
+    pub standard_error: f64,
+    // at the request's confidence_level, from the request's interval_method quantile
+    pub confidence_interval: (f64, f64),
+    // standard_error / |price|, None when the price is zero
+    pub relative_error: Option<f64>,
+    // paths whose payoff went into the estimate. every path carries equal weight in plain
+    // iid Monte Carlo, so this is the number of paths that contributed
+    pub effective_sample_size: f64,
+}
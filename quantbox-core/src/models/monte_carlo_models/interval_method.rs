@@ -0,0 +1,14 @@
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+// which quantile the confidence intervals are built from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum IntervalMethod {
+    #[default]
+    Normal,
+    // Student's t with num_simulations - 1 degrees of freedom
+    StudentT,
+}
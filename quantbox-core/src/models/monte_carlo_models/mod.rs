@@ -0,0 +1,46 @@
+mod monte_carlo_request;
+mod monte_carlo_response;
+mod monte_carlo_result;
+mod comparison_result;
+mod comparison_response;
+mod price_differences;
+mod convergence_request;
+mod convergence_response;
+mod convergence_point;
+mod convergence_rate;
+mod stream_request;
+mod interval_method;
+mod payoff_spec;
+mod instrument_statistics;
+mod parity_diagnostics;
+mod target_precision_request;
+mod target_precision_result;
+mod target_precision_response;
+mod simulation_kernel;
+mod chunk_size;
+mod execution_stats;
+mod worker_stats;
+
+
+pub use monte_carlo_request::{DEFAULT_NUM_SIMULATIONS, MonteCarloRequest};
+pub use monte_carlo_response::MonteCarloResponse;
+pub use monte_carlo_result::MonteCarloResult;
+pub use comparison_result::ComparisonResult;
+pub use price_differences::PriceDifferences;
+pub use comparison_response::ComparisonResponse;
+pub use convergence_request::{ConvergenceRequest, DEFAULT_CONVERGENCE_MAX_SIMULATIONS, DEFAULT_CONVERGENCE_STEP_SIZE};
+pub use convergence_response::ConvergenceResponse;
+pub use convergence_point::ConvergencePoint;
+pub use convergence_rate::ConvergenceRate;
+pub use stream_request::StreamRequest;
+pub use interval_method::IntervalMethod;
+pub use payoff_spec::PayoffSpec;
+pub use instrument_statistics::InstrumentStatistics;
+pub use parity_diagnostics::ParityDiagnostics;
+pub use target_precision_request::{TargetPrecisionRequest, TargetType};
+pub use target_precision_result::{StopReason, TargetPrecisionResult};
+pub use target_precision_response::TargetPrecisionResponse;
+pub use simulation_kernel::SimulationKernel;
+pub use chunk_size::ChunkSize;
+pub use execution_stats::ExecutionStats;
+pub use worker_stats::WorkerStats;
@@ -0,0 +1,104 @@
+use alloc::format;
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+use crate::models::monte_carlo_models::{ChunkSize, IntervalMethod, PayoffSpec, SimulationKernel};
+use crate::models::validation::{Validate, ValidationError, validate_execution_settings, validate_option_inputs, require_simulation_count};
+
+// paths when a request doesn't say
+pub const DEFAULT_NUM_SIMULATIONS: usize = 100_000;
+
+#[cfg(feature = "serde")]
+fn default_num_simulations() -> usize { DEFAULT_NUM_SIMULATIONS }
+
+// also the default for target precision requests
+pub(crate) fn default_confidence_level() -> f64 { 0.95 }
+
+const MAX_EXTRA_PAYOFFS: usize = 16;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MonteCarloRequest {
+    pub spot_price: f64,
+    pub strike_price: f64,
+    pub time_to_expiry: f64,
+    pub risk_free_rate: f64,
+    pub volatility: f64,
+    #[cfg_attr(feature = "serde", serde(default = "default_num_simulations"))]
+    pub num_simulations: usize,
+    #[cfg_attr(feature = "serde", serde(default = "default_confidence_level"))]
+    pub confidence_level: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub interval_method: IntervalMethod,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub extra_payoffs: Vec<PayoffSpec>,
+    // only the parallel engine has a SIMD kernel; the sequential engine ignores this
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kernel: SimulationKernel,
+    // parallel engine only. None falls back to QUANTBOX_THREADS_PER_REQUEST / QUANTBOX_CHUNK_SIZE;
+    // num_threads is capped at the compute pool's size so requests can't oversubscribe the cores
+    pub num_threads: Option<usize>,
+    pub chunk_size: Option<ChunkSize>,
+}
+
+impl MonteCarloRequest {
+    // plain call/put pricing request with default statistics settings
+    pub fn new(spot_price: f64, strike_price: f64, time_to_expiry: f64, risk_free_rate: f64, volatility: f64, num_simulations: usize) -> Self {
+        MonteCarloRequest {
+            spot_price,
+            strike_price,
+            time_to_expiry,
+            risk_free_rate,
+            volatility,
+            num_simulations,
+            confidence_level: default_confidence_level(),
+            interval_method: IntervalMethod::default(),
+            extra_payoffs: Vec::new(),
+            kernel: SimulationKernel::default(),
+            num_threads: None,
+            chunk_size: None,
+        }
+    }
+}
+
+impl Validate for MonteCarloRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        validate_option_inputs(
+            self.spot_price,
+            self.strike_price,
+            self.risk_free_rate,
+            self.volatility,
+            ("time_to_expiry", self.time_to_expiry),
+        )?;
+        require_simulation_count("num_simulations", self.num_simulations)?;
+        validate_execution_settings(self.num_threads, self.chunk_size)?;
+
+        if !self.confidence_level.is_finite() || self.confidence_level <= 0.0 || self.confidence_level >= 1.0 {
+            return Err(ValidationError::new(
+                "out_of_range",
+                "confidence_level",
+                format!("confidence_level must be strictly between 0 and 1, got {}", self.confidence_level),
+            ));
+        }
+
+        if self.extra_payoffs.len() > MAX_EXTRA_PAYOFFS {
+            return Err(ValidationError::new(
+                "out_of_range",
+                "extra_payoffs",
+                format!("at most {} extra payoffs per request, got {}", MAX_EXTRA_PAYOFFS, self.extra_payoffs.len()),
+            ));
+        }
+        for payoff in &self.extra_payoffs {
+            let strike: f64 = payoff.strike();
+            if !strike.is_finite() || strike <= 0.0 {
+                return Err(ValidationError::new(
+                    "not_positive",
+                    "extra_payoffs",
+                    format!("extra payoff strikes must be positive, got {}", strike),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
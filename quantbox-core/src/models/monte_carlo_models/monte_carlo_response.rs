@@ -0,0 +1,39 @@
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Serialize};
+use crate::models::monte_carlo_models::{ExecutionStats, InstrumentStatistics, IntervalMethod, MonteCarloResult, ParityDiagnostics};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MonteCarloResponse {
+    pub call_price: f64,
+    pub put_price: f64,
+    pub standard_error: f64,
+    pub confidence_interval_95: (f64, f64),
+    pub num_simulations: usize,
+    pub computation_time_ms: u128,
+    pub confidence_level: f64,
+    pub interval_method: IntervalMethod,
+    pub instruments: Vec<InstrumentStatistics>,
+    pub put_call_parity: Option<ParityDiagnostics>,
+    pub execution: Option<ExecutionStats>,
+}
+
+impl MonteCarloResponse {
+    pub fn from_result(result: &MonteCarloResult, num_simulations: usize, computation_time: core::time::Duration) -> Self {
+        MonteCarloResponse {
+            call_price: result.call_price,
+            put_price: result.put_price,
+            standard_error: result.standard_error,
+            confidence_interval_95: result.confidence_interval_95,
+            num_simulations,
+            computation_time_ms: computation_time.as_millis(),
+            confidence_level: result.confidence_level,
+            interval_method: result.interval_method,
+            instruments: result.instruments.clone(),
+            put_call_parity: result.put_call_parity.clone(),
+            execution: result.execution.clone(),
+        }
+    }
+}
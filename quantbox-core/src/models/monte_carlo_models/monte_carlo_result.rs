@@ -0,0 +1,17 @@
+use alloc::vec::Vec;
+use crate::models::monte_carlo_models::{ExecutionStats, InstrumentStatistics, IntervalMethod, ParityDiagnostics};
+
+#[derive(Debug, Default, Clone)]
+pub struct MonteCarloResult {
+    pub call_price: f64,
+    pub put_price: f64,
+    pub standard_error: f64,
+    pub confidence_interval_95: (f64, f64),
+    pub confidence_level: f64,
+    pub interval_method: IntervalMethod,
+    // call, put, then any extra payoffs in request order
+    pub instruments: Vec<InstrumentStatistics>,
+    pub put_call_parity: Option<ParityDiagnostics>,
+    // thread and chunk breakdown, from the parallel engine only
+    pub execution: Option<ExecutionStats>,
+}
@@ -0,0 +1,17 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// put-call parity C - P = S - K·e^(-rT) checked on the simulated sample. the call and
+// put come from the same paths, so the standard error is that of the per-path
+// difference rather than the two standard errors combined
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ParityDiagnostics {
+    pub simulated_call_minus_put: f64,
+    pub theoretical_call_minus_put: f64,
+    pub difference: f64,
+    pub standard_error: f64,
+    // difference in standard errors; |z| much above 2 points at a biased simulation
+    pub z_score: f64,
+}
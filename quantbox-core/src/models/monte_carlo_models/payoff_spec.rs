@@ -0,0 +1,46 @@
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+// extra payoffs evaluated on the same simulated paths as the main call/put,
+// e.g. {"type": "digital_call", "strike": 105.0}
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum PayoffSpec {
+    Call { strike: f64 },
+    Put { strike: f64 },
+    // pays 1 if the option finishes in the money
+    DigitalCall { strike: f64 },
+    DigitalPut { strike: f64 },
+}
+
+impl PayoffSpec {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PayoffSpec::Call { .. } => "call",
+            PayoffSpec::Put { .. } => "put",
+            PayoffSpec::DigitalCall { .. } => "digital_call",
+            PayoffSpec::DigitalPut { .. } => "digital_put",
+        }
+    }
+
+    pub fn strike(&self) -> f64 {
+        match *self {
+            PayoffSpec::Call { strike }
+            | PayoffSpec::Put { strike }
+            | PayoffSpec::DigitalCall { strike }
+            | PayoffSpec::DigitalPut { strike } => strike,
+        }
+    }
+
+    // undiscounted payoff at expiry
+    pub fn payoff(&self, final_price: f64) -> f64 {
+        match *self {
+            PayoffSpec::Call { strike } => (final_price - strike).max(0.0),
+            PayoffSpec::Put { strike } => (strike - final_price).max(0.0),
+            PayoffSpec::DigitalCall { strike } => if final_price > strike { 1.0 } else { 0.0 },
+            PayoffSpec::DigitalPut { strike } => if final_price < strike { 1.0 } else { 0.0 },
+        }
+    }
+}
@@ -0,0 +1,15 @@
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+// how the parallel engine generates and prices each chunk of paths
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SimulationKernel {
+    // one Normal::sample and one exp per path
+    #[default]
+    Scalar,
+    // batched Box-Muller normals and lane-wise exp/payoffs over f64x4
+    Simd,
+}
@@ -0,0 +1,21 @@
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+use crate::models::monte_carlo_models::{ConvergenceRequest, MonteCarloRequest};
+use crate::models::validation::{Validate, ValidationError};
+
+// first message on the websocket: {"kind": "convergence" | "monte_carlo_parallel", "params": {...}}
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "params", rename_all = "snake_case"))]
+pub enum StreamRequest {
+    Convergence(ConvergenceRequest),
+    MonteCarloParallel(MonteCarloRequest),
+}
+
+impl Validate for StreamRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        match self {
+            StreamRequest::Convergence(req) => req.validate(),
+            StreamRequest::MonteCarloParallel(req) => req.validate(),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use alloc::format;
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+use crate::models::monte_carlo_models::{ChunkSize, IntervalMethod, MonteCarloRequest, PayoffSpec, SimulationKernel};
+use crate::models::validation::{Validate, ValidationError, require_simulation_count};
+#[cfg(feature = "serde")]
+use super::monte_carlo_request::default_confidence_level;
+
+#[cfg(feature = "serde")]
+fn default_max_simulations() -> usize { crate::models::validation::MAX_SIMULATIONS }
+
+// longest a single request may ask to keep simulating for
+const MAX_TIME_BUDGET_MS: u64 = 10 * 60 * 1000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TargetType {
+    // standard error in price units
+    #[default]
+    Absolute,
+    // standard error / price
+    Relative,
+}
+
+// price with however many paths it takes for both the call's and the put's standard error
+// to reach target_standard_error, stopping early at max_simulations or time_budget_ms
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TargetPrecisionRequest {
+    pub spot_price: f64,
+    pub strike_price: f64,
+    pub time_to_expiry: f64,
+    pub risk_free_rate: f64,
+    pub volatility: f64,
+    pub target_standard_error: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub target_type: TargetType,
+    pub time_budget_ms: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(default = "default_max_simulations"))]
+    pub max_simulations: usize,
+    #[cfg_attr(feature = "serde", serde(default = "default_confidence_level"))]
+    pub confidence_level: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub interval_method: IntervalMethod,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub extra_payoffs: Vec<PayoffSpec>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kernel: SimulationKernel,
+    pub num_threads: Option<usize>,
+    pub chunk_size: Option<ChunkSize>,
+}
+
+impl TargetPrecisionRequest {
+    // the equivalent fixed-size request, capped at max_simulations
+    pub fn pricing_params(&self) -> MonteCarloRequest {
+        MonteCarloRequest {
+            confidence_level: self.confidence_level,
+            interval_method: self.interval_method,
+            extra_payoffs: self.extra_payoffs.clone(),
+            kernel: self.kernel,
+            num_threads: self.num_threads,
+            chunk_size: self.chunk_size,
+            ..MonteCarloRequest::new(
+                self.spot_price,
+                self.strike_price,
+                self.time_to_expiry,
+                self.risk_free_rate,
+                self.volatility,
+                self.max_simulations,
+            )
+        }
+    }
+}
+
+impl Validate for TargetPrecisionRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        // max_simulations stands in for num_simulations, so report it under its own name
+        require_simulation_count("max_simulations", self.max_simulations)?;
+        self.pricing_params().validate()?;
+
+        if !self.target_standard_error.is_finite() || self.target_standard_error <= 0.0 {
+            return Err(ValidationError::new(
+                "not_positive",
+                "target_standard_error",
+                format!("target_standard_error must be greater than 0, got {}", self.target_standard_error),
+            ));
+        }
+        if self.target_type == TargetType::Relative && self.target_standard_error >= 1.0 {
+            return Err(ValidationError::new(
+                "out_of_range",
+                "target_standard_error",
+                format!("a relative target_standard_error must be below 1, got {}", self.target_standard_error),
+            ));
+        }
+
+        if let Some(budget) = self.time_budget_ms
+            && (budget == 0 || budget > MAX_TIME_BUDGET_MS) {
+            return Err(ValidationError::new(
+                "out_of_range",
+                "time_budget_ms",
+                format!("time_budget_ms must be between 1 and {}, got {}", MAX_TIME_BUDGET_MS, budget),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use crate::models::monte_carlo_models::{MonteCarloResponse, StopReason, TargetPrecisionRequest,
+                                        TargetPrecisionResult, TargetType};
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TargetPrecisionResponse {
+    // num_simulations here is the number of paths actually used
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub monte_carlo: MonteCarloResponse,
+    pub target_standard_error: f64,
+    pub target_type: TargetType,
+    pub achieved_error: f64,
+    pub target_met: bool,
+    pub stop_reason: StopReason,
+}
+
+impl TargetPrecisionResponse {
+    pub fn from_result(req: &TargetPrecisionRequest, outcome: &TargetPrecisionResult) -> Self {
+        TargetPrecisionResponse {
+            monte_carlo: MonteCarloResponse::from_result(&outcome.result, outcome.num_simulations, outcome.elapsed),
+            target_standard_error: req.target_standard_error,
+            target_type: req.target_type,
+            achieved_error: outcome.achieved_error,
+            target_met: outcome.target_met,
+            stop_reason: outcome.stop_reason,
+        }
+    }
+}
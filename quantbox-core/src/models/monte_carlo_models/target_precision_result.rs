@@ -0,0 +1,25 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use crate::models::monte_carlo_models::MonteCarloResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum StopReason {
+    TargetMet,
+    TimeBudgetExhausted,
+    SimulationCapReached,
+    Cancelled,
+}
+
+#[derive(Debug)]
+pub struct TargetPrecisionResult {
+    pub result: MonteCarloResult,
+    pub num_simulations: usize,
+    pub target_met: bool,
+    pub stop_reason: StopReason,
+    // whichever of the call/put errors is further from the target, in the target's units
+    pub achieved_error: f64,
+    pub elapsed: core::time::Duration,
+}
@@ -0,0 +1,15 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// what one pool thread did for a request
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WorkerStats {
+    // index of the thread within the compute pool
+    pub thread: usize,
+    pub chunks: usize,
+    pub paths: usize,
+    // time spent simulating, excluding waiting to be scheduled
+    pub busy_ms: f64,
+}
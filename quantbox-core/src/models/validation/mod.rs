@@ -0,0 +1,7 @@
+mod validation_error;
+mod validate;
+
+
+pub use validation_error::ValidationError;
+pub use validate::{MAX_CONVERGENCE_POINTS, MAX_SIMULATIONS, MAX_STEP_SIZE, Validate, validate_contract_inputs, validate_execution_settings, validate_option_inputs,
+                   require_price, require_simulation_count};
@@ -0,0 +1,196 @@
+use alloc::format;
+use crate::models::monte_carlo_models::ChunkSize;
+use crate::models::validation::ValidationError;
+
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationError>;
+}
+
+pub fn require_finite(field: &'static str, value: f64) -> Result<(), ValidationError> {
+    if !value.is_finite() {
+        return Err(ValidationError::new(
+            "not_finite",
+            field,
+            format!("{} must be a finite number", field),
+        ));
+    }
+    Ok(())
+}
+
+pub fn require_positive(field: &'static str, value: f64) -> Result<(), ValidationError> {
+    require_finite(field, value)?;
+    if value <= 0.0 {
+        return Err(ValidationError::new(
+            "not_positive",
+            field,
+            format!("{} must be greater than 0, got {}", field, value),
+        ));
+    }
+    Ok(())
+}
+
+// inclusive on both ends
+pub fn require_in_range(field: &'static str, value: f64, min: f64, max: f64) -> Result<(), ValidationError> {
+    require_finite(field, value)?;
+    if value < min || value > max {
+        return Err(ValidationError::new(
+            "out_of_range",
+            field,
+            format!("{} must be between {} and {}, got {}", field, min, max, value),
+        ));
+    }
+    Ok(())
+}
+
+// bounds shared by every pricing request
+const MAX_PRICE: f64 = 1.0e9;
+const MAX_VOLATILITY: f64 = 5.0;
+const MAX_TIME_YEARS: f64 = 100.0;
+const MAX_ABS_RATE: f64 = 1.0;
+
+// hard ceiling on paths per request, to bound the compute time one request can hold a
+// worker for (tens of seconds on one core at this size). the engines stream their payoffs,
+// so memory isn't the limit. a server can enforce a lower one of its own
+pub const MAX_SIMULATIONS: usize = 50_000_000;
+
+// hard ceiling on points per convergence analysis, each of which is a round of simulation
+// and an entry in the response. a server can enforce a lower one of its own
+pub const MAX_CONVERGENCE_POINTS: usize = 100_000;
+
+// a convergence analysis can only stop between points, so this bounds how long a
+// cancelled or timed out one keeps going
+pub const MAX_STEP_SIZE: usize = 1_000_000;
+
+pub fn require_simulation_count(field: &'static str, value: usize) -> Result<(), ValidationError> {
+    // need at least two paths for a sample variance
+    if value < 2 {
+        return Err(ValidationError::new(
+            "too_few_simulations",
+            field,
+            format!("{} must be at least 2, got {}", field, value),
+        ));
+    }
+    if value > MAX_SIMULATIONS {
+        return Err(ValidationError::new(
+            "too_many_simulations",
+            field,
+            format!("{} must be at most {}, got {}", field, MAX_SIMULATIONS, value),
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_option_inputs(
+    spot_price: f64,
+    strike_price: f64,
+    risk_free_rate: f64,
+    volatility: f64,
+    time: (&'static str, f64),
+) -> Result<(), ValidationError> {
+    validate_contract_inputs(spot_price, strike_price, risk_free_rate, time)?;
+    require_positive("volatility", volatility)?;
+    require_in_range("volatility", volatility, f64::MIN_POSITIVE, MAX_VOLATILITY)?;
+    Ok(())
+}
+
+// everything but the volatility, e.g. for implied volatility where it's the unknown
+pub fn validate_contract_inputs(
+    spot_price: f64,
+    strike_price: f64,
+    risk_free_rate: f64,
+    time: (&'static str, f64),
+) -> Result<(), ValidationError> {
+    require_positive("spot_price", spot_price)?;
+    require_in_range("spot_price", spot_price, f64::MIN_POSITIVE, MAX_PRICE)?;
+    require_positive("strike_price", strike_price)?;
+    require_in_range("strike_price", strike_price, f64::MIN_POSITIVE, MAX_PRICE)?;
+    require_in_range("risk_free_rate", risk_free_rate, -MAX_ABS_RATE, MAX_ABS_RATE)?;
+    require_positive(time.0, time.1)?;
+    require_in_range(time.0, time.1, f64::MIN_POSITIVE, MAX_TIME_YEARS)?;
+    Ok(())
+}
+
+pub fn require_price(field: &'static str, value: f64) -> Result<(), ValidationError> {
+    require_positive(field, value)?;
+    require_in_range(field, value, f64::MIN_POSITIVE, MAX_PRICE)
+}
+
+// more workers than this can't be useful on any machine we'd run on
+pub const MAX_THREADS: usize = 1024;
+
+// the parallel engine checks for progress and cancellation between rounds of
+// threads * 4 chunks, so this bounds how long a cancelled run keeps going
+pub const MAX_CHUNK_SIZE: usize = 1_000_000;
+
+// per-request parallel engine overrides; None means the server default
+pub fn validate_execution_settings(num_threads: Option<usize>, chunk_size: Option<ChunkSize>) -> Result<(), ValidationError> {
+    if let Some(threads) = num_threads
+        && !(1..=MAX_THREADS).contains(&threads)
+    {
+        return Err(ValidationError::new(
+            "out_of_range",
+            "num_threads",
+            format!("num_threads must be between 1 and {}, got {}", MAX_THREADS, threads),
+        ));
+    }
+    if let Some(ChunkSize::Fixed(size)) = chunk_size
+        && !(1..=MAX_CHUNK_SIZE).contains(&size)
+    {
+        return Err(ValidationError::new(
+            "out_of_range",
+            "chunk_size",
+            format!("chunk_size must be \"auto\" or between 1 and {}, got {}", MAX_CHUNK_SIZE, size),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code<T>(result: Result<T, ValidationError>) -> (&'static str, &'static str) {
+        let err: ValidationError = result.err().expect("expected a validation error");
+        (err.code, err.field)
+    }
+
+    #[test]
+    fn simulation_counts_are_bounded_on_both_ends() {
+        assert_eq!(code(require_simulation_count("num_simulations", 1)), ("too_few_simulations", "num_simulations"));
+        assert!(require_simulation_count("num_simulations", 2).is_ok());
+        assert!(require_simulation_count("num_simulations", MAX_SIMULATIONS).is_ok());
+        assert_eq!(code(require_simulation_count("num_simulations", MAX_SIMULATIONS + 1)), ("too_many_simulations", "num_simulations"));
+    }
+
+    #[test]
+    fn ranges_are_inclusive_and_reject_non_finite_values() {
+        assert!(require_in_range("volatility", 5.0, 0.0, 5.0).is_ok());
+        assert_eq!(code(require_in_range("volatility", 5.000001, 0.0, 5.0)), ("out_of_range", "volatility"));
+        assert_eq!(code(require_in_range("volatility", f64::NAN, 0.0, 5.0)), ("not_finite", "volatility"));
+        assert_eq!(code(require_positive("spot_price", 0.0)), ("not_positive", "spot_price"));
+        assert_eq!(code(require_positive("spot_price", f64::INFINITY)), ("not_finite", "spot_price"));
+    }
+
+    #[test]
+    fn option_inputs_name_the_first_bad_field() {
+        assert!(validate_option_inputs(100.0, 100.0, 0.05, 0.2, ("time_to_expiry", 1.0)).is_ok());
+        assert!(validate_option_inputs(100.0, 100.0, -MAX_ABS_RATE, MAX_VOLATILITY, ("time_to_expiry", MAX_TIME_YEARS)).is_ok());
+
+        assert_eq!(code(validate_option_inputs(-1.0, 100.0, 0.05, 0.2, ("time_to_expiry", 1.0))), ("not_positive", "spot_price"));
+        assert_eq!(code(validate_option_inputs(100.0, MAX_PRICE * 2.0, 0.05, 0.2, ("time_to_expiry", 1.0))), ("out_of_range", "strike_price"));
+        assert_eq!(code(validate_option_inputs(100.0, 100.0, 1.5, 0.2, ("time_to_expiry", 1.0))), ("out_of_range", "risk_free_rate"));
+        assert_eq!(code(validate_option_inputs(100.0, 100.0, 0.05, 6.0, ("time_to_expiry", 1.0))), ("out_of_range", "volatility"));
+        assert_eq!(code(validate_option_inputs(100.0, 100.0, 0.05, 0.2, ("time_to_maturity", 0.0))), ("not_positive", "time_to_maturity"));
+    }
+
+    #[test]
+    fn execution_settings_are_capped() {
+        assert!(validate_execution_settings(Some(MAX_THREADS), Some(ChunkSize::Fixed(MAX_CHUNK_SIZE))).is_ok());
+        assert!(validate_execution_settings(None, Some(ChunkSize::Auto)).is_ok());
+
+        assert_eq!(code(validate_execution_settings(Some(0), None)), ("out_of_range", "num_threads"));
+        assert_eq!(code(validate_execution_settings(Some(MAX_THREADS + 1), None)), ("out_of_range", "num_threads"));
+        assert_eq!(code(validate_execution_settings(None, Some(ChunkSize::Fixed(0)))), ("out_of_range", "chunk_size"));
+        assert_eq!(code(validate_execution_settings(None, Some(ChunkSize::Fixed(MAX_CHUNK_SIZE + 1)))), ("out_of_range", "chunk_size"));
+    }
+}
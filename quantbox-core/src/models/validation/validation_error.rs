@@ -0,0 +1,22 @@
+use alloc::string::String;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// a request that parsed fine but carries values we can't price with
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ValidationError {
+    pub code: &'static str,
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl ValidationError {
+    pub fn new(code: &'static str, field: &'static str, message: impl Into<String>) -> Self {
+        ValidationError {
+            code,
+            field,
+            message: message.into(),
+        }
+    }
+}
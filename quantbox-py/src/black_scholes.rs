@@ -0,0 +1,101 @@
+use std::borrow::Cow;
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyArrayMethods};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use quantbox_core::compute::black_scholes::{calculate_heatmap_grid, price_batch_slices};
+use quantbox_core::models::black_scholes_models::{BatchValuation, HeatmapGrid, OptionType};
+
+use crate::inputs::{FloatInput, broadcast, parse_option_type, validation_error};
+
+// the batch kernel over broadcast inputs, run on the rayon pool with the GIL released
+fn value_batch(
+    py: Python<'_>,
+    spot_price: &FloatInput<'_>,
+    strike_price: &FloatInput<'_>,
+    risk_free_rate: &FloatInput<'_>,
+    volatility: &FloatInput<'_>,
+    time_to_maturity: &FloatInput<'_>,
+) -> PyResult<BatchValuation> {
+    let [spot, strike, rate, vol, time]: [Cow<'_, [f64]>; 5] = broadcast([
+        ("spot_price", spot_price),
+        ("strike_price", strike_price),
+        ("risk_free_rate", risk_free_rate),
+        ("volatility", volatility),
+        ("time_to_maturity", time_to_maturity),
+    ])?;
+    py.detach(|| price_batch_slices(&spot, &strike, &rate, &vol, &time)).map_err(validation_error)
+}
+
+/// Black-Scholes call and put prices. Each argument is a float64 array or a number; numbers
+/// are broadcast to the arrays' length. Returns a dict with `call_price` and `put_price` arrays.
+#[pyfunction]
+pub fn calculate_options_prices<'py>(
+    py: Python<'py>,
+    spot_price: FloatInput<'py>,
+    strike_price: FloatInput<'py>,
+    risk_free_rate: FloatInput<'py>,
+    volatility: FloatInput<'py>,
+    time_to_maturity: FloatInput<'py>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let valuation: BatchValuation = value_batch(py, &spot_price, &strike_price, &risk_free_rate, &volatility, &time_to_maturity)?;
+
+    let result: Bound<'py, PyDict> = PyDict::new(py);
+    result.set_item("call_price", valuation.call_price.into_pyarray(py))?;
+    result.set_item("put_price", valuation.put_price.into_pyarray(py))?;
+    Ok(result)
+}
+
+/// Black-Scholes Greeks for calls or puts, with the API's conventions: theta per calendar day,
+/// vega and rho per 1%. Returns a dict of `delta`, `gamma`, `theta`, `vega` and `rho` arrays.
+#[pyfunction]
+#[pyo3(signature = (spot_price, strike_price, risk_free_rate, volatility, time_to_maturity, option_type = "call"))]
+pub fn calculate_greeks<'py>(
+    py: Python<'py>,
+    spot_price: FloatInput<'py>,
+    strike_price: FloatInput<'py>,
+    risk_free_rate: FloatInput<'py>,
+    volatility: FloatInput<'py>,
+    time_to_maturity: FloatInput<'py>,
+    option_type: &str,
+) -> PyResult<Bound<'py, PyDict>> {
+    let option_type: OptionType = parse_option_type(option_type)?;
+    let valuation: BatchValuation = value_batch(py, &spot_price, &strike_price, &risk_free_rate, &volatility, &time_to_maturity)?;
+    let (delta, theta, rho) = match option_type {
+        OptionType::Call => (valuation.call_delta, valuation.call_theta, valuation.call_rho),
+        OptionType::Put => (valuation.put_delta, valuation.put_theta, valuation.put_rho),
+    };
+
+    let result: Bound<'py, PyDict> = PyDict::new(py);
+    result.set_item("delta", delta.into_pyarray(py))?;
+    result.set_item("gamma", valuation.gamma.into_pyarray(py))?;
+    result.set_item("theta", theta.into_pyarray(py))?;
+    result.set_item("vega", valuation.vega.into_pyarray(py))?;
+    result.set_item("rho", rho.into_pyarray(py))?;
+    Ok(result)
+}
+
+/// Call and put prices over a 10x10 grid of spot (±40%) and volatility (±80%) around the
+/// given option. Returns `spot_prices` and `volatilities` axes and `call_prices`/`put_prices`
+/// matrices with one row per spot price.
+#[pyfunction]
+pub fn generate_heatmap_data<'py>(
+    py: Python<'py>,
+    spot_price: f64,
+    strike_price: f64,
+    volatility: f64,
+    risk_free_rate: f64,
+    time_to_maturity: f64,
+) -> PyResult<Bound<'py, PyDict>> {
+    let grid: HeatmapGrid = calculate_heatmap_grid(spot_price, strike_price, volatility, risk_free_rate, time_to_maturity);
+    let shape: [usize; 2] = [grid.spot_prices.len(), grid.volatilities.len()];
+    let call_prices: Bound<'py, PyArray2<f64>> = grid.call_prices.into_pyarray(py).reshape(shape)?;
+    let put_prices: Bound<'py, PyArray2<f64>> = grid.put_prices.into_pyarray(py).reshape(shape)?;
+    let spot_prices: Bound<'py, PyArray1<f64>> = grid.spot_prices.into_pyarray(py);
+
+    let result: Bound<'py, PyDict> = PyDict::new(py);
+    result.set_item("spot_prices", spot_prices)?;
+    result.set_item("volatilities", grid.volatilities.into_pyarray(py))?;
+    result.set_item("call_prices", call_prices)?;
+    result.set_item("put_prices", put_prices)?;
+    Ok(result)
+}
@@ -0,0 +1,92 @@
+use std::borrow::Cow;
+use numpy::PyReadonlyArray1;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use quantbox_core::models::black_scholes_models::OptionType;
+use quantbox_core::models::monte_carlo_models::{ChunkSize, SimulationKernel};
+use quantbox_core::models::validation::ValidationError;
+
+// a float64 NumPy array or a plain number, which is broadcast to the arrays' length
+#[derive(FromPyObject)]
+pub enum FloatInput<'py> {
+    Array(PyReadonlyArray1<'py, f64>),
+    Scalar(f64),
+}
+
+impl FloatInput<'_> {
+    fn len(&self) -> Option<usize> {
+        match self {
+            FloatInput::Array(array) => Some(array.as_array().len()),
+            FloatInput::Scalar(_) => None,
+        }
+    }
+
+    // contiguous arrays are borrowed in place; strided views and scalars are copied out
+    fn values(&self, len: usize) -> Cow<'_, [f64]> {
+        match self {
+            FloatInput::Array(array) => match array.as_slice() {
+                Ok(slice) => Cow::Borrowed(slice),
+                Err(_) => Cow::Owned(array.as_array().to_vec()),
+            },
+            FloatInput::Scalar(value) => Cow::Owned(vec![*value; len]),
+        }
+    }
+}
+
+// every input as a column of the same length. arrays must agree on their length; with no
+// arrays at all the result is a single row
+pub fn broadcast<'a, const N: usize>(inputs: [(&'static str, &'a FloatInput<'_>); N]) -> PyResult<[Cow<'a, [f64]>; N]> {
+    let mut len: Option<usize> = None;
+    for (name, input) in &inputs {
+        match (len, input.len()) {
+            (Some(expected), Some(actual)) if expected != actual => {
+                return Err(PyValueError::new_err(format!(
+                    "{} has {} elements but the other arrays have {}",
+                    name, actual, expected
+                )));
+            }
+            (None, Some(actual)) => len = Some(actual),
+            _ => {}
+        }
+    }
+
+    let len: usize = len.unwrap_or(1);
+    Ok(inputs.map(|(_, input)| input.values(len)))
+}
+
+pub fn parse_option_type(option_type: &str) -> PyResult<OptionType> {
+    match option_type.to_ascii_lowercase().as_str() {
+        "call" | "c" => Ok(OptionType::Call),
+        "put" | "p" => Ok(OptionType::Put),
+        other => Err(PyValueError::new_err(format!("option_type must be \"call\" or \"put\", got {:?}", other))),
+    }
+}
+
+// chunk_size as the HTTP API takes it: a path count or "auto"
+#[derive(FromPyObject)]
+pub enum ChunkSizeInput {
+    Fixed(usize),
+    Named(String),
+}
+
+impl ChunkSizeInput {
+    pub fn parse(&self) -> PyResult<ChunkSize> {
+        match self {
+            ChunkSizeInput::Fixed(size) => Ok(ChunkSize::Fixed(*size)),
+            ChunkSizeInput::Named(name) => name.parse().map_err(PyValueError::new_err),
+        }
+    }
+}
+
+pub fn parse_kernel(kernel: &str) -> PyResult<SimulationKernel> {
+    match kernel.to_ascii_lowercase().as_str() {
+        "scalar" => Ok(SimulationKernel::Scalar),
+        "simd" => Ok(SimulationKernel::Simd),
+        other => Err(PyValueError::new_err(format!("kernel must be \"scalar\" or \"simd\", got {:?}", other))),
+    }
+}
+
+// the same checks the HTTP API runs, raised as ValueError("<field>: <message>")
+pub fn validation_error(err: ValidationError) -> PyErr {
+    PyValueError::new_err(format!("{}: {}", err.field, err.message))
+}
@@ -0,0 +1,16 @@
+// Python bindings over quantbox-core, so notebooks price with the same numerics as the server.
+// built into a wheel with maturin; see pyproject.toml
+use pyo3::prelude::*;
+
+mod black_scholes;
+mod inputs;
+mod monte_carlo;
+
+#[pymodule]
+fn quantbox(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(black_scholes::calculate_options_prices, m)?)?;
+    m.add_function(wrap_pyfunction!(black_scholes::calculate_greeks, m)?)?;
+    m.add_function(wrap_pyfunction!(black_scholes::generate_heatmap_data, m)?)?;
+    m.add_function(wrap_pyfunction!(monte_carlo::monte_carlo_price, m)?)?;
+    Ok(())
+}
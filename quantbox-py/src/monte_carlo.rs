@@ -0,0 +1,79 @@
+use std::borrow::Cow;
+use numpy::{IntoPyArray, PyArray2, PyArrayMethods};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use quantbox_core::compute::monte_carlo_engine::MonteCarloEngine;
+use quantbox_core::compute::parallel_monte_carlo_engine::ParallelMonteCarloEngine;
+use quantbox_core::models::monte_carlo_models::{MonteCarloRequest, MonteCarloResult};
+use quantbox_core::models::validation::Validate;
+
+use crate::inputs::{ChunkSizeInput, FloatInput, broadcast, parse_kernel, validation_error};
+
+/// Monte Carlo prices of European calls and puts, one simulation per row of the broadcast
+/// inputs. `parallel` picks the rayon engine (with optional `num_threads`, `chunk_size` and
+/// `kernel` overrides) over the sequential one; the GIL is released while paths are simulated.
+/// Returns a dict of `call_price`, `put_price` and `standard_error` arrays and an (n, 2)
+/// `confidence_interval` array for the call at `confidence_level`.
+#[pyfunction]
+#[pyo3(signature = (
+    spot_price, strike_price, time_to_expiry, risk_free_rate, volatility,
+    num_simulations = 100_000, confidence_level = 0.95, parallel = true,
+    num_threads = None, chunk_size = None, kernel = "scalar",
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn monte_carlo_price<'py>(
+    py: Python<'py>,
+    spot_price: FloatInput<'py>,
+    strike_price: FloatInput<'py>,
+    time_to_expiry: FloatInput<'py>,
+    risk_free_rate: FloatInput<'py>,
+    volatility: FloatInput<'py>,
+    num_simulations: usize,
+    confidence_level: f64,
+    parallel: bool,
+    num_threads: Option<usize>,
+    chunk_size: Option<ChunkSizeInput>,
+    kernel: &str,
+) -> PyResult<Bound<'py, PyDict>> {
+    let [spot, strike, time, rate, vol]: [Cow<'_, [f64]>; 5] = broadcast([
+        ("spot_price", &spot_price),
+        ("strike_price", &strike_price),
+        ("time_to_expiry", &time_to_expiry),
+        ("risk_free_rate", &risk_free_rate),
+        ("volatility", &volatility),
+    ])?;
+
+    let mut requests: Vec<MonteCarloRequest> = Vec::with_capacity(spot.len());
+    for i in 0..spot.len() {
+        let mut request: MonteCarloRequest = MonteCarloRequest::new(spot[i], strike[i], time[i], rate[i], vol[i], num_simulations);
+        request.confidence_level = confidence_level;
+        request.num_threads = num_threads;
+        request.chunk_size = chunk_size.as_ref().map(ChunkSizeInput::parse).transpose()?;
+        request.kernel = parse_kernel(kernel)?;
+        request.validate().map_err(validation_error)?;
+        requests.push(request);
+    }
+
+    let results: Vec<MonteCarloResult> = py.detach(|| {
+        requests.iter()
+            .map(|request| if parallel {
+                ParallelMonteCarloEngine::price_european_option(request)
+            } else {
+                MonteCarloEngine::price_european_option(request)
+            })
+            .collect()
+    });
+
+    let column = |field: fn(&MonteCarloResult) -> f64| results.iter().map(field).collect::<Vec<f64>>();
+    let intervals: Vec<f64> = results.iter()
+        .flat_map(|result| [result.confidence_interval_95.0, result.confidence_interval_95.1])
+        .collect();
+    let confidence_interval: Bound<'py, PyArray2<f64>> = intervals.into_pyarray(py).reshape([results.len(), 2])?;
+
+    let result: Bound<'py, PyDict> = PyDict::new(py);
+    result.set_item("call_price", column(|r| r.call_price).into_pyarray(py))?;
+    result.set_item("put_price", column(|r| r.put_price).into_pyarray(py))?;
+    result.set_item("standard_error", column(|r| r.standard_error).into_pyarray(py))?;
+    result.set_item("confidence_interval", confidence_interval)?;
+    Ok(result)
+}
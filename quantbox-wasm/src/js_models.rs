@@ -0,0 +1,42 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+// shapes the client's black-scholes types already use, so results drop straight into them
+#[wasm_bindgen(typescript_custom_section)]
+const TS_MODELS: &'static str = r#"
+export interface OptionPrices {
+  callPrice: number;
+  putPrice: number;
+}
+
+export interface Greeks {
+  delta: number;
+  gamma: number;
+  theta: number;
+  vega: number;
+  rho: number;
+}
+
+export interface HeatmapData {
+  spotPrices: string[];
+  volatilities: string[];
+  callData: string[][];
+  putData: string[][];
+}
+"#;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionPrices {
+    pub call_price: f64,
+    pub put_price: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeatmapData {
+    pub spot_prices: Vec<String>,
+    pub volatilities: Vec<String>,
+    pub call_data: Vec<Vec<String>>,
+    pub put_data: Vec<Vec<String>>,
+}
@@ -0,0 +1,95 @@
+// the analytic half of quantbox-core for the browser, so the Black-Scholes pages price locally
+// with the server's numerics. build with `wasm-pack build --target web`; Monte Carlo stays on the server
+use quantbox_core::compute::black_scholes;
+use quantbox_core::models::black_scholes_models::{BlackScholesResult, Greeks, OptionType};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+mod js_models;
+
+use js_models::{HeatmapData, OptionPrices};
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    serde_wasm_bindgen::to_value(value).map_err(|err| JsError::new(&err.to_string()))
+}
+
+fn parse_option_type(option_type: &str) -> Result<OptionType, JsError> {
+    match option_type.to_ascii_lowercase().as_str() {
+        "call" => Ok(OptionType::Call),
+        "put" => Ok(OptionType::Put),
+        other => Err(JsError::new(&format!("optionType must be \"Call\" or \"Put\", got {:?}", other))),
+    }
+}
+
+#[wasm_bindgen(js_name = calculateCallPrice)]
+pub fn calculate_call_price(spot_price: f64, strike_price: f64, risk_free_rate: f64, volatility: f64, time_to_maturity: f64) -> f64 {
+    black_scholes::calculate_call_price(spot_price, strike_price, risk_free_rate, volatility, time_to_maturity)
+}
+
+#[wasm_bindgen(js_name = calculatePutPrice)]
+pub fn calculate_put_price(spot_price: f64, strike_price: f64, risk_free_rate: f64, volatility: f64, time_to_maturity: f64) -> f64 {
+    black_scholes::calculate_put_price(spot_price, strike_price, risk_free_rate, volatility, time_to_maturity)
+}
+
+#[wasm_bindgen(js_name = calculateOptionsPrices, unchecked_return_type = "OptionPrices")]
+pub fn calculate_options_prices(
+    spot_price: f64,
+    strike_price: f64,
+    risk_free_rate: f64,
+    volatility: f64,
+    time_to_maturity: f64,
+) -> Result<JsValue, JsError> {
+    let prices: BlackScholesResult =
+        black_scholes::calculate_options_prices(spot_price, strike_price, risk_free_rate, volatility, time_to_maturity);
+    to_js(&OptionPrices {
+        call_price: prices.call_price,
+        put_price: prices.put_price,
+    })
+}
+
+// optionType is "Call" or "Put", as in the client's OptionType enum
+#[wasm_bindgen(js_name = calculateGreeks, unchecked_return_type = "Greeks")]
+pub fn calculate_greeks(
+    spot_price: f64,
+    strike_price: f64,
+    risk_free_rate: f64,
+    volatility: f64,
+    time_to_maturity: f64,
+    option_type: &str,
+) -> Result<JsValue, JsError> {
+    let option_type: OptionType = parse_option_type(option_type)?;
+    let greeks: Greeks =
+        black_scholes::calculate_greeks(spot_price, strike_price, risk_free_rate, volatility, time_to_maturity, option_type);
+    to_js(&greeks)
+}
+
+#[wasm_bindgen(js_name = generateHeatmapData, unchecked_return_type = "HeatmapData")]
+pub fn generate_heatmap_data(
+    spot_price: f64,
+    strike_price: f64,
+    volatility: f64,
+    risk_free_rate: f64,
+    time_to_maturity: f64,
+) -> Result<JsValue, JsError> {
+    let heatmap = black_scholes::generate_heatmap_data(spot_price, strike_price, volatility, risk_free_rate, time_to_maturity);
+    to_js(&HeatmapData {
+        spot_prices: heatmap.spot_prices,
+        volatilities: heatmap.volatilities,
+        call_data: heatmap.call_data,
+        put_data: heatmap.put_data,
+    })
+}
+
+// undefined when the price is outside the no-arbitrage bounds or the search doesn't converge
+#[wasm_bindgen(js_name = calculateImpliedVolatility)]
+pub fn calculate_implied_volatility(
+    price: f64,
+    spot_price: f64,
+    strike_price: f64,
+    risk_free_rate: f64,
+    time_to_maturity: f64,
+    option_type: &str,
+) -> Result<Option<f64>, JsError> {
+    let option_type: OptionType = parse_option_type(option_type)?;
+    Ok(black_scholes::calculate_implied_volatility(price, spot_price, strike_price, risk_free_rate, time_to_maturity, option_type))
+}
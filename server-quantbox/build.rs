@@ -0,0 +1,14 @@
+// compiles proto/quantbox.proto into the tonic service and prost messages under grpc::proto.
+// protoc comes from protoc-bin-vendored so the build doesn't need one installed
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let protoc: std::path::PathBuf = protoc_bin_vendored::protoc_bin_path()?;
+
+    let mut config: tonic_prost_build::Config = tonic_prost_build::Config::new();
+    config.protoc_executable(protoc);
+
+    tonic_prost_build::configure()
+        .compile_with_config(config, &["proto/quantbox.proto"], &["proto"])?;
+
+    println!("cargo:rerun-if-changed=proto/quantbox.proto");
+    Ok(())
+}
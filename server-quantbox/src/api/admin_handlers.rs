@@ -0,0 +1,88 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use crate::api::api_error::{ApiError, ErrorBody};
+use crate::api::app_state::AppState;
+use crate::api::validated_json::ValidatedJson;
+use crate::auth::authenticator::Authenticator;
+use crate::models::auth_models::{ApiKeyInfo, CreateApiKeyRequest, CreatedApiKey, TenantUsage};
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/keys",
+    tag = "admin",
+    request_body = CreateApiKeyRequest,
+    params(("x-admin-token" = String, Header, description = "the configured admin token")),
+    responses(
+        (status = 201, description = "the new key; this is the only time it's returned", body = CreatedApiKey),
+        (status = 401, description = "missing or wrong admin token", body = ErrorBody),
+        (status = 403, description = "the admin API is off", body = ErrorBody),
+        (status = 422, description = "invalid tenant or quota", body = ErrorBody),
+    ),
+)]
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    ValidatedJson(req): ValidatedJson<CreateApiKeyRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let created: CreatedApiKey = state.auth.blocking(move |auth| auth.create_key(&req.tenant, req.daily_path_quota)).await?;
+    tracing::info!(key_id = %created.id, tenant = %created.tenant, "API key created");
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/keys",
+    tag = "admin",
+    params(("x-admin-token" = String, Header, description = "the configured admin token")),
+    responses(
+        (status = 200, description = "every key, revoked ones included, oldest first", body = Vec<ApiKeyInfo>),
+        (status = 401, description = "missing or wrong admin token", body = ErrorBody),
+        (status = 403, description = "the admin API is off", body = ErrorBody),
+    ),
+)]
+pub async fn list_api_keys(State(state): State<AppState>) -> Result<Json<Vec<ApiKeyInfo>>, ApiError> {
+    state.auth.blocking(Authenticator::list_keys).await.map(Json)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/keys/{id}",
+    tag = "admin",
+    params(
+        ("id" = String, Path, description = "key id returned on creation"),
+        ("x-admin-token" = String, Header, description = "the configured admin token"),
+    ),
+    responses(
+        (status = 204, description = "revoked; requests with the key are refused from now on"),
+        (status = 401, description = "missing or wrong admin token", body = ErrorBody),
+        (status = 403, description = "the admin API is off", body = ErrorBody),
+        (status = 404, description = "unknown or already revoked key", body = ErrorBody),
+    ),
+)]
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let revoked: String = id.clone();
+    state.auth.blocking(move |auth| auth.revoke_key(&revoked)).await?;
+    tracing::info!(key_id = %id, "API key revoked");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/usage",
+    tag = "admin",
+    params(("x-admin-token" = String, Header, description = "the configured admin token")),
+    responses(
+        (status = 200, description = "today's usage for every tenant", body = Vec<TenantUsage>),
+        (status = 401, description = "missing or wrong admin token", body = ErrorBody),
+        (status = 403, description = "the admin API is off", body = ErrorBody),
+    ),
+)]
+pub async fn get_all_usage(State(state): State<AppState>) -> Result<Json<Vec<TenantUsage>>, ApiError> {
+    state.auth.blocking(Authenticator::all_usage).await.map(Json)
+}
@@ -0,0 +1,192 @@
+use axum::{
+    Json,
+    extract::rejection::{BytesRejection, JsonRejection},
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+use crate::bulk::bulk_error::BulkError;
+use crate::compute::compute_pool::ComputeError;
+use crate::metrics;
+use crate::models::validation::ValidationError;
+
+// error model for every endpoint:
+//   400 -> body could not be read or deserialized into the request type
+//   401 -> authentication is on and the request has no valid API key or bearer token
+//   403 -> the credentials are fine but don't allow this (e.g. the admin API is off)
+//   404 -> the addressed resource (e.g. a job) doesn't exist or has expired
+//   408 -> a stream ran past its deadline (sent as the stream's error event)
+//   413 -> body is larger than the endpoint's limit
+//   422 -> body deserialized fine but a value fails validation
+//   429 -> the client is over its rate limit or its tenant's daily quota, retry after the given delay
+//   500 -> pricing produced something we can't return (e.g. a non-finite number)
+//   503 -> the compute pool is saturated, retry after the given delay
+#[derive(Debug)]
+pub enum ApiError {
+    BadRequest { code: &'static str, message: String },
+    Unauthorized { code: &'static str, message: String },
+    Forbidden { code: &'static str, message: String },
+    NotFound { code: &'static str, message: String },
+    PayloadTooLarge { message: String },
+    Validation(ValidationError),
+    RateLimited { retry_after_secs: u64 },
+    QuotaExceeded { retry_after_secs: u64, message: String },
+    Internal { code: &'static str, field: Option<&'static str>, message: String },
+    Overloaded { retry_after_secs: u64 },
+    // a stream ran past stream_timeout_secs; only ever sent as a stream's last item
+    TimedOut { message: String },
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorBody {
+    pub code: &'static str,
+    pub field: Option<&'static str>,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn non_finite_result(field: &'static str) -> Self {
+        ApiError::Internal {
+            code: "non_finite_result",
+            field: Some(field),
+            message: format!("computed {} is not a finite number", field),
+        }
+    }
+
+    // the JSON payload on its own, for transports that don't carry an HTTP status (websocket).
+    // every transport goes through here once per error, so rejected input is counted here too
+    pub fn into_body(self) -> ErrorBody {
+        match &self {
+            ApiError::BadRequest { code, .. } => metrics::record_validation_failure(code, ""),
+            ApiError::Validation(err) => metrics::record_validation_failure(err.code, err.field),
+            _ => {}
+        }
+
+        match self {
+            ApiError::BadRequest { code, message } => ErrorBody { code, field: None, message },
+            ApiError::Unauthorized { code, message } => ErrorBody { code, field: None, message },
+            ApiError::Forbidden { code, message } => ErrorBody { code, field: None, message },
+            ApiError::NotFound { code, message } => ErrorBody { code, field: None, message },
+            ApiError::PayloadTooLarge { message } => ErrorBody { code: "payload_too_large", field: None, message },
+            ApiError::Validation(err) => ErrorBody {
+                code: err.code,
+                field: Some(err.field),
+                message: err.message,
+            },
+            ApiError::RateLimited { retry_after_secs } => ErrorBody {
+                code: "rate_limited",
+                field: None,
+                message: format!("too many requests, retry in {}s", retry_after_secs),
+            },
+            ApiError::QuotaExceeded { message, .. } => ErrorBody { code: "quota_exceeded", field: None, message },
+            ApiError::Internal { code, field, message } => ErrorBody { code, field, message },
+            ApiError::Overloaded { retry_after_secs } => ErrorBody {
+                code: "overloaded",
+                field: None,
+                message: format!("compute capacity exhausted, retry in {}s", retry_after_secs),
+            },
+            ApiError::TimedOut { message } => ErrorBody { code: "timed_out", field: None, message },
+        }
+    }
+
+    fn status(&self) -> StatusCode {
+        match self {
+            ApiError::BadRequest { .. } => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized { .. } => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden { .. } => StatusCode::FORBIDDEN,
+            ApiError::NotFound { .. } => StatusCode::NOT_FOUND,
+            ApiError::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::RateLimited { .. } | ApiError::QuotaExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::Overloaded { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::TimedOut { .. } => StatusCode::REQUEST_TIMEOUT,
+        }
+    }
+}
+
+impl From<ValidationError> for ApiError {
+    fn from(err: ValidationError) -> Self {
+        ApiError::Validation(err)
+    }
+}
+
+impl From<ComputeError> for ApiError {
+    fn from(err: ComputeError) -> Self {
+        match err {
+            ComputeError::Saturated { retry_after_secs } => ApiError::Overloaded { retry_after_secs },
+            ComputeError::Failed => ApiError::Internal {
+                code: "compute_failed",
+                field: None,
+                message: "pricing computation failed".to_string(),
+            },
+        }
+    }
+}
+
+impl From<BulkError> for ApiError {
+    fn from(err: BulkError) -> Self {
+        match err {
+            BulkError::Read(message) => ApiError::BadRequest { code: "unreadable_table", message },
+            BulkError::Invalid(err) => ApiError::Validation(err),
+            BulkError::Write(message) => ApiError::Internal { code: "table_write_failed", field: None, message },
+        }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        // the body limit trips while the body is being read, before any JSON is parsed
+        if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            return ApiError::PayloadTooLarge { message: rejection.body_text() };
+        }
+
+        let code: &'static str = match rejection {
+            JsonRejection::JsonSyntaxError(_) => "malformed_json",
+            JsonRejection::JsonDataError(_) => "invalid_body",
+            JsonRejection::MissingJsonContentType(_) => "missing_content_type",
+            _ => "unreadable_body",
+        };
+
+        ApiError::BadRequest {
+            code,
+            message: rejection.body_text(),
+        }
+    }
+}
+
+// raw bodies, e.g. bulk table uploads
+impl From<BytesRejection> for ApiError {
+    fn from(rejection: BytesRejection) -> Self {
+        if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            ApiError::PayloadTooLarge { message: rejection.body_text() }
+        } else {
+            ApiError::BadRequest { code: "unreadable_body", message: rejection.body_text() }
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status: StatusCode = self.status();
+        let retry_after: Option<u64> = match self {
+            ApiError::Overloaded { retry_after_secs }
+            | ApiError::RateLimited { retry_after_secs }
+            | ApiError::QuotaExceeded { retry_after_secs, .. } => Some(retry_after_secs),
+            _ => None,
+        };
+        let unauthorized: bool = matches!(self, ApiError::Unauthorized { .. });
+
+        let body: ErrorBody = self.into_body();
+
+        let mut response: Response = (status, Json(body)).into_response();
+        if let Some(secs) = retry_after {
+            response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(secs));
+        }
+        if unauthorized {
+            response.headers_mut().insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        response
+    }
+}
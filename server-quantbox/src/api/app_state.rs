@@ -0,0 +1,25 @@
+use std::sync::Arc;
+use std::time::Duration;
+use crate::api::rate_limiter::RateLimiter;
+use crate::auth::authenticator::Authenticator;
+use crate::compute::compute_pool::ComputePool;
+use crate::config::simulation_config::SimulationConfig;
+use crate::jobs::job_store::JobStore;
+use crate::service::pricing_service::PricingService;
+
+#[derive(Clone)]
+pub struct AppState {
+    pub compute_pool: Arc<ComputePool>,
+    pub job_store: Arc<JobStore>,
+    // shares compute_pool; the gRPC server holds a clone of the same service
+    pub pricing: PricingService,
+    // per-client request budget, also shared with the gRPC server
+    pub rate_limiter: Arc<RateLimiter>,
+    // API keys, bearer tokens and tenant quotas, also shared with the gRPC server
+    pub auth: Arc<Authenticator>,
+    // configured simulation defaults and caps, applied as requests are extracted
+    pub simulation: SimulationConfig,
+    // the same budget the HTTP timeout layer gives requests. a websocket has outlived that
+    // layer by the time it's upgraded, so it has to wait for its first message within this
+    pub request_timeout: Duration,
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use axum::{
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use crate::api::api_error::ApiError;
+use crate::api::app_state::AppState;
+use crate::api::rate_limiter::admit_request;
+use crate::auth::authenticator::{Authenticator, Tenant};
+use crate::auth::usage_meter::{self, UsageMeter};
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+pub const ADMIN_TOKEN_HEADER: &str = "x-admin-token";
+
+// reachable without credentials: health checks, the docs and /metrics for the scraper.
+// the admin API checks its own token instead
+const PUBLIC_PATHS: [&str; 5] = ["/api/v1/health", "/api/health", "/metrics", "/api/openapi.json", "/api/docs"];
+const ADMIN_PATH: &str = "/api/v1/admin";
+
+fn is_public(path: &str) -> bool {
+    let under = |prefix: &str| path == prefix || path.strip_prefix(prefix).is_some_and(|rest| rest.starts_with('/'));
+    PUBLIC_PATHS.iter().any(|public| under(public)) || under(ADMIN_PATH)
+}
+
+// the tenant for a request's x-api-key or Authorization header, looked up off the async
+// workers. shared with the gRPC server, whose metadata arrives as the same headers
+pub async fn authenticate_headers(authenticator: &Arc<Authenticator>, headers: &header::HeaderMap) -> Result<Option<Tenant>, ApiError> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok()).map(str::to_string);
+    let api_key: Option<String> = header(API_KEY_HEADER);
+    let authorization: Option<String> = header(header::AUTHORIZATION.as_str());
+    authenticator.blocking(move |auth| auth.authenticate(api_key.as_deref(), authorization.as_deref())).await
+}
+
+// with auth enabled, answers 401 without valid credentials and 429 once the tenant's
+// daily quota is used up. the tenant is put on the request for handlers that need it (and
+// the rate limiter), and is charged for whatever the request goes on to simulate. failed
+// attempts still use up their IP's rate limit, so guessing keys is throttled too
+pub async fn authenticate(State(state): State<AppState>, mut request: Request, next: Next) -> Response {
+    if !state.auth.enabled() || is_public(request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    match authenticate_headers(&state.auth, request.headers()).await {
+        Ok(Some(tenant)) => {
+            let meter: UsageMeter = UsageMeter::new(Arc::clone(&state.auth), tenant.clone());
+            request.extensions_mut().insert(tenant);
+            usage_meter::metered(Some(meter), next.run(request)).await
+        }
+        Ok(None) => next.run(request).await,
+        Err(err) => match admit_request(&state.rate_limiter, &request) {
+            Ok(()) => err.into_response(),
+            Err(limited) => limited.into_response(),
+        },
+    }
+}
+
+// guards /api/v1/admin with the configured admin token
+pub async fn require_admin(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let token: Option<&str> = request.headers().get(ADMIN_TOKEN_HEADER).and_then(|value| value.to_str().ok());
+    match state.auth.authorize_admin(token) {
+        Ok(()) => next.run(request).await,
+        Err(err) => err.into_response(),
+    }
+}
@@ -1,61 +1,118 @@
 use axum::{
     Json,
+    extract::State,
     http::StatusCode,
     response::IntoResponse,
 };
-use crate::models::black_scholes_models::{BlackScholesRequest, Greeks, HeatmapData, GreekRequest};
-use crate::compute::black_scholes::*;
+use crate::api::api_error::{ApiError, ErrorBody};
+use crate::api::app_state::AppState;
+use crate::api::validated_json::ValidatedJson;
+use crate::models::black_scholes_models::{BatchColumns, BatchPricingRequest, BatchPricingResponse,
+                                          BlackScholesRequest, BlackScholesResult, Greeks, HeatmapData, GreekRequest};
+use crate::models::response_models::{GreeksResponse, OptionPricesResponse};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 
+// round to cents, refusing NaN/inf instead of panicking in Decimal::from_f64
+fn round_to_cents(field: &'static str, value: f64) -> Result<Decimal, ApiError> {
+    Decimal::from_f64(value)
+        .map(|d: Decimal| d.round_dp(2))
+        .ok_or_else(|| ApiError::non_finite_result(field))
+}
+
+#[utoipa::path(get, path = "/api/v1/health", tag = "health", responses((status = 200, description = "the server is up")))]
 pub async fn health_check() -> impl IntoResponse {
-    println!("health check endpoint hit!!");
     StatusCode::OK
 }
 
-// TODO -- REFACTOR TO JUST USE CALCULATE_OPTIONS_PRICES FUNC
-pub async fn get_options_prices(Json(req): Json<BlackScholesRequest>) -> impl IntoResponse {
-    println!("options endpoint hit");
-    let call: f64 = calculate_call_price(req.spot_price, req.strike_price, req.risk_free_rate, req.volatility, req.time_to_maturity);
-    let put: f64 = calculate_put_price(req.spot_price, req.strike_price, req.risk_free_rate, req.volatility, req.time_to_maturity);
+#[utoipa::path(
+    post,
+    path = "/api/v1/options/price",
+    tag = "black-scholes",
+    request_body = BlackScholesRequest,
+    responses(
+        (status = 200, description = "call and put prices, rounded to cents", body = OptionPricesResponse),
+        (status = 400, description = "unreadable body", body = ErrorBody),
+        (status = 422, description = "invalid option inputs", body = ErrorBody),
+    ),
+)]
+pub async fn get_options_prices(
+    State(state): State<AppState>,
+    ValidatedJson(req): ValidatedJson<BlackScholesRequest>,
+) -> Result<Json<OptionPricesResponse>, ApiError> {
+    let prices: BlackScholesResult = state.pricing.options_prices(&req);
 
-    let call_rounded: Decimal = Decimal::from_f64(call).unwrap().round_dp(2);
-    let put_rounded: Decimal = Decimal::from_f64(put).unwrap().round_dp(2);
-
-    Json(serde_json::json!({
-        "callPrice": call_rounded,
-        "putPrice": put_rounded
+    Ok(Json(OptionPricesResponse {
+        call_price: round_to_cents("call_price", prices.call_price)?,
+        put_price: round_to_cents("put_price", prices.put_price)?,
     }))
 }
 
-pub async fn get_greeks_prices(Json(req): Json<GreekRequest>) -> impl IntoResponse {
-    println!("greeks endpoint hit");
-    let greeks: Greeks = calculate_greeks(
-        req.scholes.spot_price,
-        req.scholes.strike_price,
-        req.scholes.risk_free_rate,
-        req.scholes.volatility,
-        req.scholes.time_to_maturity,
-        req.option_type,
-    );
-
-    Json(serde_json::json!({
-        "delta": Decimal::from_f64(greeks.delta).unwrap().round_dp(2),
-        "gamma": Decimal::from_f64(greeks.gamma).unwrap().round_dp(2),
-        "theta": Decimal::from_f64(greeks.theta).unwrap().round_dp(2),
-        "vega": Decimal::from_f64(greeks.vega).unwrap().round_dp(2),
-        "rho": Decimal::from_f64(greeks.rho).unwrap().round_dp(2)
+#[utoipa::path(
+    post,
+    path = "/api/v1/options/greeks",
+    tag = "black-scholes",
+    request_body = GreekRequest,
+    responses(
+        (status = 200, description = "Greeks for the given option type, rounded to cents", body = GreeksResponse),
+        (status = 400, description = "unreadable body", body = ErrorBody),
+        (status = 422, description = "invalid option inputs", body = ErrorBody),
+    ),
+)]
+pub async fn get_greeks_prices(
+    State(state): State<AppState>,
+    ValidatedJson(req): ValidatedJson<GreekRequest>,
+) -> Result<Json<GreeksResponse>, ApiError> {
+    let greeks: Greeks = state.pricing.greeks(&req);
+
+    Ok(Json(GreeksResponse {
+        delta: round_to_cents("delta", greeks.delta)?,
+        gamma: round_to_cents("gamma", greeks.gamma)?,
+        theta: round_to_cents("theta", greeks.theta)?,
+        vega: round_to_cents("vega", greeks.vega)?,
+        rho: round_to_cents("rho", greeks.rho)?,
     }))
 }
 
-pub async fn get_heatmap_prices(Json(req): Json<BlackScholesRequest>) -> impl IntoResponse {
-    let heatmap: HeatmapData = generate_heatmap_data(
-        req.spot_price,
-        req.strike_price,
-        req.risk_free_rate,
-        req.volatility,
-        req.time_to_maturity,
-    );
+#[utoipa::path(
+    post,
+    path = "/api/v1/options/heatmap",
+    tag = "black-scholes",
+    request_body = BlackScholesRequest,
+    responses(
+        (status = 200, description = "call and put prices over a spot x volatility grid around the inputs", body = HeatmapData),
+        (status = 400, description = "unreadable body", body = ErrorBody),
+        (status = 422, description = "invalid option inputs", body = ErrorBody),
+    ),
+)]
+pub async fn get_heatmap_prices(
+    State(state): State<AppState>,
+    ValidatedJson(req): ValidatedJson<BlackScholesRequest>,
+) -> Json<HeatmapData> {
+    let heatmap: HeatmapData = state.pricing.heatmap(&req);
 
     Json(heatmap)
-}
\ No newline at end of file
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/options/batch",
+    tag = "black-scholes",
+    request_body(content = BatchPricingRequest, description = "an options array or the five input columns"),
+    responses(
+        (status = 200, description = "unrounded prices and Greeks, one array per quantity in request order", body = BatchPricingResponse),
+        (status = 400, description = "unreadable body", body = ErrorBody),
+        (status = 413, description = "body larger than the batch body limit", body = ErrorBody),
+        (status = 422, description = "invalid batch or option inputs", body = ErrorBody),
+        (status = 503, description = "compute pool saturated; see Retry-After", body = ErrorBody),
+    ),
+)]
+pub async fn get_batch_prices(
+    State(state): State<AppState>,
+    ValidatedJson(req): ValidatedJson<BatchPricingRequest>,
+) -> Result<Json<BatchPricingResponse>, ApiError> {
+    let columns: BatchColumns = req.into_columns();
+    let response: BatchPricingResponse = state.pricing.batch(columns).await?;
+
+    Ok(Json(response))
+}
@@ -0,0 +1,78 @@
+use std::time::Instant;
+use axum::{
+    body::Bytes,
+    extract::{State, rejection::BytesRejection},
+    http::{HeaderMap, header},
+    response::IntoResponse,
+};
+use tracing::Span;
+use crate::api::api_error::{ApiError, ErrorBody};
+use crate::api::app_state::AppState;
+use crate::api::validated_query::ValidatedQuery;
+use crate::bulk::bulk_error::BulkError;
+use crate::bulk::table_io::{read_table, write_table};
+use crate::bulk::table_pricing::price_table;
+use crate::models::bulk_models::{BulkFormat, BulkPricingQuery};
+use crate::telemetry::{record_inputs, record_run};
+
+fn header_format(headers: &HeaderMap, name: header::HeaderName) -> Option<BulkFormat> {
+    let value: &str = headers.get(name)?.to_str().ok()?;
+    // Accept may list several types; take the first we can produce
+    value.split(',').find_map(BulkFormat::from_content_type)
+}
+
+// body is a CSV, Arrow IPC or Parquet table of option specs; the response is the same table
+// with price and Greek columns (and implied_volatility, given market prices) appended
+#[utoipa::path(
+    post,
+    path = "/api/v1/options/bulk",
+    tag = "black-scholes",
+    params(
+        ("input" = Option<String>, Query, description = "csv, arrow or parquet; defaults to the Content-Type"),
+        ("output" = Option<String>, Query, description = "csv, arrow or parquet; defaults to the Accept header, then the input format"),
+    ),
+    request_body(
+        content((Vec<u8> = "text/csv"), (Vec<u8> = "application/vnd.apache.arrow.stream"), (Vec<u8> = "application/vnd.apache.parquet")),
+        description = "a table with spot_price, strike_price, risk_free_rate, volatility and time_to_maturity columns. \
+                       other column names can be mapped in the query string, e.g. ?spot_price=S",
+    ),
+    responses(
+        (status = 200, description = "the input table with price and Greek columns appended, in the output format", body = Vec<u8>),
+        (status = 400, description = "unknown format or unreadable table", body = ErrorBody),
+        (status = 413, description = "upload larger than the batch body limit", body = ErrorBody),
+        (status = 422, description = "invalid column mapping or option inputs", body = ErrorBody),
+        (status = 503, description = "compute pool saturated; see Retry-After", body = ErrorBody),
+    ),
+)]
+pub async fn price_table_upload(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    ValidatedQuery(query): ValidatedQuery<BulkPricingQuery>,
+    body: Result<Bytes, BytesRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let body: Bytes = body?;
+    let input: BulkFormat = query.input
+        .or_else(|| header_format(&headers, header::CONTENT_TYPE))
+        .ok_or_else(|| ApiError::BadRequest {
+            code: "unknown_format",
+            message: "set Content-Type to text/csv, application/vnd.apache.arrow.stream or \
+                      application/vnd.apache.parquet, or pass ?input=csv|arrow|parquet".to_string(),
+        })?;
+    let output: BulkFormat = query.output
+        .or_else(|| header_format(&headers, header::ACCEPT))
+        .unwrap_or(input);
+
+    record_inputs(format!("input={:?} output={:?} bytes={}", input, output, body.len()));
+    let max_rows: usize = state.simulation.max_batch_options;
+    let (encoded, computation_time) = state.compute_pool.run_cancellable(move |cancel| {
+        let start_time: Instant = Instant::now();
+        let encoded: Result<Vec<u8>, BulkError> = read_table(body, input)
+            .and_then(|table| price_table(&table, &query.mapping, max_rows, cancel))
+            .and_then(|priced| write_table(&priced, output));
+        (encoded, start_time.elapsed())
+    }).await?;
+    record_run(&Span::current(), "black_scholes_bulk", None, computation_time);
+    let encoded: Vec<u8> = encoded?;
+
+    Ok(([(header::CONTENT_TYPE, output.content_type())], encoded))
+}
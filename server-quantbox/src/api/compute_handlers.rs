@@ -0,0 +1,16 @@
+use axum::{
+    Json,
+    extract::State,
+};
+use crate::api::app_state::AppState;
+use crate::models::compute_models::ComputeStats;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/compute/stats",
+    tag = "compute",
+    responses((status = 200, description = "compute pool size, load and rejections so far", body = ComputeStats)),
+)]
+pub async fn get_compute_stats(State(state): State<AppState>) -> Json<ComputeStats> {
+    Json(state.compute_pool.stats())
+}
@@ -0,0 +1,26 @@
+use axum::{
+    http::{HeaderName, HeaderValue},
+    middleware::map_response,
+    response::Response,
+    routing::MethodRouter,
+};
+use crate::api::app_state::AppState;
+
+// the pre-v1 routes were deprecated on 2026-10-19 (RFC 9745 date, unix seconds)
+// and are removed after the sunset date (RFC 8594 HTTP-date)
+pub const LEGACY_DEPRECATION: &str = "@1792368000";
+pub const LEGACY_SUNSET: &str = "Mon, 19 Apr 2027 00:00:00 GMT";
+
+// serves a pre-v1 route unchanged, adding Deprecation and Sunset headers and a Link
+// to the v1 route that replaces it
+pub fn deprecated_alias(route: MethodRouter<AppState>, successor: &'static str) -> MethodRouter<AppState> {
+    route.layer(map_response(move |mut response: Response| async move {
+        let headers = response.headers_mut();
+        headers.insert(HeaderName::from_static("deprecation"), HeaderValue::from_static(LEGACY_DEPRECATION));
+        headers.insert(HeaderName::from_static("sunset"), HeaderValue::from_static(LEGACY_SUNSET));
+        if let Ok(link) = HeaderValue::from_str(&format!("<{}>; rel=\"successor-version\"", successor)) {
+            headers.insert(axum::http::header::LINK, link);
+        }
+        response
+    }))
+}
@@ -0,0 +1,8 @@
+use axum::Json;
+use utoipa::OpenApi;
+use crate::api::openapi::ApiDoc;
+
+// the OpenAPI 3 document; the interactive reference at /api/docs renders the same spec
+pub async fn get_openapi_spec() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
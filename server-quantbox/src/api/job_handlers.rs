@@ -0,0 +1,101 @@
+use std::sync::Arc;
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use crate::api::api_error::{ApiError, ErrorBody};
+use crate::api::app_state::AppState;
+use crate::api::validated_json::ValidatedJson;
+use crate::auth::authenticator::Tenant;
+use crate::auth::usage_meter::PathCharge;
+use crate::compute::compute_pool::PendingJob;
+use crate::jobs::job_runner::spawn_job;
+use crate::models::job_models::{JobRequest, JobSnapshot};
+
+fn job_not_found(id: &str) -> ApiError {
+    ApiError::NotFound {
+        code: "job_not_found",
+        message: format!("no job with id {} (it may have expired)", id),
+    }
+}
+
+fn tenant_name(tenant: &Option<Extension<Tenant>>) -> Option<&str> {
+    tenant.as_ref().map(|Extension(tenant)| tenant.name.as_str())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/jobs",
+    tag = "jobs",
+    request_body(content = JobRequest, description = "{\"kind\": ..., \"params\": <body of the matching endpoint>}"),
+    responses(
+        (status = 202, description = "job queued; poll the Location header", body = JobSnapshot,
+         headers(("Location" = String, description = "URL of the job"))),
+        (status = 400, description = "unreadable body", body = ErrorBody),
+        (status = 422, description = "invalid params", body = ErrorBody),
+        (status = 503, description = "too many jobs already queued or running; retry after the Retry-After header", body = ErrorBody),
+    ),
+)]
+pub async fn submit_job(
+    State(state): State<AppState>,
+    tenant: Option<Extension<Tenant>>,
+    ValidatedJson(req): ValidatedJson<JobRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let pending: PendingJob = state.compute_pool.admit_job()?;
+    let charge: PathCharge = PathCharge::reserve(req.simulations().unwrap_or(0)).await?;
+    let (snapshot, cancel) = state.job_store.create(req.kind(), tenant_name(&tenant));
+    spawn_job(
+        Arc::clone(&state.compute_pool),
+        pending,
+        charge,
+        Arc::clone(&state.job_store),
+        snapshot.id.clone(),
+        req,
+        cancel,
+    );
+
+    let location: String = format!("/api/v1/jobs/{}", snapshot.id);
+    Ok((StatusCode::ACCEPTED, [(header::LOCATION, location)], Json(snapshot)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/jobs/{id}",
+    tag = "jobs",
+    params(("id" = String, Path, description = "job id returned on submission")),
+    responses(
+        (status = 200, description = "current status, progress and (once finished) the result", body = JobSnapshot),
+        (status = 404, description = "unknown or expired job, or one another tenant submitted", body = ErrorBody),
+    ),
+)]
+pub async fn get_job(
+    State(state): State<AppState>,
+    tenant: Option<Extension<Tenant>>,
+    Path(id): Path<String>,
+) -> Result<Json<JobSnapshot>, ApiError> {
+    state.job_store.get(&id, tenant_name(&tenant))
+        .map(Json)
+        .ok_or_else(|| job_not_found(&id))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/jobs/{id}",
+    tag = "jobs",
+    params(("id" = String, Path, description = "job id returned on submission")),
+    responses(
+        (status = 200, description = "the job, with cancellation requested", body = JobSnapshot),
+        (status = 404, description = "unknown or expired job, or one another tenant submitted", body = ErrorBody),
+    ),
+)]
+pub async fn cancel_job(
+    State(state): State<AppState>,
+    tenant: Option<Extension<Tenant>>,
+    Path(id): Path<String>,
+) -> Result<Json<JobSnapshot>, ApiError> {
+    state.job_store.cancel(&id, tenant_name(&tenant))
+        .map(Json)
+        .ok_or_else(|| job_not_found(&id))
+}
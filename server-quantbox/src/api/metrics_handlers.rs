@@ -0,0 +1,13 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::IntoResponse,
+};
+use crate::api::app_state::AppState;
+use crate::metrics::{METRICS_CONTENT_TYPE, render};
+
+// Prometheus scrape target. covers both transports, since they share the process-wide
+// counters and the compute pool
+pub async fn get_metrics(State(state): State<AppState>) -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, METRICS_CONTENT_TYPE)], render(&state.compute_pool.stats()))
+}
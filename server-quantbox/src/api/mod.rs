@@ -1,3 +1,19 @@
+mod admin_handlers;
+pub mod api_error;
+pub mod app_state;
+pub mod authentication;
 mod black_scholes_handlers;
+mod bulk_handlers;
+mod compute_handlers;
+mod deprecation;
+mod docs_handlers;
+mod job_handlers;
+mod metrics_handlers;
 pub mod routes;
-mod monte_carlo_handlers;
\ No newline at end of file
+mod monte_carlo_handlers;
+pub mod openapi;
+pub mod rate_limiter;
+mod streaming_handlers;
+mod usage_handlers;
+mod validated_json;
+mod validated_query;
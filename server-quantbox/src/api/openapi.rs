@@ -0,0 +1,57 @@
+use utoipa::OpenApi;
+use crate::api::{admin_handlers, black_scholes_handlers, bulk_handlers, compute_handlers, job_handlers,
+                 monte_carlo_handlers, streaming_handlers, usage_handlers};
+
+// the spec served at /api/openapi.json. schemas are collected from the handlers'
+// request and response types, so they can't drift from what the endpoints send
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "QuantBox API",
+        version = "1.0.0",
+        description = "Black-Scholes and Monte Carlo option pricing. Request and response fields are snake_case. \
+                       The pre-v1 routes under /api (e.g. /api/black-scholes/get-options-prices) still work until \
+                       their Sunset date, and point at their v1 replacement in a Link header. \
+                       Bodies over the configured limit are answered with 413, and clients over their rate limit \
+                       with 429 and a Retry-After header. A request that runs past the server's timeout gets 408, \
+                       and any Monte Carlo run it started is stopped. \
+                       When the server has authentication enabled, every endpoint but the health check needs an \
+                       x-api-key header or an Authorization: Bearer token (401 otherwise), and a tenant that has \
+                       used its daily simulated-path quota gets 429 until 00:00 UTC.",
+    ),
+    paths(
+        black_scholes_handlers::health_check,
+        black_scholes_handlers::get_options_prices,
+        black_scholes_handlers::get_greeks_prices,
+        black_scholes_handlers::get_heatmap_prices,
+        black_scholes_handlers::get_batch_prices,
+        bulk_handlers::price_table_upload,
+        monte_carlo_handlers::create_simulation,
+        monte_carlo_handlers::get_monte_carlo_price_target_precision,
+        monte_carlo_handlers::get_monte_carlo_comparison,
+        monte_carlo_handlers::get_monte_carlo_convergence_analysis,
+        streaming_handlers::stream_monte_carlo_price_parallel,
+        streaming_handlers::stream_monte_carlo_convergence,
+        streaming_handlers::stream_monte_carlo_ws,
+        compute_handlers::get_compute_stats,
+        job_handlers::submit_job,
+        job_handlers::get_job,
+        job_handlers::cancel_job,
+        usage_handlers::get_usage,
+        admin_handlers::create_api_key,
+        admin_handlers::list_api_keys,
+        admin_handlers::revoke_api_key,
+        admin_handlers::get_all_usage,
+    ),
+    tags(
+        (name = "black-scholes", description = "analytic pricing, Greeks, heatmaps and batch/bulk pricing"),
+        (name = "monte-carlo", description = "Monte Carlo pricing on the compute pool"),
+        (name = "streaming", description = "incremental Monte Carlo results over SSE and WebSocket"),
+        (name = "jobs", description = "asynchronous pricing jobs"),
+        (name = "compute", description = "compute pool status"),
+        (name = "usage", description = "the calling tenant's usage and quota"),
+        (name = "admin", description = "API keys and usage across tenants, behind the admin token"),
+        (name = "health"),
+    ),
+)]
+pub struct ApiDoc;
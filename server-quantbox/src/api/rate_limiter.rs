@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use crate::api::api_error::ApiError;
+use crate::api::app_state::AppState;
+use crate::auth::authenticator::Tenant;
+use crate::config::rate_limit_config::RateLimitConfig;
+
+pub const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+// past this many clients, buckets that have refilled are dropped before adding another --
+// a full bucket behaves the same as no bucket at all
+const MAX_TRACKED_CLIENTS: usize = 100_000;
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+// one token bucket per client: each request takes a token, and tokens come back at
+// requests_per_second up to burst. shared by the REST and gRPC servers
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        RateLimiter {
+            config,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // takes a token from the client's bucket, or says how long until there's one to take
+    pub fn check(&self, client: &str) -> Result<(), Duration> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        let now: Instant = Instant::now();
+        let capacity: f64 = f64::from(self.config.burst);
+        let rate: f64 = self.config.requests_per_second;
+        let refilled = |bucket: &Bucket| (bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * rate).min(capacity);
+
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(client) {
+            buckets.retain(|_, bucket| refilled(bucket) < capacity);
+        }
+        let bucket: &mut Bucket = buckets.entry(client.to_string())
+            .or_insert(Bucket { tokens: capacity, updated: now });
+        bucket.tokens = refilled(bucket);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        }
+    }
+
+    // takes a token for a request, answering 429 once its client's bucket is empty
+    pub fn admit(&self, tenant: Option<&Tenant>, forwarded_for: Option<&str>, peer: Option<IpAddr>) -> Result<(), ApiError> {
+        let client: String = self.client_key(tenant, forwarded_for, peer);
+        self.check(&client)
+            .map_err(|wait| ApiError::RateLimited { retry_after_secs: retry_after_secs(wait) })
+    }
+
+    // the bucket a request is counted against: its tenant once it's authenticated, so every
+    // key and token of a tenant share one budget and made-up keys don't get fresh buckets.
+    // anything else goes by IP. X-Forwarded-For is only used when configured, since anyone
+    // can send it; the peer address is missing for in-process requests
+    pub fn client_key(&self, tenant: Option<&Tenant>, forwarded_for: Option<&str>, peer: Option<IpAddr>) -> String {
+        if let Some(tenant) = tenant {
+            return format!("tenant:{}", tenant.name);
+        }
+        let forwarded: Option<IpAddr> = forwarded_for
+            .filter(|_| self.config.trust_forwarded_for)
+            .and_then(|value| value.split(',').next())
+            .and_then(|first| first.trim().parse().ok());
+        match forwarded.or(peer) {
+            Some(ip) => format!("ip:{}", ip),
+            None => "unknown".to_string(),
+        }
+    }
+}
+
+// whole seconds for Retry-After, rounded up so a client that waits that long gets through
+pub fn retry_after_secs(wait: Duration) -> u64 {
+    (wait.as_secs_f64().ceil() as u64).max(1)
+}
+
+// takes a token for a REST request, keyed on the tenant authentication put on it, if any
+pub fn admit_request(rate_limiter: &RateLimiter, request: &Request) -> Result<(), ApiError> {
+    let forwarded_for: Option<&str> = request.headers().get(FORWARDED_FOR_HEADER).and_then(|value| value.to_str().ok());
+    let peer: Option<IpAddr> = request.extensions().get::<ConnectInfo<SocketAddr>>().map(|ConnectInfo(addr)| addr.ip());
+    rate_limiter.admit(request.extensions().get::<Tenant>(), forwarded_for, peer)
+}
+
+// answers 429 with Retry-After once the client's bucket is empty. runs after authentication,
+// which also sends requests it turns away through here, by IP
+pub async fn rate_limit(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    match admit_request(&state.rate_limiter, &request) {
+        Ok(()) => next.run(request).await,
+        Err(err) => err.into_response(),
+    }
+}
@@ -1,32 +1,158 @@
 use axum::{
-    routing::{get, post},
+    extract::DefaultBodyLimit,
+    http::{HeaderName, HeaderValue, StatusCode},
+    middleware,
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
     Router,
 };
-use tower_http::cors::{CorsLayer, Any};
+use tower::ServiceBuilder;
+use tower_http::LatencyUnit;
+use tower_http::catch_panic::CatchPanicLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer, Any};
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::timeout::TimeoutLayer;
+use tower_http::trace::{DefaultOnResponse, TraceLayer};
+use tracing::Level;
+use utoipa::OpenApi;
+use utoipa_scalar::{Scalar, Servable};
 
-use crate::api::black_scholes_handlers::{get_greeks_prices, get_heatmap_prices, get_options_prices, health_check};
-use crate::api::monte_carlo_handlers::{get_monte_carlo_price, get_monte_carlo_comparison, get_monte_carlo_convergence_analysis, get_monte_carlo_price_parallel};
+use crate::api::admin_handlers::{create_api_key, get_all_usage, list_api_keys, revoke_api_key};
+use crate::api::api_error::ApiError;
+use crate::api::app_state::AppState;
+use crate::api::authentication::{authenticate, require_admin};
+use crate::api::bulk_handlers::price_table_upload;
+use crate::api::compute_handlers::get_compute_stats;
+use crate::api::deprecation::deprecated_alias;
+use crate::api::docs_handlers::get_openapi_spec;
+use crate::api::job_handlers::{cancel_job, get_job, submit_job};
+use crate::api::metrics_handlers::get_metrics;
+use crate::api::streaming_handlers::{stream_monte_carlo_convergence, stream_monte_carlo_price_parallel, stream_monte_carlo_ws};
+use crate::api::black_scholes_handlers::{get_batch_prices, get_greeks_prices, get_heatmap_prices, get_options_prices, health_check};
+use crate::api::openapi::ApiDoc;
+use crate::api::rate_limiter::rate_limit;
+use crate::api::usage_handlers::get_usage;
+use crate::config::server_config::ServerConfig;
+use crate::metrics::{self, RecordRequestLayer};
+use crate::telemetry::{MakeRequestSpan, REQUEST_ID_HEADER};
+use crate::api::monte_carlo_handlers::{create_simulation, get_monte_carlo_price, get_monte_carlo_comparison, get_monte_carlo_convergence_analysis, get_monte_carlo_price_parallel, get_monte_carlo_price_target_precision};
 
-pub fn create_router() -> Router {
+pub fn create_router(state: AppState, config: &ServerConfig) -> Router {
 
-    // define CORS layer
-    // all origins, methods, and headers for now (ONLY FOR DEV)
+    // define CORS layer. origins come from the config (already validated), methods and headers are open
+    let origins: AllowOrigin = if config.http.cors_origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(config.http.cors_origins.iter().filter_map(|origin| HeaderValue::from_str(origin).ok()))
+    };
     let cors_layer: CorsLayer = CorsLayer::new()
-        .allow_origin([
-            "http://localhost:3000".parse().unwrap()
-        ])
+        .allow_origin(origins)
         .allow_methods(Any)
         .allow_headers(Any);
 
-    // set up and return the router
+    // every request gets an x-request-id (the client's, or a new uuid) before its span is
+    // made, and the same id goes back on the response
+    let request_id: HeaderName = HeaderName::from_static(REQUEST_ID_HEADER);
+    let trace_layers = ServiceBuilder::new()
+        .layer(SetRequestIdLayer::new(request_id.clone(), MakeRequestUuid))
+        .layer(TraceLayer::new_for_http()
+            .make_span_with(MakeRequestSpan { transport: "http" })
+            .on_response(DefaultOnResponse::new().level(Level::INFO).latency_unit(LatencyUnit::Millis)))
+        .layer(PropagateRequestIdLayer::new(request_id));
+
+    // set up and return the router. streams and websockets answer straight away, so the
+    // timeout only bounds how long a client waits for its response to start; a timed out
+    // Monte Carlo run is cancelled at its next checkpoint.
+    // a panicking handler becomes a 500, and is counted and traced like any other response.
+    // unauthenticated requests are answered before any of that, then rate limited ones
+    // (by tenant, so after authentication), but both are still counted and traced
+    let batch_limit: usize = config.http.batch_body_limit_bytes;
+    let auth_layer = middleware::from_fn_with_state(state.clone(), authenticate);
+    let rate_limit_layer = middleware::from_fn_with_state(state.clone(), rate_limit);
     Router::new()
-        .route("/api/health", get(health_check))
-        .route("/api/black-scholes/get-greeks-prices", post(get_greeks_prices))
-        .route("/api/black-scholes/get-options-prices", post(get_options_prices))
-        .route("/api/black-scholes/get-heatmap-prices", post(get_heatmap_prices))
-        .route("/api/monte-carlo/get-price", post(get_monte_carlo_price))
-        .route("/api/monte-carlo/get-price-parallel", post(get_monte_carlo_price_parallel))
-        .route("/api/monte-carlo/get-comparison", post(get_monte_carlo_comparison))
-        .route("/api/monte-carlo/get-convergence", post(get_monte_carlo_convergence_analysis))
+        .nest("/api/v1", v1_routes(batch_limit).nest("/admin", admin_routes(&state)))
+        .merge(legacy_routes(batch_limit))
+        .route("/api/openapi.json", get(get_openapi_spec))
+        .merge(Scalar::with_url("/api/docs", ApiDoc::openapi()))
+        .route("/metrics", get(get_metrics))
+        .layer(CatchPanicLayer::custom(panic_response))
+        .layer(DefaultBodyLimit::max(config.http.body_limit_bytes))
+        .layer(TimeoutLayer::with_status_code(StatusCode::REQUEST_TIMEOUT, config.request_timeout()))
+        .layer(rate_limit_layer)
+        .layer(auth_layer)
+        .layer(RecordRequestLayer { transport: "http" })
+        .layer(trace_layers)
         .layer(cors_layer)
-}
\ No newline at end of file
+        .with_state(state)
+}
+
+fn panic_response(_: Box<dyn std::any::Any + Send + 'static>) -> Response {
+    metrics::record_panic("handler");
+    ApiError::Internal {
+        code: "internal_error",
+        field: None,
+        message: "the request failed unexpectedly".to_string(),
+    }.into_response()
+}
+
+// the stable API. new endpoints go here; response shapes are pinned by tests/v1_contract.rs
+// batch and bulk uploads get their own (larger) body limit
+fn v1_routes(batch_limit: usize) -> Router<AppState> {
+    Router::new()
+        .route("/health", get(health_check))
+        .route("/options/price", post(get_options_prices))
+        .route("/options/greeks", post(get_greeks_prices))
+        .route("/options/heatmap", post(get_heatmap_prices))
+        .route("/options/batch", post(get_batch_prices).layer(DefaultBodyLimit::max(batch_limit)))
+        .route("/options/bulk", post(price_table_upload).layer(DefaultBodyLimit::max(batch_limit)))
+        .route("/monte-carlo/simulations", post(create_simulation))
+        .route("/monte-carlo/simulations/stream", get(stream_monte_carlo_price_parallel))
+        .route("/monte-carlo/comparisons", post(get_monte_carlo_comparison))
+        .route("/monte-carlo/convergence", post(get_monte_carlo_convergence_analysis))
+        .route("/monte-carlo/convergence/stream", get(stream_monte_carlo_convergence))
+        .route("/monte-carlo/target-precision", post(get_monte_carlo_price_target_precision))
+        .route("/monte-carlo/ws", get(stream_monte_carlo_ws))
+        .route("/compute/stats", get(get_compute_stats))
+        .route("/jobs", post(submit_job))
+        .route("/jobs/{id}", get(get_job).delete(cancel_job))
+        .route("/usage", get(get_usage))
+}
+
+// key management and usage across tenants, behind the admin token rather than a tenant's
+// credentials
+fn admin_routes(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/keys", post(create_api_key).get(list_api_keys))
+        .route("/keys/{id}", delete(revoke_api_key))
+        .route("/usage", get(get_all_usage))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
+}
+
+// the original routes, kept working until LEGACY_SUNSET. every response carries
+// Deprecation/Sunset headers and a Link to its v1 replacement
+fn legacy_routes(batch_limit: usize) -> Router<AppState> {
+    Router::new()
+        .route("/api/health", deprecated_alias(get(health_check), "/api/v1/health"))
+        .route("/api/black-scholes/get-greeks-prices", deprecated_alias(post(get_greeks_prices), "/api/v1/options/greeks"))
+        .route("/api/black-scholes/get-options-prices", deprecated_alias(post(get_options_prices), "/api/v1/options/price"))
+        .route("/api/black-scholes/get-heatmap-prices", deprecated_alias(post(get_heatmap_prices), "/api/v1/options/heatmap"))
+        .route("/api/black-scholes/batch", deprecated_alias(
+            post(get_batch_prices).layer(DefaultBodyLimit::max(batch_limit)),
+            "/api/v1/options/batch",
+        ))
+        .route("/api/black-scholes/bulk", deprecated_alias(
+            post(price_table_upload).layer(DefaultBodyLimit::max(batch_limit)),
+            "/api/v1/options/bulk",
+        ))
+        .route("/api/monte-carlo/get-price", deprecated_alias(post(get_monte_carlo_price), "/api/v1/monte-carlo/simulations"))
+        .route("/api/monte-carlo/get-price-parallel", deprecated_alias(post(get_monte_carlo_price_parallel), "/api/v1/monte-carlo/simulations"))
+        .route("/api/monte-carlo/get-price-target-precision", deprecated_alias(post(get_monte_carlo_price_target_precision), "/api/v1/monte-carlo/target-precision"))
+        .route("/api/monte-carlo/get-comparison", deprecated_alias(post(get_monte_carlo_comparison), "/api/v1/monte-carlo/comparisons"))
+        .route("/api/monte-carlo/get-convergence", deprecated_alias(post(get_monte_carlo_convergence_analysis), "/api/v1/monte-carlo/convergence"))
+        .route("/api/monte-carlo/stream-price-parallel", deprecated_alias(get(stream_monte_carlo_price_parallel), "/api/v1/monte-carlo/simulations/stream"))
+        .route("/api/monte-carlo/stream-convergence", deprecated_alias(get(stream_monte_carlo_convergence), "/api/v1/monte-carlo/convergence/stream"))
+        .route("/api/monte-carlo/ws", deprecated_alias(get(stream_monte_carlo_ws), "/api/v1/monte-carlo/ws"))
+        .route("/api/compute/stats", deprecated_alias(get(get_compute_stats), "/api/v1/compute/stats"))
+        .route("/api/jobs", deprecated_alias(post(submit_job), "/api/v1/jobs"))
+        .route("/api/jobs/{id}", deprecated_alias(get(get_job).delete(cancel_job), "/api/v1/jobs/{id}"))
+}
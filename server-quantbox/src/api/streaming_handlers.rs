@@ -0,0 +1,208 @@
+use std::convert::Infallible;
+use std::pin::Pin;
+use axum::{
+    extract::{State, ws::{Message, WebSocket, WebSocketUpgrade}},
+    response::{IntoResponse, sse::{Event, KeepAlive, Sse}},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio_stream::{Stream, StreamExt, wrappers::ReceiverStream};
+use crate::api::api_error::{ApiError, ErrorBody};
+use crate::api::app_state::AppState;
+use crate::api::validated_query::ValidatedQuery;
+use crate::auth::usage_meter::{self, UsageMeter};
+use crate::config::simulation_config::WithinLimits;
+use crate::models::monte_carlo_models::{ConvergenceRequest, MonteCarloRequest, StreamRequest};
+use crate::models::validation::Validate;
+use crate::service::pricing_service::{PricingService, StreamReceiver};
+use crate::service::stream_event::{ConvergenceEvent, MonteCarloEvent};
+
+// one event on the wire: SSE sends `event` as the event name and `data` as the payload,
+// the websocket sends {"event": ..., "data": ...}
+#[derive(Serialize)]
+struct StreamMessage {
+    event: &'static str,
+    data: Value,
+}
+
+impl StreamMessage {
+    fn new<T: Serialize>(event: &'static str, data: &T) -> Self {
+        StreamMessage {
+            event,
+            data: serde_json::to_value(data).unwrap_or(Value::Null),
+        }
+    }
+
+    fn from_item<T: Into<StreamMessage>>(item: Result<T, ApiError>) -> Self {
+        match item {
+            Ok(event) => event.into(),
+            Err(err) => StreamMessage::new("error", &err.into_body()),
+        }
+    }
+}
+
+impl From<ConvergenceEvent> for StreamMessage {
+    fn from(event: ConvergenceEvent) -> Self {
+        match event {
+            ConvergenceEvent::Point(point) => StreamMessage::new("point", &point),
+            ConvergenceEvent::Complete(response) => StreamMessage::new("complete", &response),
+        }
+    }
+}
+
+impl From<MonteCarloEvent> for StreamMessage {
+    fn from(event: MonteCarloEvent) -> Self {
+        match event {
+            MonteCarloEvent::Progress(running) => StreamMessage::new("progress", &running),
+            MonteCarloEvent::Complete(response) => StreamMessage::new("complete", &response),
+        }
+    }
+}
+
+type MessageStream = Pin<Box<dyn Stream<Item = StreamMessage> + Send>>;
+
+fn messages<T: Into<StreamMessage> + Send + 'static>(rx: StreamReceiver<T>) -> MessageStream {
+    Box::pin(ReceiverStream::new(rx).map(StreamMessage::from_item))
+}
+
+// reserves a compute slot and starts the run; dropping the stream cancels it
+async fn open_stream(pricing: &PricingService, request: StreamRequest) -> Result<MessageStream, ApiError> {
+    match request {
+        StreamRequest::Convergence(req) => Ok(messages(pricing.stream_convergence(req).await?)),
+        StreamRequest::MonteCarloParallel(req) => Ok(messages(pricing.stream_monte_carlo_parallel(req).await?)),
+    }
+}
+
+fn into_sse(stream: MessageStream) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events = stream.map(|msg: StreamMessage| {
+        Ok(Event::default().event(msg.event).data(msg.data.to_string()))
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/monte-carlo/convergence/stream",
+    tag = "streaming",
+    description = "Takes the fields of the convergence body as query parameters. Sends a `point` \
+                   event per convergence point, then `complete` with the full analysis, or `error`.",
+    responses(
+        (status = 200, description = "server-sent events", content_type = "text/event-stream", body = String),
+        (status = 422, description = "invalid inputs", body = ErrorBody),
+        (status = 503, description = "compute pool saturated; see Retry-After", body = ErrorBody),
+    ),
+)]
+pub async fn stream_monte_carlo_convergence(
+    State(state): State<AppState>,
+    ValidatedQuery(req): ValidatedQuery<ConvergenceRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let stream: MessageStream = open_stream(&state.pricing, StreamRequest::Convergence(req)).await?;
+
+    Ok(into_sse(stream))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/monte-carlo/simulations/stream",
+    tag = "streaming",
+    description = "Takes the fields of the simulations body as query parameters and runs the parallel engine. Sends a `progress` \
+                   event with the running estimate after each round, then `complete`, or `error`.",
+    responses(
+        (status = 200, description = "server-sent events", content_type = "text/event-stream", body = String),
+        (status = 422, description = "invalid inputs", body = ErrorBody),
+        (status = 503, description = "compute pool saturated; see Retry-After", body = ErrorBody),
+    ),
+)]
+pub async fn stream_monte_carlo_price_parallel(
+    State(state): State<AppState>,
+    ValidatedQuery(req): ValidatedQuery<MonteCarloRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let stream: MessageStream = open_stream(&state.pricing, StreamRequest::MonteCarloParallel(req)).await?;
+
+    Ok(into_sse(stream))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/monte-carlo/ws",
+    tag = "streaming",
+    description = "WebSocket upgrade. The first text message is {\"kind\": \"convergence\" | \"monte_carlo_parallel\", \
+                   \"params\": {...}}; events come back as {\"event\": ..., \"data\": ...}.",
+    responses((status = 101, description = "switching to the WebSocket protocol")),
+)]
+pub async fn stream_monte_carlo_ws(State(state): State<AppState>, ws: WebSocketUpgrade) -> impl IntoResponse {
+    // the socket is served after this handler returns, so it takes the tenant along
+    let meter: Option<UsageMeter> = usage_meter::current();
+    ws.on_upgrade(move |socket: WebSocket| usage_meter::metered(meter, handle_socket(state, socket)))
+}
+
+async fn send_message(socket: &mut WebSocket, msg: &StreamMessage) -> bool {
+    let text: String = serde_json::to_string(msg).unwrap_or_default();
+    socket.send(Message::Text(text.into())).await.is_ok()
+}
+
+async fn send_error(socket: &mut WebSocket, err: ApiError) {
+    let _ = send_message(socket, &StreamMessage::new("error", &err.into_body())).await;
+    let _ = socket.send(Message::Close(None)).await;
+}
+
+// the client opens the socket and sends one StreamRequest as its first text message, within
+// the request timeout. results are pushed back as they come in; closing the socket cancels the run
+async fn handle_socket(state: AppState, mut socket: WebSocket) {
+    let first: Option<Result<Message, axum::Error>> = match tokio::time::timeout(state.request_timeout, socket.recv()).await {
+        Ok(first) => first,
+        Err(_) => {
+            send_error(&mut socket, ApiError::TimedOut {
+                message: "no request arrived on the websocket before the request timeout".to_string(),
+            }).await;
+            return;
+        }
+    };
+    let (mut request, body): (StreamRequest, Value) = match first {
+        Some(Ok(Message::Text(text))) => match serde_json::from_str::<Value>(text.as_str())
+            .and_then(|body| Ok((StreamRequest::deserialize(&body)?, body))) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                send_error(&mut socket, ApiError::BadRequest { code: "invalid_body", message: err.to_string() }).await;
+                return;
+            }
+        },
+        _ => return,
+    };
+
+    request.fill_defaults(&body, &state.simulation);
+    if let Err(err) = request.validate().and_then(|_| request.check_limits(&state.simulation)) {
+        send_error(&mut socket, err.into()).await;
+        return;
+    }
+
+    let mut stream: MessageStream = match open_stream(&state.pricing, request).await {
+        Ok(stream) => stream,
+        Err(err) => {
+            send_error(&mut socket, err).await;
+            return;
+        }
+    };
+
+    loop {
+        tokio::select! {
+            msg = stream.next() => match msg {
+                Some(msg) => {
+                    if !send_message(&mut socket, &msg).await {
+                        break;
+                    }
+                }
+                None => {
+                    let _ = socket.send(Message::Close(None)).await;
+                    break;
+                }
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                _ => {}
+            },
+        }
+    }
+    // the stream drops here, which stops the producer at its next checkpoint
+}
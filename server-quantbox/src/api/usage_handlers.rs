@@ -0,0 +1,31 @@
+use axum::{
+    Extension, Json,
+    extract::State,
+};
+use crate::api::api_error::{ApiError, ErrorBody};
+use crate::api::app_state::AppState;
+use crate::auth::authenticator::Tenant;
+use crate::models::auth_models::TenantUsage;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/usage",
+    tag = "usage",
+    responses(
+        (status = 200, description = "the calling tenant's usage and quota for today", body = TenantUsage),
+        (status = 401, description = "missing or invalid credentials", body = ErrorBody),
+        (status = 403, description = "authentication is off, so there's no tenant to report on", body = ErrorBody),
+    ),
+)]
+pub async fn get_usage(
+    State(state): State<AppState>,
+    tenant: Option<Extension<Tenant>>,
+) -> Result<Json<TenantUsage>, ApiError> {
+    let Some(Extension(tenant)) = tenant else {
+        return Err(ApiError::Forbidden {
+            code: "auth_disabled",
+            message: "usage is only tracked when authentication is enabled".to_string(),
+        });
+    };
+    state.auth.blocking(move |auth| auth.usage(&tenant.name)).await.map(Json)
+}
@@ -0,0 +1,41 @@
+use axum::{
+    Json,
+    extract::{FromRequest, Request},
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use crate::api::api_error::ApiError;
+use crate::api::app_state::AppState;
+use crate::config::simulation_config::WithinLimits;
+use crate::models::validation::Validate;
+
+// drop-in for axum's Json extractor that also fills in the configured simulation defaults
+// and runs the request's validation and the configured caps, so handlers only ever see
+// inputs that are safe to price
+pub struct ValidatedJson<T>(pub T);
+
+impl<T> FromRequest<AppState> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate + WithinLimits,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &AppState) -> Result<Self, Self::Rejection> {
+        let value: T = match T::HAS_DEFAULTS {
+            // read as a Value first, to see which fields the body left out
+            true => {
+                let Json(body) = Json::<Value>::from_request(req, state).await?;
+                let mut value: T = T::deserialize(&body).map_err(|e| ApiError::BadRequest {
+                    code: "invalid_body",
+                    message: format!("Failed to deserialize the JSON body into the target type: {}", e),
+                })?;
+                value.fill_defaults(&body, &state.simulation);
+                value
+            }
+            false => Json::<T>::from_request(req, state).await?.0,
+        };
+        value.validate()?;
+        value.check_limits(&state.simulation)?;
+        Ok(ValidatedJson(value))
+    }
+}
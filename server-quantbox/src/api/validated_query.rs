@@ -0,0 +1,37 @@
+use axum::{
+    extract::{FromRequestParts, Query, rejection::QueryRejection},
+    http::request::Parts,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use crate::api::api_error::ApiError;
+use crate::api::app_state::AppState;
+use crate::config::simulation_config::WithinLimits;
+use crate::models::validation::Validate;
+
+// query-string counterpart of ValidatedJson, for GET endpoints such as EventSource streams
+pub struct ValidatedQuery<T>(pub T);
+
+impl<T> FromRequestParts<AppState> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate + WithinLimits,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let Query(mut value) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(|rejection: QueryRejection| ApiError::BadRequest {
+                code: "invalid_query",
+                message: rejection.body_text(),
+            })?;
+        if T::HAS_DEFAULTS {
+            // just the parameter names, to see which ones were left out
+            let given: Value = Query::<Value>::try_from_uri(&parts.uri).map(|Query(given)| given).unwrap_or_default();
+            value.fill_defaults(&given, &state.simulation);
+        }
+        value.validate()?;
+        value.check_limits(&state.simulation)?;
+        Ok(ValidatedQuery(value))
+    }
+}
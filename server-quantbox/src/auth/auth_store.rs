@@ -0,0 +1,217 @@
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use rusqlite::{Connection, OptionalExtension, params};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use crate::jobs::job_store::now_ms;
+use crate::models::auth_models::ApiKeyInfo;
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+// prefixed so a leaked key is easy to recognise (and to grep for)
+const KEY_PREFIX: &str = "qbk_";
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS tenants (
+        name TEXT PRIMARY KEY,
+        daily_path_quota INTEGER
+    );
+    CREATE TABLE IF NOT EXISTS api_keys (
+        id TEXT PRIMARY KEY,
+        tenant TEXT NOT NULL REFERENCES tenants(name),
+        key_hash TEXT NOT NULL UNIQUE,
+        created_at_ms INTEGER NOT NULL,
+        revoked_at_ms INTEGER
+    );
+    CREATE TABLE IF NOT EXISTS usage (
+        tenant TEXT NOT NULL,
+        day INTEGER NOT NULL,
+        requests INTEGER NOT NULL DEFAULT 0,
+        simulated_paths INTEGER NOT NULL DEFAULT 0,
+        PRIMARY KEY (tenant, day)
+    );
+";
+
+// days since the unix epoch, in UTC. usage is counted per day
+pub fn utc_day(time_ms: u64) -> u64 {
+    time_ms / DAY_MS
+}
+
+// ms from `time_ms` until the next UTC day starts and quotas reset
+pub fn until_next_day_ms(time_ms: u64) -> u64 {
+    DAY_MS - time_ms % DAY_MS
+}
+
+// YYYY-MM-DD for a utc_day, using the days-to-civil conversion from
+// http://howardhinnant.github.io/date_algorithms.html
+pub fn format_day(day: u64) -> String {
+    let z: i64 = day as i64 + 719_468;
+    let era: i64 = z.div_euclid(146_097);
+    let day_of_era: i64 = z - era * 146_097;
+    let year_of_era: i64 = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year: i64 = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month: i64 = (5 * day_of_year + 2) / 153;
+    let day_of_month: i64 = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month: i64 = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year: i64 = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day_of_month)
+}
+
+fn hash_key(key: &str) -> String {
+    Sha256::digest(key.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// SQLite counts are signed; ours never get near the top of the range
+fn to_sql(count: u64) -> i64 {
+    i64::try_from(count).unwrap_or(i64::MAX)
+}
+
+// tenants, their API keys and what they've used, in one SQLite file. keys are stored as
+// SHA-256 hashes: they're random enough that a salt or a slow hash wouldn't add anything
+pub struct AuthStore {
+    connection: Mutex<Connection>,
+}
+
+impl AuthStore {
+    // ":memory:" gives a store that lasts as long as the process
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        let connection: Connection = Connection::open(path)?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(AuthStore { connection: Mutex::new(connection) })
+    }
+
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        self.connection.lock().unwrap()
+    }
+
+    // returns the key's listing and the key itself, which isn't stored anywhere
+    pub fn create_key(&self, tenant: &str, daily_path_quota: Option<u64>) -> rusqlite::Result<(ApiKeyInfo, String)> {
+        let key: String = format!("{}{}{}", KEY_PREFIX, Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let info: ApiKeyInfo = ApiKeyInfo {
+            id: Uuid::new_v4().to_string(),
+            tenant: tenant.to_string(),
+            created_at_ms: now_ms(),
+            revoked_at_ms: None,
+        };
+
+        let mut connection = self.connection();
+        let transaction = connection.transaction()?;
+        transaction.execute("INSERT OR IGNORE INTO tenants (name) VALUES (?1)", params![tenant])?;
+        if let Some(quota) = daily_path_quota {
+            transaction.execute("UPDATE tenants SET daily_path_quota = ?2 WHERE name = ?1", params![tenant, to_sql(quota)])?;
+        }
+        transaction.execute(
+            "INSERT INTO api_keys (id, tenant, key_hash, created_at_ms) VALUES (?1, ?2, ?3, ?4)",
+            params![info.id, tenant, hash_key(&key), to_sql(info.created_at_ms)],
+        )?;
+        transaction.commit()?;
+
+        Ok((info, key))
+    }
+
+    // false if there's no such key or it was already revoked
+    pub fn revoke_key(&self, id: &str) -> rusqlite::Result<bool> {
+        let revoked: usize = self.connection().execute(
+            "UPDATE api_keys SET revoked_at_ms = ?2 WHERE id = ?1 AND revoked_at_ms IS NULL",
+            params![id, to_sql(now_ms())],
+        )?;
+        Ok(revoked > 0)
+    }
+
+    pub fn list_keys(&self) -> rusqlite::Result<Vec<ApiKeyInfo>> {
+        let connection = self.connection();
+        let mut statement = connection.prepare(
+            "SELECT id, tenant, created_at_ms, revoked_at_ms FROM api_keys ORDER BY created_at_ms, id",
+        )?;
+        let keys = statement.query_map([], |row| Ok(ApiKeyInfo {
+            id: row.get(0)?,
+            tenant: row.get(1)?,
+            created_at_ms: row.get::<_, i64>(2)? as u64,
+            revoked_at_ms: row.get::<_, Option<i64>>(3)?.map(|ms| ms as u64),
+        }))?;
+        keys.collect()
+    }
+
+    // the tenant an unrevoked key belongs to
+    pub fn tenant_for_key(&self, key: &str) -> rusqlite::Result<Option<String>> {
+        self.connection().query_row(
+            "SELECT tenant FROM api_keys WHERE key_hash = ?1 AND revoked_at_ms IS NULL",
+            params![hash_key(key)],
+            |row| row.get(0),
+        ).optional()
+    }
+
+    // None when the tenant has no quota of its own (or isn't known, e.g. a token-only tenant)
+    pub fn daily_path_quota(&self, tenant: &str) -> rusqlite::Result<Option<u64>> {
+        let quota: Option<Option<i64>> = self.connection().query_row(
+            "SELECT daily_path_quota FROM tenants WHERE name = ?1",
+            params![tenant],
+            |row| row.get(0),
+        ).optional()?;
+        Ok(quota.flatten().map(|quota| quota as u64))
+    }
+
+    // adds to the tenant's counts for the day
+    pub fn record_usage(&self, tenant: &str, day: u64, requests: u64, simulated_paths: u64) -> rusqlite::Result<()> {
+        self.connection().execute(
+            "INSERT INTO usage (tenant, day, requests, simulated_paths) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (tenant, day) DO UPDATE SET
+                 requests = requests + excluded.requests,
+                 simulated_paths = simulated_paths + excluded.simulated_paths",
+            params![tenant, to_sql(day), to_sql(requests), to_sql(simulated_paths)],
+        )?;
+        Ok(())
+    }
+
+    // adds `paths` to the tenant's simulated paths for the day, as long as that keeps them
+    // within `quota`. false, and nothing added, if it wouldn't
+    pub fn reserve_paths(&self, tenant: &str, day: u64, paths: u64, quota: u64) -> rusqlite::Result<bool> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction()?;
+        transaction.execute(
+            "INSERT OR IGNORE INTO usage (tenant, day, requests, simulated_paths) VALUES (?1, ?2, 0, 0)",
+            params![tenant, to_sql(day)],
+        )?;
+        let reserved: usize = transaction.execute(
+            "UPDATE usage SET simulated_paths = simulated_paths + ?3
+             WHERE tenant = ?1 AND day = ?2 AND simulated_paths + ?3 <= ?4",
+            params![tenant, to_sql(day), to_sql(paths), to_sql(quota)],
+        )?;
+        transaction.commit()?;
+        Ok(reserved > 0)
+    }
+
+    // takes paths reserved with reserve_paths but never simulated back off the day's count
+    pub fn release_paths(&self, tenant: &str, day: u64, paths: u64) -> rusqlite::Result<()> {
+        self.connection().execute(
+            "UPDATE usage SET simulated_paths = MAX(simulated_paths - ?3, 0) WHERE tenant = ?1 AND day = ?2",
+            params![tenant, to_sql(day), to_sql(paths)],
+        )?;
+        Ok(())
+    }
+
+    // (requests, simulated paths) for the tenant on the day
+    pub fn usage(&self, tenant: &str, day: u64) -> rusqlite::Result<(u64, u64)> {
+        let usage: Option<(i64, i64)> = self.connection().query_row(
+            "SELECT requests, simulated_paths FROM usage WHERE tenant = ?1 AND day = ?2",
+            params![tenant, to_sql(day)],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).optional()?;
+        Ok(usage.map_or((0, 0), |(requests, paths)| (requests as u64, paths as u64)))
+    }
+
+    // every tenant with a key or with usage on the day, as (tenant, requests, simulated paths)
+    pub fn all_usage(&self, day: u64) -> rusqlite::Result<Vec<(String, u64, u64)>> {
+        let connection = self.connection();
+        let mut statement = connection.prepare(
+            "SELECT name, COALESCE(usage.requests, 0), COALESCE(usage.simulated_paths, 0)
+             FROM (SELECT name FROM tenants UNION SELECT tenant FROM usage WHERE day = ?1)
+             LEFT JOIN usage ON usage.tenant = name AND usage.day = ?1
+             ORDER BY name",
+        )?;
+        let rows = statement.query_map(params![to_sql(day)], |row| {
+            Ok((row.get(0)?, row.get::<_, i64>(1)? as u64, row.get::<_, i64>(2)? as u64))
+        })?;
+        rows.collect()
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use crate::api::api_error::ApiError;
+use crate::auth::auth_store::{AuthStore, format_day, until_next_day_ms, utc_day};
+use crate::config::auth_config::AuthConfig;
+use crate::jobs::job_store::now_ms;
+use crate::models::auth_models::{ApiKeyInfo, CreatedApiKey, TenantUsage};
+
+// who a request is from, once its credentials check out
+#[derive(Debug, Clone)]
+pub struct Tenant {
+    pub name: String,
+    pub daily_path_quota: u64,
+}
+
+// bearer tokens are HS256, name the tenant in `sub` and must carry an `exp`
+#[derive(Debug, Deserialize)]
+struct Claims {
+    sub: String,
+}
+
+fn store_error(err: rusqlite::Error) -> ApiError {
+    tracing::error!(error = %err, "auth store failed");
+    ApiError::Internal {
+        code: "auth_store_failed",
+        field: None,
+        message: "the key store could not be read or written".to_string(),
+    }
+}
+
+fn unauthorized(code: &'static str, message: &str) -> ApiError {
+    ApiError::Unauthorized { code, message: message.to_string() }
+}
+
+// checks API keys and bearer tokens, and keeps each tenant's daily usage. shared by the
+// REST and gRPC servers. with auth disabled nothing is opened and every request is let through
+pub struct Authenticator {
+    config: AuthConfig,
+    store: Option<AuthStore>,
+    decoding_key: Option<DecodingKey>,
+    // requests per (tenant, day) not yet written to the store. counting them here keeps the
+    // store's lock off the path of every request; they're flushed every few seconds and
+    // before usage is read
+    request_counts: Mutex<HashMap<(String, u64), u64>>,
+}
+
+impl Authenticator {
+    pub fn new(config: AuthConfig) -> Result<Self, String> {
+        let store: Option<AuthStore> = match config.enabled {
+            true => Some(AuthStore::open(&config.database)
+                .map_err(|e| format!("failed to open auth database {}: {}", config.database.display(), e))?),
+            false => None,
+        };
+        let decoding_key: Option<DecodingKey> = config.jwt_secret.as_ref()
+            .map(|secret| DecodingKey::from_secret(secret.as_bytes()));
+
+        Ok(Authenticator { config, store, decoding_key, request_counts: Mutex::new(HashMap::new()) })
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.store.is_some()
+    }
+
+    // runs `call` on tokio's blocking pool, so a slow or locked SQLite file holds up a
+    // blocking thread rather than an async worker. every store access from a request goes
+    // through here; with auth disabled there's no store and `call` runs in place
+    pub async fn blocking<T, F>(self: &Arc<Self>, call: F) -> Result<T, ApiError>
+    where
+        F: FnOnce(&Authenticator) -> Result<T, ApiError> + Send + 'static,
+        T: Send + 'static,
+    {
+        if !self.enabled() {
+            return call(self);
+        }
+        let authenticator: Arc<Authenticator> = Arc::clone(self);
+        tokio::task::spawn_blocking(move || call(&authenticator))
+            .await
+            .unwrap_or_else(|err| {
+                tracing::error!(error = %err, "auth store call failed");
+                Err(ApiError::Internal {
+                    code: "auth_store_failed",
+                    field: None,
+                    message: "the key store could not be read or written".to_string(),
+                })
+            })
+    }
+
+    // the tenant behind an x-api-key value or an Authorization header, None with auth disabled.
+    // the quota isn't checked here: a tenant that has used it up can still read its usage,
+    // poll and cancel jobs and make analytic calls. runs that simulate paths reserve them
+    pub fn authenticate(&self, api_key: Option<&str>, authorization: Option<&str>) -> Result<Option<Tenant>, ApiError> {
+        let Some(store) = &self.store else {
+            return Ok(None);
+        };
+
+        let name: String = match (api_key, authorization) {
+            (Some(key), _) => store.tenant_for_key(key)
+                .map_err(store_error)?
+                .ok_or_else(|| unauthorized("invalid_api_key", "the API key is unknown or has been revoked"))?,
+            (None, Some(authorization)) => self.token_subject(authorization)?,
+            (None, None) => return Err(unauthorized(
+                "missing_credentials",
+                "send an API key in x-api-key or a bearer token in Authorization",
+            )),
+        };
+        let tenant: Tenant = Tenant {
+            daily_path_quota: store.daily_path_quota(&name).map_err(store_error)?
+                .unwrap_or(self.config.default_daily_path_quota),
+            name,
+        };
+
+        *self.request_counts.lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry((tenant.name.clone(), utc_day(now_ms())))
+            .or_default() += 1;
+
+        Ok(Some(tenant))
+    }
+
+    fn token_subject(&self, authorization: &str) -> Result<String, ApiError> {
+        let Some(token) = authorization.strip_prefix("Bearer ") else {
+            return Err(unauthorized("invalid_authorization", "the Authorization header must be \"Bearer <token>\""));
+        };
+        let Some(key) = &self.decoding_key else {
+            return Err(unauthorized("bearer_tokens_disabled", "bearer tokens aren't accepted here; use an API key"));
+        };
+
+        let mut validation: Validation = Validation::new(Algorithm::HS256);
+        validation.set_required_spec_claims(&["exp", "sub"]);
+        let claims: Claims = jsonwebtoken::decode::<Claims>(token.trim(), key, &validation)
+            .map_err(|e| unauthorized("invalid_token", &format!("the bearer token was rejected: {}", e)))?
+            .claims;
+        if claims.sub.is_empty() {
+            return Err(unauthorized("invalid_token", "the bearer token has an empty subject"));
+        }
+        Ok(claims.sub)
+    }
+
+    // takes a run's paths out of the tenant's quota for today before it starts, so a run
+    // can't take the tenant past its quota. answers 429 if they don't fit in what's left.
+    // returns the day they were taken from, to settle against
+    pub fn reserve(&self, tenant: &Tenant, paths: u64) -> Result<u64, ApiError> {
+        let Some(store) = &self.store else {
+            return Ok(0);
+        };
+
+        let now: u64 = now_ms();
+        let day: u64 = utc_day(now);
+        if store.reserve_paths(&tenant.name, day, paths, tenant.daily_path_quota).map_err(store_error)? {
+            return Ok(day);
+        }
+        let (_, used) = store.usage(&tenant.name, day).map_err(store_error)?;
+        Err(ApiError::QuotaExceeded {
+            retry_after_secs: until_next_day_ms(now).div_ceil(1000),
+            message: format!(
+                "the run needs {} simulated paths, but tenant {} has {} of its {} left for today; the quota resets at 00:00 UTC",
+                paths, tenant.name, tenant.daily_path_quota.saturating_sub(used), tenant.daily_path_quota,
+            ),
+        })
+    }
+
+    // once a run is over, squares its reservation with the paths it actually simulated:
+    // the ones it didn't get to are given back, and any past the reservation are charged
+    pub fn settle(&self, tenant: &str, day: u64, reserved: u64, simulated_paths: u64) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        let settled: rusqlite::Result<()> = match simulated_paths.cmp(&reserved) {
+            std::cmp::Ordering::Less => store.release_paths(tenant, day, reserved - simulated_paths),
+            std::cmp::Ordering::Greater => store.record_usage(tenant, day, 0, simulated_paths - reserved),
+            std::cmp::Ordering::Equal => Ok(()),
+        };
+        if let Err(err) = settled {
+            tracing::error!(error = %err, tenant, "failed to record usage");
+        }
+    }
+
+    // the admin API is only there with auth enabled and an admin token configured
+    pub fn authorize_admin(&self, token: Option<&str>) -> Result<(), ApiError> {
+        let Some(expected) = self.config.admin_token.as_deref().filter(|_| self.enabled()) else {
+            return Err(ApiError::Forbidden {
+                code: "admin_disabled",
+                message: "the admin API needs auth.enabled and auth.admin_token".to_string(),
+            });
+        };
+        match token {
+            Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => Ok(()),
+            _ => Err(unauthorized("invalid_admin_token", "send the configured admin token in x-admin-token")),
+        }
+    }
+
+    fn store(&self) -> Result<&AuthStore, ApiError> {
+        self.store.as_ref().ok_or_else(|| ApiError::Forbidden {
+            code: "auth_disabled",
+            message: "authentication is not enabled on this server".to_string(),
+        })
+    }
+
+    pub fn create_key(&self, tenant: &str, daily_path_quota: Option<u64>) -> Result<CreatedApiKey, ApiError> {
+        let store: &AuthStore = self.store()?;
+        let (info, key) = store.create_key(tenant, daily_path_quota).map_err(store_error)?;
+        Ok(CreatedApiKey {
+            id: info.id,
+            tenant: info.tenant,
+            key,
+            created_at_ms: info.created_at_ms,
+            daily_path_quota: daily_path_quota.unwrap_or(self.tenant_quota(store, tenant)?),
+        })
+    }
+
+    pub fn revoke_key(&self, id: &str) -> Result<(), ApiError> {
+        match self.store()?.revoke_key(id).map_err(store_error)? {
+            true => Ok(()),
+            false => Err(ApiError::NotFound {
+                code: "api_key_not_found",
+                message: format!("no active API key with id {}", id),
+            }),
+        }
+    }
+
+    pub fn list_keys(&self) -> Result<Vec<ApiKeyInfo>, ApiError> {
+        self.store()?.list_keys().map_err(store_error)
+    }
+
+    // writes the requests counted since the last flush to the store
+    pub fn flush_request_counts(&self) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        let counts: HashMap<(String, u64), u64> = std::mem::take(
+            &mut *self.request_counts.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+        );
+        for ((tenant, day), requests) in counts {
+            if let Err(err) = store.record_usage(&tenant, day, requests, 0) {
+                tracing::error!(error = %err, tenant, "failed to record usage");
+            }
+        }
+    }
+
+    // today's usage for one tenant
+    pub fn usage(&self, tenant: &str) -> Result<TenantUsage, ApiError> {
+        let store: &AuthStore = self.store()?;
+        self.flush_request_counts();
+        let day: u64 = utc_day(now_ms());
+        let (requests, simulated_paths) = store.usage(tenant, day).map_err(store_error)?;
+        let daily_path_quota: u64 = self.tenant_quota(store, tenant)?;
+        Ok(usage_entry(tenant, day, requests, simulated_paths, daily_path_quota))
+    }
+
+    // today's usage for every known tenant
+    pub fn all_usage(&self) -> Result<Vec<TenantUsage>, ApiError> {
+        let store: &AuthStore = self.store()?;
+        self.flush_request_counts();
+        let day: u64 = utc_day(now_ms());
+        store.all_usage(day).map_err(store_error)?
+            .into_iter()
+            .map(|(tenant, requests, simulated_paths)| {
+                let daily_path_quota: u64 = self.tenant_quota(store, &tenant)?;
+                Ok(usage_entry(&tenant, day, requests, simulated_paths, daily_path_quota))
+            })
+            .collect()
+    }
+
+    fn tenant_quota(&self, store: &AuthStore, tenant: &str) -> Result<u64, ApiError> {
+        Ok(store.daily_path_quota(tenant).map_err(store_error)?.unwrap_or(self.config.default_daily_path_quota))
+    }
+}
+
+// writes request counts to the store every ten seconds for as long as the server runs
+pub fn spawn_usage_flush(auth: Arc<Authenticator>) {
+    if !auth.enabled() {
+        return;
+    }
+    tokio::spawn(async move {
+        let mut interval: tokio::time::Interval = tokio::time::interval(Duration::from_secs(10));
+        loop {
+            interval.tick().await;
+            let auth: Arc<Authenticator> = Arc::clone(&auth);
+            let _ = tokio::task::spawn_blocking(move || auth.flush_request_counts()).await;
+        }
+    });
+}
+
+fn usage_entry(tenant: &str, day: u64, requests: u64, simulated_paths: u64, daily_path_quota: u64) -> TenantUsage {
+    TenantUsage {
+        tenant: tenant.to_string(),
+        day: format_day(day),
+        requests,
+        simulated_paths,
+        daily_path_quota,
+        remaining_paths: daily_path_quota.saturating_sub(simulated_paths),
+    }
+}
+
+// so the time taken doesn't give away how much of a guessed token was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
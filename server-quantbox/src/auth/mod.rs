@@ -0,0 +1,3 @@
+pub mod auth_store;
+pub mod authenticator;
+pub mod usage_meter;
@@ -0,0 +1,93 @@
+use std::future::Future;
+use std::sync::Arc;
+use tokio::runtime::Handle;
+use crate::api::api_error::ApiError;
+use crate::auth::authenticator::{Authenticator, Tenant};
+
+// the tenant a request is running for, so the paths it simulates can be charged to it
+// without threading the tenant through every handler and service call. set around each
+// authenticated request; the runs it starts (jobs and streams included) take it along
+// in their PathCharge
+#[derive(Clone)]
+pub struct UsageMeter {
+    authenticator: Arc<Authenticator>,
+    tenant: Tenant,
+}
+
+tokio::task_local! {
+    static METER: UsageMeter;
+}
+
+impl UsageMeter {
+    pub fn new(authenticator: Arc<Authenticator>, tenant: Tenant) -> Self {
+        UsageMeter { authenticator, tenant }
+    }
+}
+
+// the meter of the request being handled, None outside one or with auth disabled
+pub fn current() -> Option<UsageMeter> {
+    METER.try_with(UsageMeter::clone).ok()
+}
+
+// runs `future` with `meter` as the current meter, or as it is when there's none
+pub async fn metered<F: Future>(meter: Option<UsageMeter>, future: F) -> F::Output {
+    match meter {
+        Some(meter) => METER.scope(meter, future).await,
+        None => future.await,
+    }
+}
+
+// a run's paths, reserved against the current request's tenant's quota before it starts
+// and settled against the paths it actually simulated when dropped. taken before the run
+// and moved into its compute closure, so it's settled on the compute thread however the
+// run ends: finished, cancelled because its caller timed out or went away, or panicked.
+// a no-op when nothing is metered
+pub struct PathCharge {
+    reservation: Option<Reservation>,
+    simulated_paths: usize,
+}
+
+struct Reservation {
+    meter: UsageMeter,
+    day: u64,
+    paths: u64,
+}
+
+impl PathCharge {
+    // answers 429 if the paths don't fit in what's left of the tenant's quota for today
+    pub async fn reserve(paths: usize) -> Result<Self, ApiError> {
+        let reservation: Option<Reservation> = match current() {
+            Some(meter) if paths > 0 => {
+                let tenant: Tenant = meter.tenant.clone();
+                let day: u64 = meter.authenticator.blocking(move |auth| auth.reserve(&tenant, paths as u64)).await?;
+                Some(Reservation { meter, day, paths: paths as u64 })
+            }
+            _ => None,
+        };
+        Ok(PathCharge { reservation, simulated_paths: 0 })
+    }
+
+    // paths simulated so far; call at each checkpoint and once the run is done
+    pub fn record(&mut self, simulated_paths: usize) {
+        self.simulated_paths = simulated_paths;
+    }
+}
+
+impl Drop for PathCharge {
+    fn drop(&mut self) {
+        let Some(reservation) = self.reservation.take() else {
+            return;
+        };
+        let simulated_paths: u64 = self.simulated_paths as u64;
+        let settle = move || {
+            let Reservation { meter, day, paths } = reservation;
+            meter.authenticator.settle(&meter.tenant.name, day, paths, simulated_paths);
+        };
+        // a run that never started (turned away, or given up on in the queue) is dropped
+        // on an async worker, which mustn't wait on the store
+        match Handle::try_current() {
+            Ok(runtime) => drop(runtime.spawn_blocking(settle)),
+            Err(_) => settle(),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use std::fmt;
+use crate::models::validation::ValidationError;
+
+#[derive(Debug)]
+pub enum BulkError {
+    // the input isn't a readable table in the declared format
+    Read(String),
+    // the table is readable but a column is missing, mistyped, null or out of range
+    Invalid(ValidationError),
+    // encoding the priced table failed
+    Write(String),
+}
+
+impl From<ValidationError> for BulkError {
+    fn from(err: ValidationError) -> Self {
+        BulkError::Invalid(err)
+    }
+}
+
+impl fmt::Display for BulkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BulkError::Read(message) => write!(f, "could not read table: {}", message),
+            BulkError::Invalid(err) => write!(f, "{} ({}): {}", err.field, err.code, err.message),
+            BulkError::Write(message) => write!(f, "could not write table: {}", message),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod bulk_error;
+pub mod table_io;
+pub mod table_pricing;
@@ -0,0 +1,109 @@
+use std::io::Cursor;
+use std::sync::Arc;
+use arrow::array::RecordBatch;
+use arrow::csv::reader::Format;
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::ipc::reader::{FileReader, StreamReader};
+use arrow::ipc::writer::StreamWriter;
+use bytes::Bytes;
+use parquet::arrow::ArrowWriter;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use crate::bulk::bulk_error::BulkError;
+use crate::models::bulk_models::BulkFormat;
+
+// rows per record batch when reading CSV
+const CSV_BATCH_SIZE: usize = 65_536;
+
+// Arrow IPC files open with this magic; streams don't
+const ARROW_FILE_MAGIC: &[u8] = b"ARROW1";
+
+pub struct Table {
+    pub schema: SchemaRef,
+    pub batches: Vec<RecordBatch>,
+}
+
+impl Table {
+    pub fn num_rows(&self) -> usize {
+        self.batches.iter().map(RecordBatch::num_rows).sum()
+    }
+}
+
+pub fn read_table(data: Bytes, format: BulkFormat) -> Result<Table, BulkError> {
+    let read_error = |err: &dyn std::fmt::Display| BulkError::Read(err.to_string());
+
+    match format {
+        BulkFormat::Csv => {
+            // column types are inferred from the whole file, so integer-looking columns
+            // (e.g. whole-number strikes) come through as Int64 and get cast later
+            let (schema, _) = Format::default()
+                .with_header(true)
+                .infer_schema(Cursor::new(&data), None)
+                .map_err(|e| read_error(&e))?;
+            let schema: SchemaRef = Arc::new(schema);
+
+            let reader = arrow::csv::ReaderBuilder::new(Arc::clone(&schema))
+                .with_header(true)
+                .with_batch_size(CSV_BATCH_SIZE)
+                .build(Cursor::new(data))
+                .map_err(|e| read_error(&e))?;
+            let batches: Vec<RecordBatch> = reader.collect::<Result<_, _>>().map_err(|e| read_error(&e))?;
+            Ok(Table { schema, batches })
+        }
+        BulkFormat::Arrow if data.starts_with(ARROW_FILE_MAGIC) => {
+            let reader: FileReader<Cursor<Bytes>> = FileReader::try_new(Cursor::new(data), None).map_err(|e| read_error(&e))?;
+            let schema: SchemaRef = reader.schema();
+            let batches: Vec<RecordBatch> = reader.collect::<Result<_, _>>().map_err(|e| read_error(&e))?;
+            Ok(Table { schema, batches })
+        }
+        BulkFormat::Arrow => {
+            let reader: StreamReader<Cursor<Bytes>> = StreamReader::try_new(Cursor::new(data), None).map_err(|e| read_error(&e))?;
+            let schema: SchemaRef = reader.schema();
+            let batches: Vec<RecordBatch> = reader.collect::<Result<_, _>>().map_err(|e| read_error(&e))?;
+            Ok(Table { schema, batches })
+        }
+        BulkFormat::Parquet => {
+            let builder = ParquetRecordBatchReaderBuilder::try_new(data).map_err(|e| read_error(&e))?;
+            let schema: SchemaRef = Arc::clone(builder.schema());
+            let reader = builder.build().map_err(|e| read_error(&e))?;
+            let batches: Vec<RecordBatch> = reader.collect::<Result<_, _>>().map_err(|e| read_error(&e))?;
+            Ok(Table { schema, batches })
+        }
+    }
+}
+
+pub fn write_table(table: &Table, format: BulkFormat) -> Result<Vec<u8>, BulkError> {
+    let write_error = |err: &dyn std::fmt::Display| BulkError::Write(err.to_string());
+    let schema: &Schema = table.schema.as_ref();
+    let mut out: Vec<u8> = Vec::new();
+
+    match format {
+        BulkFormat::Csv => {
+            let mut writer = arrow::csv::WriterBuilder::new().with_header(true).build(&mut out);
+            for batch in &table.batches {
+                writer.write(batch).map_err(|e| write_error(&e))?;
+            }
+        }
+        BulkFormat::Arrow => {
+            let mut writer: StreamWriter<&mut Vec<u8>> = StreamWriter::try_new(&mut out, schema).map_err(|e| write_error(&e))?;
+            for batch in &table.batches {
+                writer.write(batch).map_err(|e| write_error(&e))?;
+            }
+            writer.finish().map_err(|e| write_error(&e))?;
+        }
+        BulkFormat::Parquet => {
+            let properties: WriterProperties = WriterProperties::builder()
+                .set_compression(Compression::SNAPPY)
+                .build();
+            let mut writer: ArrowWriter<&mut Vec<u8>> = ArrowWriter::try_new(&mut out, Arc::clone(&table.schema), Some(properties))
+                .map_err(|e| write_error(&e))?;
+            for batch in &table.batches {
+                writer.write(batch).map_err(|e| write_error(&e))?;
+            }
+            writer.close().map_err(|e| write_error(&e))?;
+        }
+    }
+
+    Ok(out)
+}
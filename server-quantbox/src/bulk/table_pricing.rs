@@ -0,0 +1,199 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use arrow::array::{Array, ArrayRef, AsArray, Float64Array, RecordBatch};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Float64Type, Schema, SchemaRef};
+use rayon::prelude::*;
+use crate::bulk::bulk_error::BulkError;
+use crate::bulk::table_io::Table;
+use crate::compute::black_scholes::{calculate_implied_volatility, price_batch};
+use crate::models::black_scholes_models::{BatchColumns, BatchValuation, OptionType};
+use crate::models::bulk_models::ColumnMapping;
+use crate::models::validation::{ValidationError, validate_option_inputs};
+
+const INPUT_FIELDS: [&str; 5] = ["spot_price", "strike_price", "risk_free_rate", "volatility", "time_to_maturity"];
+
+// appended in this order; any input column with one of these names is replaced
+const OUTPUT_COLUMNS: [&str; 10] = [
+    "call_price", "put_price", "call_delta", "put_delta", "gamma",
+    "vega", "call_theta", "put_theta", "call_rho", "put_rho",
+];
+const IMPLIED_VOLATILITY_COLUMN: &str = "implied_volatility";
+
+// where each input lives in the table's schema
+struct ResolvedColumns {
+    inputs: [usize; 5],
+    market_price: Option<usize>,
+    option_type: Option<usize>,
+    // input columns carried through to the output
+    passthrough: Vec<usize>,
+}
+
+fn resolve_columns(schema: &Schema, mapping: &ColumnMapping) -> Result<ResolvedColumns, ValidationError> {
+    let find = |field: &'static str| -> Result<Option<usize>, ValidationError> {
+        let column: &str = mapping.column(field);
+        match schema.index_of(column) {
+            Ok(index) => Ok(Some(index)),
+            // an explicitly mapped column has to exist, even for the optional fields
+            Err(_) if mapping.is_mapped(field) || INPUT_FIELDS.contains(&field) => Err(ValidationError::new(
+                "missing_column",
+                field,
+                format!("no column named {:?} for {}", column, field),
+            )),
+            Err(_) => Ok(None),
+        }
+    };
+
+    let mut inputs: [usize; 5] = [0; 5];
+    for (slot, field) in inputs.iter_mut().zip(INPUT_FIELDS) {
+        *slot = find(field)?.expect("required columns are reported missing by find");
+    }
+    let market_price: Option<usize> = find("market_price")?;
+    let option_type: Option<usize> = find("option_type")?;
+
+    let passthrough: Vec<usize> = schema.fields().iter().enumerate()
+        .filter(|(_, field)| !OUTPUT_COLUMNS.contains(&field.name().as_str()) && field.name() != IMPLIED_VOLATILITY_COLUMN)
+        .map(|(index, _)| index)
+        .collect();
+
+    Ok(ResolvedColumns { inputs, market_price, option_type, passthrough })
+}
+
+fn output_schema(schema: &Schema, columns: &ResolvedColumns) -> SchemaRef {
+    let mut fields: Vec<Field> = columns.passthrough.iter()
+        .map(|&index| schema.field(index).clone())
+        .collect();
+    fields.extend(OUTPUT_COLUMNS.iter().map(|name| Field::new(*name, DataType::Float64, false)));
+    if columns.market_price.is_some() {
+        // null where the market price is outside the no-arbitrage bounds
+        fields.push(Field::new(IMPLIED_VOLATILITY_COLUMN, DataType::Float64, true));
+    }
+    Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone()))
+}
+
+// any numeric column as f64s; nulls are rejected with the row they're on
+fn float_column(batch: &RecordBatch, index: usize, field: &'static str, first_row: usize) -> Result<Vec<f64>, ValidationError> {
+    let column: &ArrayRef = batch.column(index);
+    let values: ArrayRef = cast(column, &DataType::Float64).map_err(|_| ValidationError::new(
+        "invalid_column",
+        field,
+        format!("{} must be numeric, got {}", field, column.data_type()),
+    ))?;
+    let values: &Float64Array = values.as_primitive::<Float64Type>();
+
+    if let Some(row) = (0..values.len()).find(|&row| values.is_null(row)) {
+        return Err(ValidationError::new(
+            "null_value",
+            field,
+            format!("row {}: {} is empty", first_row + row, field),
+        ));
+    }
+    Ok(values.values().to_vec())
+}
+
+fn option_types(batch: &RecordBatch, index: usize, first_row: usize) -> Result<Vec<OptionType>, ValidationError> {
+    let column: ArrayRef = cast(batch.column(index), &DataType::Utf8).map_err(|_| ValidationError::new(
+        "invalid_column",
+        "option_type",
+        "option_type must be a text column",
+    ))?;
+
+    column.as_string::<i32>().iter().enumerate()
+        .map(|(row, value)| match value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+            Some("call") | Some("c") => Ok(OptionType::Call),
+            Some("put") | Some("p") => Ok(OptionType::Put),
+            other => Err(ValidationError::new(
+                "invalid_value",
+                "option_type",
+                format!("row {}: option_type must be call or put, got {:?}", first_row + row, other.unwrap_or("")),
+            )),
+        })
+        .collect()
+}
+
+fn price_record_batch(batch: &RecordBatch, columns: &ResolvedColumns, schema: &SchemaRef, first_row: usize) -> Result<RecordBatch, BulkError> {
+    let [s, k, r, v, t] = columns.inputs;
+    let inputs: BatchColumns = BatchColumns {
+        spot_price: float_column(batch, s, "spot_price", first_row)?,
+        strike_price: float_column(batch, k, "strike_price", first_row)?,
+        risk_free_rate: float_column(batch, r, "risk_free_rate", first_row)?,
+        volatility: float_column(batch, v, "volatility", first_row)?,
+        time_to_maturity: float_column(batch, t, "time_to_maturity", first_row)?,
+    };
+
+    // same rules as the JSON endpoints, with the failing row in the message
+    for row in 0..inputs.len() {
+        validate_option_inputs(
+            inputs.spot_price[row],
+            inputs.strike_price[row],
+            inputs.risk_free_rate[row],
+            inputs.volatility[row],
+            ("time_to_maturity", inputs.time_to_maturity[row]),
+        ).map_err(|err| ValidationError {
+            message: format!("row {}: {}", first_row + row, err.message),
+            ..err
+        })?;
+    }
+
+    let valuation: BatchValuation = price_batch(&inputs)?;
+    let mut arrays: Vec<ArrayRef> = columns.passthrough.iter()
+        .map(|&index| Arc::clone(batch.column(index)))
+        .collect();
+    for values in [
+        valuation.call_price, valuation.put_price, valuation.call_delta, valuation.put_delta, valuation.gamma,
+        valuation.vega, valuation.call_theta, valuation.put_theta, valuation.call_rho, valuation.put_rho,
+    ] {
+        arrays.push(Arc::new(Float64Array::from(values)));
+    }
+
+    if let Some(market_index) = columns.market_price {
+        let market_prices: Vec<f64> = float_column(batch, market_index, "market_price", first_row)?;
+        let types: Vec<OptionType> = match columns.option_type {
+            Some(type_index) => option_types(batch, type_index, first_row)?,
+            None => vec![OptionType::Call; inputs.len()],
+        };
+
+        let implied: Vec<Option<f64>> = (0..inputs.len())
+            .into_par_iter()
+            .map(|row| calculate_implied_volatility(
+                market_prices[row],
+                inputs.spot_price[row],
+                inputs.strike_price[row],
+                inputs.risk_free_rate[row],
+                inputs.time_to_maturity[row],
+                types[row],
+            ))
+            .collect();
+        arrays.push(Arc::new(Float64Array::from(implied)));
+    }
+
+    RecordBatch::try_new(Arc::clone(schema), arrays).map_err(|e| BulkError::Write(e.to_string()))
+}
+
+// the input table with price, Greek and (given market prices) implied-vol columns appended.
+// max_rows is the same cap as a JSON batch. raising `cancel` stops it between record batches,
+// and what's been priced so far is returned
+pub fn price_table(table: &Table, mapping: &ColumnMapping, max_rows: usize, cancel: &AtomicBool) -> Result<Table, BulkError> {
+    if table.num_rows() > max_rows {
+        return Err(BulkError::Invalid(ValidationError::new(
+            "too_many_options",
+            "rows",
+            format!("at most {} rows per table, got {}", max_rows, table.num_rows()),
+        )));
+    }
+
+    let columns: ResolvedColumns = resolve_columns(&table.schema, mapping)?;
+    let schema: SchemaRef = output_schema(&table.schema, &columns);
+
+    let mut first_row: usize = 0;
+    let mut batches: Vec<RecordBatch> = Vec::with_capacity(table.batches.len());
+    for batch in &table.batches {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        batches.push(price_record_batch(batch, &columns, &schema, first_row)?);
+        first_row += batch.num_rows();
+    }
+
+    Ok(Table { schema, batches })
+}
@@ -0,0 +1,131 @@
+use clap::{Args, ValueEnum};
+use serde_json::Value;
+use server_quantbox::compute::black_scholes::{calculate_greeks, calculate_implied_volatility, calculate_options_prices, generate_heatmap_data};
+use server_quantbox::models::black_scholes_models::{BlackScholesRequest, BlackScholesResult, GreekRequest, Greeks, HeatmapData,
+                                                    ImpliedVolatilityRequest};
+use crate::cli::report::{Cell, Report, Section};
+use crate::cli::request_input::{MarketFlags, load_request};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum OptionKind {
+    Call,
+    Put,
+}
+
+impl OptionKind {
+    // OptionType's serde names
+    fn value(self) -> Value {
+        match self {
+            OptionKind::Call => Value::from("Call"),
+            OptionKind::Put => Value::from("Put"),
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct PriceArgs {
+    #[command(flatten)]
+    pub market: MarketFlags,
+    /// annualised volatility, e.g. 0.2
+    #[arg(long)]
+    pub vol: Option<f64>,
+}
+
+impl PriceArgs {
+    fn request(&self) -> Result<BlackScholesRequest, String> {
+        let mut fields = self.market.fields("time_to_maturity");
+        fields.push(("volatility", self.vol.map(Value::from)));
+        load_request(self.market.file.as_deref(), fields)
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct GreeksArgs {
+    #[command(flatten)]
+    pub price: PriceArgs,
+    #[arg(long = "type", value_enum)]
+    pub option_type: Option<OptionKind>,
+}
+
+#[derive(Debug, Args)]
+pub struct ImpliedVolatilityArgs {
+    #[command(flatten)]
+    pub market: MarketFlags,
+    /// observed option price to invert
+    #[arg(long)]
+    pub price: Option<f64>,
+    #[arg(long = "type", value_enum)]
+    pub option_type: Option<OptionKind>,
+}
+
+pub fn price(args: &PriceArgs) -> Result<Report, String> {
+    let req: BlackScholesRequest = args.request()?;
+    let result: BlackScholesResult = calculate_options_prices(req.spot_price, req.strike_price, req.risk_free_rate, req.volatility, req.time_to_maturity);
+
+    Ok(Report::new(&result).section(Section::fields(None, vec![
+        ("call_price", Cell::from(result.call_price)),
+        ("put_price", Cell::from(result.put_price)),
+    ])))
+}
+
+pub fn greeks(args: &GreeksArgs) -> Result<Report, String> {
+    let mut fields = args.price.market.fields("time_to_maturity");
+    fields.push(("volatility", args.price.vol.map(Value::from)));
+    fields.push(("option_type", args.option_type.map(OptionKind::value)));
+    let req: GreekRequest = load_request(args.price.market.file.as_deref(), fields)?;
+
+    let s = &req.scholes;
+    let greeks: Greeks = calculate_greeks(s.spot_price, s.strike_price, s.risk_free_rate, s.volatility, s.time_to_maturity, req.option_type);
+
+    Ok(Report::new(&greeks).section(Section::fields(None, vec![
+        ("delta", Cell::from(greeks.delta)),
+        ("gamma", Cell::from(greeks.gamma)),
+        ("theta", Cell::from(greeks.theta)),
+        ("vega", Cell::from(greeks.vega)),
+        ("rho", Cell::from(greeks.rho)),
+    ])))
+}
+
+pub fn implied_volatility(args: &ImpliedVolatilityArgs) -> Result<Report, String> {
+    let mut fields = args.market.fields("time_to_maturity");
+    fields.push(("market_price", args.price.map(Value::from)));
+    fields.push(("option_type", args.option_type.map(OptionKind::value)));
+    let req: ImpliedVolatilityRequest = load_request(args.market.file.as_deref(), fields)?;
+
+    let implied: f64 = calculate_implied_volatility(
+        req.market_price,
+        req.spot_price,
+        req.strike_price,
+        req.risk_free_rate,
+        req.time_to_maturity,
+        req.option_type,
+    ).ok_or_else(|| format!(
+        "no volatility reproduces a price of {} -- it is outside the no-arbitrage bounds for this option",
+        req.market_price
+    ))?;
+
+    Ok(Report::new(&serde_json::json!({ "implied_volatility": implied }))
+        .section(Section::fields(None, vec![("implied_volatility", Cell::from(implied))])))
+}
+
+pub fn heatmap(args: &PriceArgs) -> Result<Report, String> {
+    let req: BlackScholesRequest = args.request()?;
+    let heatmap: HeatmapData = generate_heatmap_data(req.spot_price, req.strike_price, req.volatility, req.risk_free_rate, req.time_to_maturity);
+
+    // spot down the side, volatility across the top
+    let grid = |title: &str, data: &[Vec<String>]| -> Section {
+        let mut columns: Vec<&str> = vec!["spot \\ vol"];
+        columns.extend(heatmap.volatilities.iter().map(String::as_str));
+        let mut section: Section = Section::new(Some(title), &columns);
+        for (spot, row) in heatmap.spot_prices.iter().zip(data) {
+            let mut cells: Vec<Cell> = vec![Cell::from(spot.as_str())];
+            cells.extend(row.iter().map(|price| Cell::from(price.as_str())));
+            section.row(cells);
+        }
+        section
+    };
+
+    let call: Section = grid("call", &heatmap.call_data);
+    let put: Section = grid("put", &heatmap.put_data);
+    Ok(Report::new(&heatmap).section(call).section(put))
+}
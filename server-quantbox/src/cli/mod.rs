@@ -0,0 +1,75 @@
+mod black_scholes;
+mod monte_carlo;
+mod price_file;
+mod report;
+mod request_input;
+mod serve;
+
+use std::io::Write;
+use std::process::ExitCode;
+use clap::{Parser, Subcommand};
+use crate::cli::black_scholes::{GreeksArgs, ImpliedVolatilityArgs, PriceArgs};
+use crate::cli::monte_carlo::{ConvergenceArgs, MonteCarloArgs};
+use crate::cli::price_file::PriceFileArgs;
+use crate::cli::report::{OutputFormat, Report};
+use crate::cli::serve::ServeArgs;
+
+/// Black-Scholes and Monte Carlo option pricing, as an HTTP server or from the command line
+#[derive(Debug, Parser)]
+#[command(name = "quantbox", version)]
+pub struct Cli {
+    /// how pricing commands print their result
+    #[arg(long, short, global = true, value_enum, default_value_t)]
+    output: OutputFormat,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// run the HTTP API (the default when no command is given)
+    Serve(ServeArgs),
+    /// Black-Scholes call and put prices
+    Price(PriceArgs),
+    /// Black-Scholes Greeks for a call or a put
+    Greeks(GreeksArgs),
+    /// implied volatility from an observed option price
+    Iv(ImpliedVolatilityArgs),
+    /// Monte Carlo call and put prices with error estimates
+    Mc(MonteCarloArgs),
+    /// Monte Carlo estimates at growing sample sizes against Black-Scholes
+    Convergence(ConvergenceArgs),
+    /// call and put prices over a grid of spot prices and volatilities
+    Heatmap(PriceArgs),
+    /// price a CSV, Arrow or Parquet table of options into a new file
+    PriceFile(PriceFileArgs),
+}
+
+pub fn run() -> ExitCode {
+    let cli: Cli = Cli::parse();
+
+    // serve and price-file do their own output; everything else hands back a report
+    let outcome: Result<Option<Report>, String> = match cli.command.unwrap_or(Command::Serve(ServeArgs::default())) {
+        Command::Serve(args) => serve::serve(&args).map(|_| None),
+        Command::PriceFile(args) => price_file::price_file(&args).map(|_| None),
+        Command::Price(args) => black_scholes::price(&args).map(Some),
+        Command::Greeks(args) => black_scholes::greeks(&args).map(Some),
+        Command::Iv(args) => black_scholes::implied_volatility(&args).map(Some),
+        Command::Heatmap(args) => black_scholes::heatmap(&args).map(Some),
+        Command::Mc(args) => monte_carlo::monte_carlo(&args).map(Some),
+        Command::Convergence(args) => monte_carlo::convergence(&args).map(Some),
+    };
+
+    match outcome {
+        Ok(Some(report)) => {
+            // ignore a closed pipe (e.g. `quantbox heatmap ... | head`) rather than panic
+            let _ = writeln!(std::io::stdout().lock(), "{}", report.render(cli.output));
+            ExitCode::SUCCESS
+        }
+        Ok(None) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
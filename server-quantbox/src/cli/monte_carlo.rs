@@ -0,0 +1,122 @@
+use std::time::Instant;
+use clap::{Args, ValueEnum};
+use serde_json::Value;
+use server_quantbox::compute::convergence::run_convergence_analysis;
+use server_quantbox::compute::monte_carlo_engine::MonteCarloEngine;
+use server_quantbox::compute::parallel_monte_carlo_engine::ParallelMonteCarloEngine;
+use server_quantbox::models::monte_carlo_models::{ConvergenceRequest, ConvergenceResponse, MonteCarloRequest, MonteCarloResponse,
+                                                  MonteCarloResult};
+use crate::cli::report::{Cell, Report, Section};
+use crate::cli::request_input::{MarketFlags, load_request};
+
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum Engine {
+    Sequential,
+    #[default]
+    Parallel,
+}
+
+#[derive(Debug, Args)]
+pub struct MonteCarloArgs {
+    #[command(flatten)]
+    pub market: MarketFlags,
+    /// annualised volatility, e.g. 0.2
+    #[arg(long)]
+    pub vol: Option<f64>,
+    /// number of simulated paths
+    #[arg(long)]
+    pub simulations: Option<usize>,
+    #[arg(long, value_enum, default_value_t)]
+    pub engine: Engine,
+}
+
+#[derive(Debug, Args)]
+pub struct ConvergenceArgs {
+    #[command(flatten)]
+    pub market: MarketFlags,
+    /// annualised volatility, e.g. 0.2
+    #[arg(long)]
+    pub vol: Option<f64>,
+    /// largest sample size to reach
+    #[arg(long)]
+    pub max_simulations: Option<usize>,
+    /// paths added between convergence points
+    #[arg(long)]
+    pub step_size: Option<usize>,
+}
+
+pub fn monte_carlo(args: &MonteCarloArgs) -> Result<Report, String> {
+    let mut fields = args.market.fields("time_to_expiry");
+    fields.push(("volatility", args.vol.map(Value::from)));
+    fields.push(("num_simulations", args.simulations.map(Value::from)));
+    let req: MonteCarloRequest = load_request(args.market.file.as_deref(), fields)?;
+
+    let start_time: Instant = Instant::now();
+    let result: MonteCarloResult = match args.engine {
+        Engine::Sequential => MonteCarloEngine::price_european_option(&req),
+        Engine::Parallel => ParallelMonteCarloEngine::price_european_option(&req),
+    };
+    let response: MonteCarloResponse = MonteCarloResponse::from_result(&result, req.num_simulations, start_time.elapsed());
+
+    let mut instruments: Section = Section::new(
+        Some("instruments"),
+        &["name", "strike", "price", "standard_error", "ci_low", "ci_high"],
+    );
+    for instrument in &response.instruments {
+        instruments.row(vec![
+            Cell::from(instrument.name.as_str()),
+            Cell::from(instrument.strike),
+            Cell::from(instrument.price),
+            Cell::from(instrument.standard_error),
+            Cell::from(instrument.confidence_interval.0),
+            Cell::from(instrument.confidence_interval.1),
+        ]);
+    }
+
+    let summary: Section = Section::fields(Some("summary"), vec![
+        ("call_price", Cell::from(response.call_price)),
+        ("put_price", Cell::from(response.put_price)),
+        ("standard_error", Cell::from(response.standard_error)),
+        ("num_simulations", Cell::from(response.num_simulations)),
+        ("computation_time_ms", Cell::from(response.computation_time_ms.to_string())),
+    ]);
+    Ok(Report::new(&response).section(summary).section(instruments))
+}
+
+pub fn convergence(args: &ConvergenceArgs) -> Result<Report, String> {
+    let mut fields = args.market.fields("time_to_expiry");
+    fields.push(("volatility", args.vol.map(Value::from)));
+    fields.push(("max_simulations", args.max_simulations.map(Value::from)));
+    fields.push(("step_size", args.step_size.map(Value::from)));
+    let req: ConvergenceRequest = load_request(args.market.file.as_deref(), fields)?;
+
+    let response: ConvergenceResponse = run_convergence_analysis(&req, |_, _, _| true);
+
+    let mut points: Section = Section::new(
+        Some("convergence"),
+        &["num_simulations", "call_price", "put_price", "standard_error", "call_error", "put_error", "time_ms"],
+    );
+    for point in &response.convergence_data {
+        points.row(vec![
+            Cell::from(point.num_simulations),
+            Cell::from(point.call_price),
+            Cell::from(point.put_price),
+            Cell::from(point.standard_error),
+            Cell::from(point.call_error),
+            Cell::from(point.put_error),
+            Cell::from(point.time_ms.to_string()),
+        ]);
+    }
+
+    let mut summary: Vec<(&str, Cell)> = vec![
+        ("black_scholes_call", Cell::from(response.black_scholes_reference)),
+        ("black_scholes_put", Cell::from(response.black_scholes_put_reference)),
+        ("final_call_difference", Cell::from(response.final_difference)),
+        ("final_put_difference", Cell::from(response.final_put_difference)),
+    ];
+    if let Some(rate) = &response.call_convergence_rate {
+        // ≈ -0.5 when the error falls like 1/√N
+        summary.push(("call_error_slope", Cell::from(rate.slope)));
+    }
+    Ok(Report::new(&response).section(Section::fields(Some("summary"), summary)).section(points))
+}
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::time::Instant;
+use bytes::Bytes;
+use clap::Args;
+use server_quantbox::bulk::table_io::{Table, read_table, write_table};
+use server_quantbox::bulk::table_pricing::price_table;
+use server_quantbox::models::black_scholes_models::MAX_BATCH_OPTIONS;
+use server_quantbox::models::bulk_models::{BulkFormat, ColumnMapping};
+use server_quantbox::models::validation::Validate;
+
+#[derive(Debug, Args)]
+pub struct PriceFileArgs {
+    /// CSV, Arrow IPC or Parquet table of option specs
+    #[arg(long)]
+    pub input: PathBuf,
+    /// where to write the priced table
+    #[arg(long)]
+    pub output: PathBuf,
+    /// default: from the input file extension
+    #[arg(long)]
+    pub input_format: Option<BulkFormat>,
+    /// default: from the output file extension
+    #[arg(long)]
+    pub output_format: Option<BulkFormat>,
+    /// read a field from a differently named column, e.g. --map spot_price=underlying_px (repeatable)
+    #[arg(long = "map", value_name = "FIELD=COLUMN")]
+    pub mappings: Vec<String>,
+}
+
+// prices every row of the table and writes it back out with price, Greek and (given a
+// market_price column) implied_volatility columns appended
+pub fn price_file(args: &PriceFileArgs) -> Result<(), String> {
+    let mut mapping: ColumnMapping = ColumnMapping::default();
+    for assignment in &args.mappings {
+        mapping.set(assignment)?;
+    }
+    mapping.validate().map_err(|e| e.message)?;
+
+    let input_format: BulkFormat = args.input_format.or_else(|| BulkFormat::from_path(&args.input))
+        .ok_or_else(|| format!("can't tell the format of {}, pass --input-format", args.input.display()))?;
+    let output_format: BulkFormat = args.output_format.or_else(|| BulkFormat::from_path(&args.output))
+        .ok_or_else(|| format!("can't tell the format of {}, pass --output-format", args.output.display()))?;
+
+    let start_time: Instant = Instant::now();
+    let data: Vec<u8> = std::fs::read(&args.input)
+        .map_err(|e| format!("failed to read {}: {}", args.input.display(), e))?;
+
+    let table: Table = read_table(Bytes::from(data), input_format).map_err(|e| e.to_string())?;
+    let priced: Table = price_table(&table, &mapping, MAX_BATCH_OPTIONS, &AtomicBool::new(false)).map_err(|e| e.to_string())?;
+    let encoded: Vec<u8> = write_table(&priced, output_format).map_err(|e| e.to_string())?;
+
+    std::fs::write(&args.output, encoded)
+        .map_err(|e| format!("failed to write {}: {}", args.output.display(), e))?;
+
+    println!(
+        "priced {} options from {} into {} in {} ms",
+        priced.num_rows(),
+        args.input.display(),
+        args.output.display(),
+        start_time.elapsed().as_millis()
+    );
+    Ok(())
+}
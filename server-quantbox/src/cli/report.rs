@@ -0,0 +1,165 @@
+use std::fmt::Write;
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum OutputFormat {
+    // aligned columns for reading in a terminal
+    #[default]
+    Table,
+    // the same document the HTTP API returns
+    Json,
+    // one header row per section, sections separated by a blank line
+    Csv,
+}
+
+pub enum Cell {
+    Text(String),
+    Number(f64),
+}
+
+impl Cell {
+    fn render(&self, format: OutputFormat) -> String {
+        match (self, format) {
+            (Cell::Text(text), _) => text.clone(),
+            // 6 decimals is plenty to read; CSV keeps full precision for scripts
+            (Cell::Number(value), OutputFormat::Table) => format!("{:.6}", value),
+            (Cell::Number(value), _) => value.to_string(),
+        }
+    }
+}
+
+impl From<f64> for Cell {
+    fn from(value: f64) -> Self {
+        Cell::Number(value)
+    }
+}
+
+impl From<usize> for Cell {
+    fn from(value: usize) -> Self {
+        Cell::Text(value.to_string())
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(value: &str) -> Self {
+        Cell::Text(value.to_string())
+    }
+}
+
+impl From<String> for Cell {
+    fn from(value: String) -> Self {
+        Cell::Text(value)
+    }
+}
+
+pub struct Section {
+    title: Option<String>,
+    columns: Vec<String>,
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Section {
+    pub fn new(title: Option<&str>, columns: &[&str]) -> Self {
+        Section {
+            title: title.map(str::to_string),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    // two columns, one row per (field, value)
+    pub fn fields(title: Option<&str>, fields: Vec<(&str, Cell)>) -> Self {
+        let mut section: Section = Section::new(title, &["field", "value"]);
+        for (name, value) in fields {
+            section.rows.push(vec![Cell::from(name), value]);
+        }
+        section
+    }
+
+    pub fn row(&mut self, cells: Vec<Cell>) {
+        self.rows.push(cells);
+    }
+}
+
+// what a command prints: the JSON document for --output json, sections otherwise
+pub struct Report {
+    json: serde_json::Value,
+    sections: Vec<Section>,
+}
+
+impl Report {
+    pub fn new(document: &impl Serialize) -> Self {
+        Report {
+            json: serde_json::to_value(document).unwrap_or(serde_json::Value::Null),
+            sections: Vec::new(),
+        }
+    }
+
+    pub fn section(mut self, section: Section) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Json => serde_json::to_string_pretty(&self.json).unwrap_or_default(),
+            OutputFormat::Table => self.sections.iter()
+                .map(render_table)
+                .collect::<Vec<String>>()
+                .join("\n"),
+            OutputFormat::Csv => self.sections.iter()
+                .map(render_csv)
+                .collect::<Vec<String>>()
+                .join("\n"),
+        }
+    }
+}
+
+fn render_table(section: &Section) -> String {
+    let rows: Vec<Vec<String>> = section.rows.iter()
+        .map(|row| row.iter().map(|cell| cell.render(OutputFormat::Table)).collect())
+        .collect();
+
+    let mut widths: Vec<usize> = section.columns.iter().map(String::len).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut out: String = String::new();
+    if let Some(title) = &section.title {
+        let _ = writeln!(out, "{}", title);
+    }
+    let line = |cells: &[String]| -> String {
+        cells.iter().zip(&widths)
+            .map(|(cell, width)| format!("{:>width$}", cell, width = *width))
+            .collect::<Vec<String>>()
+            .join("  ")
+    };
+    let _ = writeln!(out, "{}", line(&section.columns));
+    let _ = writeln!(out, "{}", widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<String>>().join("  "));
+    for row in &rows {
+        let _ = writeln!(out, "{}", line(row));
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_csv(section: &Section) -> String {
+    let mut out: String = String::new();
+    let _ = writeln!(out, "{}", section.columns.iter().map(|c| csv_field(c)).collect::<Vec<String>>().join(","));
+    for row in &section.rows {
+        let cells: Vec<String> = row.iter().map(|cell| csv_field(&cell.render(OutputFormat::Csv))).collect();
+        let _ = writeln!(out, "{}", cells.join(","));
+    }
+    out
+}
@@ -0,0 +1,75 @@
+use std::path::{Path, PathBuf};
+use clap::Args;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use server_quantbox::models::validation::Validate;
+
+// market inputs shared by every pricing command. flags override the same field in --file
+#[derive(Debug, Args)]
+pub struct MarketFlags {
+    /// JSON or TOML file with the request fields (the HTTP request body, or the same keys in TOML)
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+    /// spot price of the underlying
+    #[arg(long)]
+    pub spot: Option<f64>,
+    /// strike price
+    #[arg(long)]
+    pub strike: Option<f64>,
+    /// continuously compounded risk-free rate, e.g. 0.05
+    #[arg(long)]
+    pub rate: Option<f64>,
+    /// time to expiry in years
+    #[arg(long)]
+    pub time: Option<f64>,
+}
+
+impl MarketFlags {
+    // the time field is time_to_maturity for Black-Scholes and time_to_expiry for Monte Carlo
+    pub fn fields(&self, time_field: &'static str) -> Vec<(&'static str, Option<Value>)> {
+        vec![
+            ("spot_price", self.spot.map(Value::from)),
+            ("strike_price", self.strike.map(Value::from)),
+            ("risk_free_rate", self.rate.map(Value::from)),
+            (time_field, self.time.map(Value::from)),
+        ]
+    }
+}
+
+fn read_document(path: &Path) -> Result<Map<String, Value>, String> {
+    let text: String = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+
+    let is_toml: bool = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+    let document: Value = if is_toml {
+        toml::from_str(&text).map_err(|e| format!("{} is not valid TOML: {}", path.display(), e))?
+    } else {
+        serde_json::from_str(&text).map_err(|e| format!("{} is not valid JSON: {}", path.display(), e))?
+    };
+
+    match document {
+        Value::Object(fields) => Ok(fields),
+        _ => Err(format!("{} must contain an object of request fields", path.display())),
+    }
+}
+
+// builds a request the same way the HTTP API would: --file first, flags on top, then the
+// model's own serde defaults and validation
+pub fn load_request<T>(file: Option<&Path>, flags: Vec<(&'static str, Option<Value>)>) -> Result<T, String>
+where
+    T: DeserializeOwned + Validate,
+{
+    let mut fields: Map<String, Value> = match file {
+        Some(path) => read_document(path)?,
+        None => Map::new(),
+    };
+    for (name, value) in flags {
+        if let Some(value) = value {
+            fields.insert(name.to_string(), value);
+        }
+    }
+
+    let request: T = serde_json::from_value(Value::Object(fields)).map_err(|e| format!("invalid request: {}", e))?;
+    request.validate().map_err(|e| format!("{} ({}): {}", e.field, e.code, e.message))?;
+    Ok(request)
+}
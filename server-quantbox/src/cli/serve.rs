@@ -0,0 +1,165 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use axum::Router;
+use clap::Args;
+use tokio::net::TcpListener;
+use server_quantbox::api::app_state::AppState;
+use server_quantbox::api::rate_limiter::RateLimiter;
+use server_quantbox::api::routes::create_router;
+use server_quantbox::auth::authenticator::{Authenticator, spawn_usage_flush};
+use server_quantbox::compute::compute_pool::ComputePool;
+use server_quantbox::config::log_format::LogFormat;
+use server_quantbox::config::log_level::LogLevel;
+use server_quantbox::config::server_config::ServerConfig;
+use server_quantbox::grpc::grpc_server::serve_grpc;
+use server_quantbox::compute::parallel_monte_carlo_engine::ParallelMonteCarloEngine;
+use server_quantbox::jobs::job_store::{JobStore, spawn_expiry_sweep};
+use server_quantbox::service::pricing_service::PricingService;
+use server_quantbox::telemetry::Telemetry;
+
+// every flag is optional and overrides the config file and QUANTBOX_* environment variables
+#[derive(Debug, Default, Args)]
+pub struct ServeArgs {
+    /// TOML config file (default: $QUANTBOX_CONFIG, if set)
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
+    /// address and port to listen on [default: 0.0.0.0:8080]
+    #[arg(long)]
+    pub bind: Option<SocketAddr>,
+
+    /// address and port for the gRPC server [default: 0.0.0.0:50051]
+    #[arg(long)]
+    pub grpc_bind: Option<SocketAddr>,
+
+    /// origin allowed to call the API from a browser, or "*"; repeat for several
+    #[arg(long = "cors-origin")]
+    pub cors_origins: Vec<String>,
+
+    /// threads in the compute pool
+    #[arg(long)]
+    pub threads: Option<usize>,
+
+    /// most paths a single request may simulate
+    #[arg(long)]
+    pub max_simulations: Option<usize>,
+
+    /// seconds before a request is answered with a timeout
+    #[arg(long)]
+    pub request_timeout_secs: Option<u64>,
+
+    #[arg(long, value_enum)]
+    pub log_level: Option<LogLevel>,
+
+    #[arg(long, value_enum)]
+    pub log_format: Option<LogFormat>,
+
+    /// OTLP/gRPC collector to export traces to, e.g. http://localhost:4317
+    #[arg(long)]
+    pub otlp_endpoint: Option<String>,
+}
+
+impl ServeArgs {
+    fn apply(&self, config: &mut ServerConfig) {
+        if let Some(bind) = self.bind {
+            config.http.bind = bind;
+        }
+        if let Some(bind) = self.grpc_bind {
+            config.grpc.bind = bind;
+        }
+        if !self.cors_origins.is_empty() {
+            config.http.cors_origins = self.cors_origins.clone();
+        }
+        if let Some(threads) = self.threads {
+            config.compute.threads = threads;
+        }
+        if let Some(max_simulations) = self.max_simulations {
+            config.simulation.max_simulations = max_simulations;
+        }
+        if let Some(secs) = self.request_timeout_secs {
+            config.request_timeout_secs = secs;
+        }
+        if let Some(level) = self.log_level {
+            config.log_level = level;
+        }
+        if let Some(format) = self.log_format {
+            config.log_format = format;
+        }
+        if let Some(endpoint) = &self.otlp_endpoint {
+            config.otlp.endpoint = Some(endpoint.clone());
+        }
+    }
+}
+
+#[tokio::main]
+pub async fn serve(args: &ServeArgs) -> Result<(), String> {
+    let mut config: ServerConfig = ServerConfig::load(args.config.as_deref())?;
+    args.apply(&mut config);
+    config.validate()?;
+
+    let telemetry: Telemetry = Telemetry::init(&config)?;
+    tracing::info!("effective configuration:\n{}", config.to_toml());
+    ParallelMonteCarloEngine::configure(config.parallel_engine.clone())
+        .map_err(|_| "the parallel engine was already configured".to_string())?;
+
+    let job_store: Arc<JobStore> = Arc::new(
+        JobStore::new((&config.jobs).into()).map_err(|e| format!("failed to open job persistence directory: {}", e))?
+    );
+    spawn_expiry_sweep(Arc::clone(&job_store));
+
+    let compute_pool: Arc<ComputePool> = Arc::new(ComputePool::new(config.compute.clone()));
+    let pricing: PricingService = PricingService::new(Arc::clone(&compute_pool), config.stream_timeout());
+    let rate_limiter: Arc<RateLimiter> = Arc::new(RateLimiter::new(config.rate_limit.clone()));
+    let auth: Arc<Authenticator> = Arc::new(Authenticator::new(config.auth.clone())?);
+    spawn_usage_flush(Arc::clone(&auth));
+    let state: AppState = AppState {
+        compute_pool,
+        job_store,
+        pricing: pricing.clone(),
+        rate_limiter: Arc::clone(&rate_limiter),
+        auth: Arc::clone(&auth),
+        simulation: config.simulation,
+        request_timeout: config.request_timeout(),
+    };
+    let app: Router = create_router(state, &config);
+
+    let listener: TcpListener = TcpListener::bind(config.http.bind)
+        .await
+        .map_err(|e| format!("failed to bind {}: {}", config.http.bind, e))?;
+    let grpc_listener: TcpListener = TcpListener::bind(config.grpc.bind)
+        .await
+        .map_err(|e| format!("failed to bind {}: {}", config.grpc.bind, e))?;
+
+    tracing::info!(address = %listener.local_addr().unwrap(), "listening");
+    tracing::info!(address = %grpc_listener.local_addr().unwrap(), "gRPC listening");
+
+    // both servers share the compute pool; either one failing takes the process down.
+    // on ctrl-c they're dropped and the remaining spans flushed before exiting
+    let servers = async {
+        tokio::try_join!(
+            async {
+                // the peer address is what clients without an API key are rate limited by
+                axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+                    .await
+                    .map_err(|e| format!("server error: {}", e))
+            },
+            async {
+                serve_grpc(grpc_listener, pricing, rate_limiter, Arc::clone(&auth), &config)
+                    .await
+                    .map_err(|e| format!("gRPC server error: {}", e))
+            },
+        )
+    };
+    let outcome: Result<(), String> = tokio::select! {
+        outcome = servers => outcome.map(|_| ()),
+        _ = tokio::signal::ctrl_c() => {
+            tracing::info!("shutting down");
+            Ok(())
+        }
+    };
+
+    auth.flush_request_counts();
+    telemetry.shutdown().await;
+    outcome
+}
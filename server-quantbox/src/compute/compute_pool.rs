@@ -0,0 +1,246 @@
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::Instant;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, oneshot};
+use crate::metrics;
+use crate::models::compute_models::ComputeStats;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ComputePoolConfig {
+    pub threads: usize,
+    pub max_concurrent_jobs: usize,
+    pub max_queued_jobs: usize,
+    // background jobs queued or running; submissions past this get a 503
+    pub max_pending_jobs: usize,
+    pub retry_after_secs: u64,
+}
+
+impl Default for ComputePoolConfig {
+    fn default() -> Self {
+        let threads: usize = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4);
+
+        ComputePoolConfig {
+            threads,
+            max_concurrent_jobs: threads,
+            max_queued_jobs: threads * 4,
+            max_pending_jobs: threads * 16,
+            retry_after_secs: 1,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ComputeError {
+    // every slot is busy and the wait queue is full
+    Saturated { retry_after_secs: u64 },
+    // the job panicked (or the pool went away) before producing a result
+    Failed,
+}
+
+#[derive(Default)]
+struct ComputeCounters {
+    in_flight: AtomicUsize,
+    queued: AtomicUsize,
+    pending_jobs: AtomicUsize,
+    rejected_total: AtomicU64,
+}
+
+// decrements a counter when dropped, so cancelled requests don't leak queue slots
+struct CounterGuard<'a>(&'a AtomicUsize);
+
+impl Drop for CounterGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// raises the flag when dropped, i.e. once the caller has stopped waiting for the job
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+// a background job's place in the pending count, held from submission until the job is done
+pub struct PendingJob(Arc<ComputeCounters>);
+
+impl Drop for PendingJob {
+    fn drop(&mut self) {
+        self.0.pending_jobs.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// a concurrency slot taken ahead of time, for handlers that have to answer the client
+// (e.g. open a stream) before the job they're holding the slot for actually runs
+pub struct ComputeSlot(OwnedSemaphorePermit);
+
+// runs CPU-heavy pricing on a dedicated rayon pool so tokio workers stay free for I/O.
+// at most max_concurrent_jobs run at once, up to max_queued_jobs wait behind them,
+// and anything past that is turned away immediately. background jobs have a queue of their
+// own (max_pending_jobs), so a backlog of them doesn't turn synchronous requests away
+pub struct ComputePool {
+    pool: ThreadPool,
+    permits: Arc<Semaphore>,
+    counters: Arc<ComputeCounters>,
+    config: ComputePoolConfig,
+}
+
+impl ComputePool {
+    pub fn new(config: ComputePoolConfig) -> Self {
+        let pool: ThreadPool = ThreadPoolBuilder::new()
+            .num_threads(config.threads)
+            .thread_name(|i| format!("quantbox-compute-{}", i))
+            .build()
+            .expect("failed to build compute thread pool");
+
+        ComputePool {
+            pool,
+            permits: Arc::new(Semaphore::new(config.max_concurrent_jobs)),
+            counters: Arc::new(ComputeCounters::default()),
+            config,
+        }
+    }
+
+    pub async fn run<F, T>(&self, job: F) -> Result<T, ComputeError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let permit: OwnedSemaphorePermit = self.acquire_permit().await?;
+        self.spawn_with_permit(permit, job).await
+    }
+
+    // like run, but the job is handed a flag that's raised if the caller stops waiting for it
+    // (the request timed out, or the client went away). jobs check it at their checkpoints
+    // and return early, so an abandoned request gives its slot back instead of running on
+    pub async fn run_cancellable<F, T>(&self, job: F) -> Result<T, ComputeError>
+    where
+        F: FnOnce(&AtomicBool) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let cancel: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+        let _cancel_on_drop: CancelOnDrop = CancelOnDrop(Arc::clone(&cancel));
+
+        self.run(move || {
+            let value: T = job(&cancel);
+            if cancel.load(Ordering::Relaxed) {
+                metrics::record_cancelled_job();
+            }
+            value
+        }).await
+    }
+
+    // counts a background job in from submission, or turns it away once max_pending_jobs
+    // are already queued or running. pass the result to run_queued
+    pub fn admit_job(&self) -> Result<PendingJob, ComputeError> {
+        let pending: usize = self.counters.pending_jobs.fetch_add(1, Ordering::SeqCst);
+        let job: PendingJob = PendingJob(Arc::clone(&self.counters));
+        if pending >= self.config.max_pending_jobs {
+            self.counters.rejected_total.fetch_add(1, Ordering::SeqCst);
+            return Err(ComputeError::Saturated {
+                retry_after_secs: self.config.retry_after_secs,
+            });
+        }
+        Ok(job)
+    }
+
+    // like run, but waits for a slot however long it takes -- for background jobs, whose
+    // submitters have already been answered. they were bounded by admit_job instead, and
+    // don't count against the queue synchronous requests are turned away from
+    pub async fn run_queued<F, T>(&self, pending: PendingJob, job: F) -> Result<T, ComputeError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let permit: OwnedSemaphorePermit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .map_err(|_| ComputeError::Failed)?;
+        self.spawn_with_permit(permit, move || {
+            let _pending: PendingJob = pending;
+            job()
+        }).await
+    }
+
+    // same admission rules as run (including the 503 when saturated), but hands the slot back
+    // instead of running anything -- pair with run_reserved
+    pub async fn reserve(&self) -> Result<ComputeSlot, ComputeError> {
+        Ok(ComputeSlot(self.acquire_permit().await?))
+    }
+
+    pub async fn run_reserved<F, T>(&self, slot: ComputeSlot, job: F) -> Result<T, ComputeError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        self.spawn_with_permit(slot.0, job).await
+    }
+
+    async fn spawn_with_permit<F, T>(&self, permit: OwnedSemaphorePermit, job: F) -> Result<T, ComputeError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let counters: Arc<ComputeCounters> = Arc::clone(&self.counters);
+        let (tx, rx) = oneshot::channel();
+
+        counters.in_flight.fetch_add(1, Ordering::SeqCst);
+        // rayon parallel iterators called from inside the job run on this same pool
+        self.pool.spawn(move || {
+            let _in_flight: CounterGuard = CounterGuard(&counters.in_flight);
+            let _permit: OwnedSemaphorePermit = permit;
+
+            let start_time: Instant = Instant::now();
+            let result = catch_unwind(AssertUnwindSafe(job));
+            metrics::record_compute_job(start_time.elapsed(), result.is_err());
+            let _ = tx.send(result);
+        });
+
+        match rx.await {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(_)) | Err(_) => Err(ComputeError::Failed),
+        }
+    }
+
+    async fn acquire_permit(&self) -> Result<OwnedSemaphorePermit, ComputeError> {
+        if let Ok(permit) = Arc::clone(&self.permits).try_acquire_owned() {
+            return Ok(permit);
+        }
+
+        // no free slot -- join the queue if there's room, otherwise push back
+        let queued: usize = self.counters.queued.fetch_add(1, Ordering::SeqCst);
+        let _queued: CounterGuard = CounterGuard(&self.counters.queued);
+        if queued >= self.config.max_queued_jobs {
+            self.counters.rejected_total.fetch_add(1, Ordering::SeqCst);
+            return Err(ComputeError::Saturated {
+                retry_after_secs: self.config.retry_after_secs,
+            });
+        }
+
+        Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .map_err(|_| ComputeError::Failed)
+    }
+
+    pub fn stats(&self) -> ComputeStats {
+        ComputeStats {
+            threads: self.pool.current_num_threads(),
+            max_concurrent_jobs: self.config.max_concurrent_jobs,
+            max_queued_jobs: self.config.max_queued_jobs,
+            in_flight: self.counters.in_flight.load(Ordering::SeqCst),
+            queued: self.counters.queued.load(Ordering::SeqCst),
+            max_pending_jobs: self.config.max_pending_jobs,
+            pending_jobs: self.counters.pending_jobs.load(Ordering::SeqCst),
+            rejected_total: self.counters.rejected_total.load(Ordering::SeqCst),
+        }
+    }
+}
@@ -1,3 +1,4 @@
-pub mod black_scholes;
-pub mod monte_carlo_engine;
-pub mod parallel_monte_carlo_engine;
\ No newline at end of file
+pub use quantbox_core::compute::{accumulator, black_scholes, convergence, monte_carlo_engine, parallel_monte_carlo_engine,
+                                 simd_kernel, statistics};
+
+pub mod compute_pool;
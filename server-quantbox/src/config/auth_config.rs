@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+
+// API key and bearer token authentication. off by default; when on, every pricing request
+// has to name a tenant, and that tenant's simulated paths count against a daily quota
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    pub enabled: bool,
+    // SQLite file with the tenants, their API keys and their usage. created if missing
+    pub database: PathBuf,
+    // HS256 key that bearer tokens are signed with. bearer tokens are refused without one
+    pub jwt_secret: Option<String>,
+    // x-admin-token value for /api/v1/admin. the admin API is off without one
+    pub admin_token: Option<String>,
+    // paths a tenant may simulate per UTC day, unless it was given a quota of its own
+    pub default_daily_path_quota: u64,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        AuthConfig {
+            enabled: false,
+            database: PathBuf::from("quantbox-auth.db"),
+            jwt_secret: None,
+            admin_token: None,
+            default_daily_path_quota: 1_000_000_000,
+        }
+    }
+}
+
+impl AuthConfig {
+    // the same settings with the secrets blanked out, for printing
+    pub fn redacted(&self) -> AuthConfig {
+        let redact = |secret: &Option<String>| secret.as_ref().map(|_| "<redacted>".to_string());
+        AuthConfig {
+            jwt_secret: redact(&self.jwt_secret),
+            admin_token: redact(&self.admin_token),
+            ..self.clone()
+        }
+    }
+}
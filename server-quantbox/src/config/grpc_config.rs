@@ -0,0 +1,21 @@
+use std::net::SocketAddr;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_GRPC_BIND: &str = "0.0.0.0:50051";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GrpcConfig {
+    pub bind: SocketAddr,
+    // streamed batches arrive as one message, so this defaults to the REST batch body limit
+    pub max_message_bytes: usize,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        GrpcConfig {
+            bind: DEFAULT_GRPC_BIND.parse().expect("default gRPC bind address is valid"),
+            max_message_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
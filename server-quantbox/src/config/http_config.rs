@@ -0,0 +1,28 @@
+use std::net::SocketAddr;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_BIND: &str = "0.0.0.0:8080";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpConfig {
+    pub bind: SocketAddr,
+    // origins allowed to call the API from a browser. "*" allows any; empty turns CORS off
+    pub cors_origins: Vec<String>,
+    // largest JSON body the single-request endpoints accept
+    pub body_limit_bytes: usize,
+    // batch and bulk uploads. a full MAX_BATCH_OPTIONS batch in the row layout runs to
+    // roughly 150 bytes per option
+    pub batch_body_limit_bytes: usize,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        HttpConfig {
+            bind: DEFAULT_BIND.parse().expect("default bind address is valid"),
+            cors_origins: vec!["http://localhost:3000".to_string()],
+            body_limit_bytes: 2 * 1024 * 1024,
+            batch_body_limit_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::jobs::job_store::JobStoreConfig;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JobsConfig {
+    // how long a finished job stays retrievable
+    pub ttl_secs: u64,
+    // unset keeps jobs in memory only
+    pub persistence_dir: Option<PathBuf>,
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        JobsConfig {
+            ttl_secs: 3600,
+            persistence_dir: None,
+        }
+    }
+}
+
+impl From<&JobsConfig> for JobStoreConfig {
+    fn from(config: &JobsConfig) -> Self {
+        JobStoreConfig {
+            ttl: Duration::from_secs(config.ttl_secs),
+            persistence_dir: config.persistence_dir.clone(),
+        }
+    }
+}
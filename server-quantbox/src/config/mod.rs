@@ -0,0 +1,10 @@
+pub mod auth_config;
+pub mod grpc_config;
+pub mod http_config;
+pub mod jobs_config;
+pub mod log_format;
+pub mod log_level;
+pub mod otlp_config;
+pub mod rate_limit_config;
+pub mod server_config;
+pub mod simulation_config;
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+// OpenTelemetry trace export over OTLP/gRPC. off unless an endpoint is set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OtlpConfig {
+    // collector address, e.g. "http://localhost:4317"
+    pub endpoint: Option<String>,
+    pub service_name: String,
+}
+
+impl Default for OtlpConfig {
+    fn default() -> Self {
+        OtlpConfig {
+            endpoint: None,
+            service_name: "quantbox".to_string(),
+        }
+    }
+}
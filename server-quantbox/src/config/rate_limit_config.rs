@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+// per-client token bucket shared by REST and gRPC. clients are told apart by their
+// tenant once authenticated, or their IP address otherwise
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    // steady rate each client's bucket refills at
+    pub requests_per_second: f64,
+    // requests a client can make at once after being idle
+    pub burst: u32,
+    // take the client IP from X-Forwarded-For. only safe behind a proxy that sets it
+    pub trust_forwarded_for: bool,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        RateLimitConfig {
+            enabled: true,
+            requests_per_second: 20.0,
+            burst: 50,
+            trust_forwarded_for: false,
+        }
+    }
+}
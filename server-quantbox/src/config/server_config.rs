@@ -0,0 +1,327 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use axum::http::HeaderValue;
+use serde::{Deserialize, Serialize};
+use crate::compute::compute_pool::ComputePoolConfig;
+use crate::compute::parallel_monte_carlo_engine::ParallelEngineConfig;
+use crate::config::auth_config::AuthConfig;
+use crate::config::grpc_config::GrpcConfig;
+use crate::config::http_config::HttpConfig;
+use crate::config::jobs_config::JobsConfig;
+use crate::config::log_format::LogFormat;
+use crate::config::log_level::LogLevel;
+use crate::config::otlp_config::OtlpConfig;
+use crate::config::rate_limit_config::RateLimitConfig;
+use crate::config::simulation_config::SimulationConfig;
+use crate::models::validation::{Validate, validate_execution_settings};
+
+// path to a TOML config file, when --config isn't given
+pub const CONFIG_PATH_ENV: &str = "QUANTBOX_CONFIG";
+
+// everything `quantbox serve` can be configured with. built up in layers, each one
+// overriding the last: defaults, then the TOML file, then QUANTBOX_* environment
+// variables, then command line flags
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub log_level: LogLevel,
+    pub log_format: LogFormat,
+    // REST and gRPC requests that take longer than this are answered with a timeout
+    pub request_timeout_secs: u64,
+    // streams (SSE, websocket and the gRPC streaming RPCs) are cut off after this; they
+    // outlive the request timeout, which only covers opening them
+    pub stream_timeout_secs: u64,
+    pub http: HttpConfig,
+    pub grpc: GrpcConfig,
+    pub compute: ComputePoolConfig,
+    pub parallel_engine: ParallelEngineConfig,
+    pub jobs: JobsConfig,
+    pub simulation: SimulationConfig,
+    pub rate_limit: RateLimitConfig,
+    pub auth: AuthConfig,
+    pub otlp: OtlpConfig,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            log_level: LogLevel::default(),
+            log_format: LogFormat::default(),
+            request_timeout_secs: 300,
+            stream_timeout_secs: 900,
+            http: HttpConfig::default(),
+            grpc: GrpcConfig::default(),
+            compute: ComputePoolConfig::default(),
+            parallel_engine: ParallelEngineConfig::default(),
+            jobs: JobsConfig::default(),
+            simulation: SimulationConfig::default(),
+            rate_limit: RateLimitConfig::default(),
+            auth: AuthConfig::default(),
+            otlp: OtlpConfig::default(),
+        }
+    }
+}
+
+// Ok(None) when the variable is unset; a value that doesn't parse is an error rather
+// than silently falling back to the default
+fn env_value<T>(var: &dyn Fn(&str) -> Option<String>, key: &str) -> Result<Option<T>, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    match var(key) {
+        Some(value) => value.trim().parse()
+            .map(Some)
+            .map_err(|e| format!("{}={:?}: {}", key, value, e)),
+        None => Ok(None),
+    }
+}
+
+impl ServerConfig {
+    // defaults, the config file (`file`, else QUANTBOX_CONFIG) and the process environment
+    pub fn load(file: Option<&Path>) -> Result<Self, String> {
+        Self::from_sources(file, &|key| std::env::var(key).ok())
+    }
+
+    // same as load, reading environment variables through `var`
+    pub fn from_sources(file: Option<&Path>, var: &dyn Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let path: Option<PathBuf> = file.map(Path::to_path_buf).or_else(|| var(CONFIG_PATH_ENV).map(PathBuf::from));
+
+        let mut config: ServerConfig = match path {
+            Some(path) => {
+                let text: String = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+                toml::from_str(&text).map_err(|e| format!("invalid config file {}: {}", path.display(), e))?
+            }
+            None => ServerConfig::default(),
+        };
+        config.apply_env(var)?;
+
+        Ok(config)
+    }
+
+    fn apply_env(&mut self, var: &dyn Fn(&str) -> Option<String>) -> Result<(), String> {
+        if let Some(level) = env_value(var, "QUANTBOX_LOG_LEVEL")? {
+            self.log_level = level;
+        }
+        if let Some(format) = env_value(var, "QUANTBOX_LOG_FORMAT")? {
+            self.log_format = format;
+        }
+        if let Some(secs) = env_value(var, "QUANTBOX_REQUEST_TIMEOUT_SECS")? {
+            self.request_timeout_secs = secs;
+        }
+        if let Some(secs) = env_value(var, "QUANTBOX_STREAM_TIMEOUT_SECS")? {
+            self.stream_timeout_secs = secs;
+        }
+
+        if let Some(bind) = env_value(var, "QUANTBOX_BIND")? {
+            self.http.bind = bind;
+        }
+        // comma separated
+        if let Some(origins) = var("QUANTBOX_CORS_ORIGINS") {
+            self.http.cors_origins = origins.split(',')
+                .map(str::trim)
+                .filter(|origin| !origin.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+        if let Some(bytes) = env_value(var, "QUANTBOX_BODY_LIMIT_BYTES")? {
+            self.http.body_limit_bytes = bytes;
+        }
+        if let Some(bytes) = env_value(var, "QUANTBOX_BATCH_BODY_LIMIT_BYTES")? {
+            self.http.batch_body_limit_bytes = bytes;
+        }
+
+        if let Some(bind) = env_value(var, "QUANTBOX_GRPC_BIND")? {
+            self.grpc.bind = bind;
+        }
+        if let Some(bytes) = env_value(var, "QUANTBOX_GRPC_MAX_MESSAGE_BYTES")? {
+            self.grpc.max_message_bytes = bytes;
+        }
+
+        if let Some(threads) = env_value(var, "QUANTBOX_COMPUTE_THREADS")? {
+            self.compute.threads = threads;
+        }
+        if let Some(jobs) = env_value(var, "QUANTBOX_MAX_CONCURRENT_JOBS")? {
+            self.compute.max_concurrent_jobs = jobs;
+        }
+        if let Some(jobs) = env_value(var, "QUANTBOX_MAX_QUEUED_JOBS")? {
+            self.compute.max_queued_jobs = jobs;
+        }
+        if let Some(jobs) = env_value(var, "QUANTBOX_MAX_PENDING_JOBS")? {
+            self.compute.max_pending_jobs = jobs;
+        }
+        if let Some(secs) = env_value(var, "QUANTBOX_RETRY_AFTER_SECS")? {
+            self.compute.retry_after_secs = secs;
+        }
+
+        if let Some(threads) = env_value(var, "QUANTBOX_THREADS_PER_REQUEST")? {
+            self.parallel_engine.threads_per_request = Some(threads);
+        }
+        if let Some(chunk_size) = env_value(var, "QUANTBOX_CHUNK_SIZE")? {
+            self.parallel_engine.chunk_size = chunk_size;
+        }
+
+        if let Some(secs) = env_value(var, "QUANTBOX_JOB_TTL_SECS")? {
+            self.jobs.ttl_secs = secs;
+        }
+        if let Some(dir) = env_value(var, "QUANTBOX_JOB_DIR")? {
+            self.jobs.persistence_dir = Some(dir);
+        }
+
+        if let Some(count) = env_value(var, "QUANTBOX_DEFAULT_SIMULATIONS")? {
+            self.simulation.default_num_simulations = count;
+        }
+        if let Some(count) = env_value(var, "QUANTBOX_CONVERGENCE_MAX_SIMULATIONS")? {
+            self.simulation.default_convergence_max_simulations = count;
+        }
+        if let Some(count) = env_value(var, "QUANTBOX_CONVERGENCE_STEP_SIZE")? {
+            self.simulation.default_convergence_step_size = count;
+        }
+        if let Some(count) = env_value(var, "QUANTBOX_MAX_SIMULATIONS")? {
+            self.simulation.max_simulations = count;
+        }
+        if let Some(count) = env_value(var, "QUANTBOX_MAX_CONVERGENCE_POINTS")? {
+            self.simulation.max_convergence_points = count;
+        }
+        if let Some(count) = env_value(var, "QUANTBOX_MAX_BATCH_OPTIONS")? {
+            self.simulation.max_batch_options = count;
+        }
+
+        if let Some(enabled) = env_value(var, "QUANTBOX_RATE_LIMIT_ENABLED")? {
+            self.rate_limit.enabled = enabled;
+        }
+        if let Some(rate) = env_value(var, "QUANTBOX_RATE_LIMIT_RPS")? {
+            self.rate_limit.requests_per_second = rate;
+        }
+        if let Some(burst) = env_value(var, "QUANTBOX_RATE_LIMIT_BURST")? {
+            self.rate_limit.burst = burst;
+        }
+        if let Some(trust) = env_value(var, "QUANTBOX_TRUST_FORWARDED_FOR")? {
+            self.rate_limit.trust_forwarded_for = trust;
+        }
+
+        if let Some(enabled) = env_value(var, "QUANTBOX_AUTH_ENABLED")? {
+            self.auth.enabled = enabled;
+        }
+        if let Some(path) = env_value(var, "QUANTBOX_AUTH_DATABASE")? {
+            self.auth.database = path;
+        }
+        if let Some(secret) = var("QUANTBOX_JWT_SECRET") {
+            self.auth.jwt_secret = Some(secret).filter(|secret| !secret.is_empty());
+        }
+        if let Some(token) = var("QUANTBOX_ADMIN_TOKEN") {
+            self.auth.admin_token = Some(token).filter(|token| !token.is_empty());
+        }
+        if let Some(quota) = env_value(var, "QUANTBOX_DAILY_PATH_QUOTA")? {
+            self.auth.default_daily_path_quota = quota;
+        }
+
+        if let Some(endpoint) = var("QUANTBOX_OTLP_ENDPOINT") {
+            self.otlp.endpoint = Some(endpoint.trim().to_string()).filter(|endpoint| !endpoint.is_empty());
+        }
+        if let Some(name) = var("QUANTBOX_OTLP_SERVICE_NAME") {
+            self.otlp.service_name = name.trim().to_string();
+        }
+
+        Ok(())
+    }
+
+    // checks the merged result, reporting every problem at once
+    pub fn validate(&self) -> Result<(), String> {
+        let mut problems: Vec<String> = Vec::new();
+        let mut require = |ok: bool, field: &str, message: &str| {
+            if !ok {
+                problems.push(format!("{}: {}", field, message));
+            }
+        };
+
+        require(self.request_timeout_secs > 0, "request_timeout_secs", "must be greater than 0");
+        require(self.stream_timeout_secs > 0, "stream_timeout_secs", "must be greater than 0");
+        require(self.http.body_limit_bytes > 0, "http.body_limit_bytes", "must be greater than 0");
+        require(self.http.batch_body_limit_bytes > 0, "http.batch_body_limit_bytes", "must be greater than 0");
+        require(self.grpc.max_message_bytes > 0, "grpc.max_message_bytes", "must be greater than 0");
+        require(
+            self.http.bind.port() == 0 || self.http.bind != self.grpc.bind,
+            "grpc.bind",
+            "must differ from http.bind",
+        );
+        for origin in &self.http.cors_origins {
+            let valid: bool = origin == "*"
+                || ((origin.starts_with("http://") || origin.starts_with("https://")) && HeaderValue::from_str(origin).is_ok());
+            require(valid, "http.cors_origins", &format!("{:?} is not \"*\" or an http(s) origin", origin));
+        }
+        require(
+            self.http.cors_origins.len() <= 1 || !self.http.cors_origins.iter().any(|origin| origin == "*"),
+            "http.cors_origins",
+            "\"*\" can't be combined with other origins",
+        );
+
+        require(self.compute.threads > 0, "compute.threads", "must be greater than 0");
+        require(self.compute.max_concurrent_jobs > 0, "compute.max_concurrent_jobs", "must be greater than 0");
+        require(self.compute.max_pending_jobs > 0, "compute.max_pending_jobs", "must be greater than 0");
+        require(self.compute.retry_after_secs > 0, "compute.retry_after_secs", "must be greater than 0");
+        if let Err(err) = validate_execution_settings(self.parallel_engine.threads_per_request, Some(self.parallel_engine.chunk_size)) {
+            let field: &str = if err.field == "num_threads" { "parallel_engine.threads_per_request" } else { "parallel_engine.chunk_size" };
+            require(false, field, &err.message);
+        }
+
+        require(self.jobs.ttl_secs > 0, "jobs.ttl_secs", "must be greater than 0");
+        if let Err(err) = self.simulation.validate() {
+            require(false, &format!("simulation.{}", err.field), &err.message);
+        }
+
+        if self.rate_limit.enabled {
+            require(
+                self.rate_limit.requests_per_second.is_finite() && self.rate_limit.requests_per_second > 0.0,
+                "rate_limit.requests_per_second",
+                "must be greater than 0",
+            );
+            require(self.rate_limit.burst > 0, "rate_limit.burst", "must be greater than 0");
+        }
+
+        if self.auth.enabled {
+            require(!self.auth.database.as_os_str().is_empty(), "auth.database", "must not be empty");
+            require(self.auth.default_daily_path_quota > 0, "auth.default_daily_path_quota", "must be greater than 0");
+        }
+        // short secrets can be brute forced offline from a single token
+        if let Some(secret) = &self.auth.jwt_secret {
+            require(secret.len() >= 32, "auth.jwt_secret", "must be at least 32 bytes");
+        }
+        if let Some(token) = &self.auth.admin_token {
+            require(token.len() >= 16, "auth.admin_token", "must be at least 16 characters");
+        }
+
+        if let Some(endpoint) = &self.otlp.endpoint {
+            require(
+                endpoint.starts_with("http://") || endpoint.starts_with("https://"),
+                "otlp.endpoint",
+                &format!("{:?} is not an http(s) URL", endpoint),
+            );
+        }
+        require(!self.otlp.service_name.is_empty(), "otlp.service_name", "must not be empty");
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("invalid configuration:\n  {}", problems.join("\n  ")))
+        }
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_secs)
+    }
+
+    pub fn stream_timeout(&self) -> Duration {
+        Duration::from_secs(self.stream_timeout_secs)
+    }
+
+    // the merged config in the same format as the file, for printing on boot. secrets are
+    // left out so the output is safe to log
+    pub fn to_toml(&self) -> String {
+        let printed: ServerConfig = ServerConfig { auth: self.auth.redacted(), ..self.clone() };
+        toml::to_string_pretty(&printed).unwrap_or_else(|e| format!("<unprintable config: {}>", e))
+    }
+}
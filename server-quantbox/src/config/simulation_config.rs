@@ -0,0 +1,292 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::models::auth_models::CreateApiKeyRequest;
+use crate::models::black_scholes_models::{BatchPricingRequest, BlackScholesRequest, GreekRequest, MAX_BATCH_OPTIONS};
+use crate::models::bulk_models::BulkPricingQuery;
+use crate::models::job_models::JobRequest;
+use crate::models::monte_carlo_models::{ConvergenceRequest, DEFAULT_CONVERGENCE_MAX_SIMULATIONS, DEFAULT_CONVERGENCE_STEP_SIZE,
+                                        DEFAULT_NUM_SIMULATIONS, MonteCarloRequest, StreamRequest, TargetPrecisionRequest};
+use crate::models::simulation_models::SimulationRequest;
+use crate::models::validation::{MAX_CONVERGENCE_POINTS, MAX_SIMULATIONS, MAX_STEP_SIZE, Validate, ValidationError};
+
+const DEFAULT_MAX_CONVERGENCE_POINTS: usize = 1_000;
+
+// simulation counts for requests that leave them out, and the per-request caps on how
+// much work one request can ask for. the core models only know their fixed MAX_* ceilings;
+// these are applied on top of them, where requests come into the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SimulationConfig {
+    pub default_num_simulations: usize,
+    pub default_convergence_max_simulations: usize,
+    pub default_convergence_step_size: usize,
+    // can only lower MAX_SIMULATIONS, never raise it
+    pub max_simulations: usize,
+    // max_simulations / step_size of a convergence analysis, up to MAX_CONVERGENCE_POINTS
+    pub max_convergence_points: usize,
+    // options in one batch or bulk table, up to MAX_BATCH_OPTIONS
+    pub max_batch_options: usize,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        SimulationConfig {
+            default_num_simulations: DEFAULT_NUM_SIMULATIONS,
+            default_convergence_max_simulations: DEFAULT_CONVERGENCE_MAX_SIMULATIONS,
+            default_convergence_step_size: DEFAULT_CONVERGENCE_STEP_SIZE,
+            max_simulations: MAX_SIMULATIONS,
+            max_convergence_points: DEFAULT_MAX_CONVERGENCE_POINTS,
+            max_batch_options: MAX_BATCH_OPTIONS,
+        }
+    }
+}
+
+impl SimulationConfig {
+    pub fn check_simulations(&self, field: &'static str, value: usize) -> Result<(), ValidationError> {
+        if value > self.max_simulations {
+            return Err(ValidationError::new(
+                "too_many_simulations",
+                field,
+                format!("{} must be at most {}, got {}", field, self.max_simulations, value),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn check_convergence_points(&self, points: usize) -> Result<(), ValidationError> {
+        if points > self.max_convergence_points {
+            return Err(ValidationError::new(
+                "too_many_points",
+                "step_size",
+                format!(
+                    "max_simulations / step_size gives {} convergence points, at most {} are allowed; use a larger step_size",
+                    points, self.max_convergence_points
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn check_batch_options(&self, field: &'static str, count: usize) -> Result<(), ValidationError> {
+        if count > self.max_batch_options {
+            return Err(ValidationError::new(
+                "too_many_options",
+                field,
+                format!("at most {} options per batch, got {}", self.max_batch_options, count),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Validate for SimulationConfig {
+    fn validate(&self) -> Result<(), ValidationError> {
+        if self.max_simulations < 2 || self.max_simulations > MAX_SIMULATIONS {
+            return Err(ValidationError::new(
+                "out_of_range",
+                "max_simulations",
+                format!("max_simulations must be between 2 and {}, got {}", MAX_SIMULATIONS, self.max_simulations),
+            ));
+        }
+        if self.max_convergence_points < 1 || self.max_convergence_points > MAX_CONVERGENCE_POINTS {
+            return Err(ValidationError::new(
+                "out_of_range",
+                "max_convergence_points",
+                format!("max_convergence_points must be between 1 and {}, got {}", MAX_CONVERGENCE_POINTS, self.max_convergence_points),
+            ));
+        }
+        if self.max_batch_options < 1 || self.max_batch_options > MAX_BATCH_OPTIONS {
+            return Err(ValidationError::new(
+                "out_of_range",
+                "max_batch_options",
+                format!("max_batch_options must be between 1 and {}, got {}", MAX_BATCH_OPTIONS, self.max_batch_options),
+            ));
+        }
+
+        // every default has to pass the cap it sits under
+        let defaults: [(&'static str, usize); 3] = [
+            ("default_num_simulations", self.default_num_simulations),
+            ("default_convergence_max_simulations", self.default_convergence_max_simulations),
+            ("default_convergence_step_size", self.default_convergence_step_size),
+        ];
+        for (field, value) in defaults {
+            if value < 2 || value > self.max_simulations {
+                return Err(ValidationError::new(
+                    "out_of_range",
+                    field,
+                    format!("{} must be between 2 and max_simulations ({}), got {}", field, self.max_simulations, value),
+                ));
+            }
+        }
+
+        if self.default_convergence_step_size > MAX_STEP_SIZE {
+            return Err(ValidationError::new(
+                "out_of_range",
+                "default_convergence_step_size",
+                format!("default_convergence_step_size must be at most {}, got {}", MAX_STEP_SIZE, self.default_convergence_step_size),
+            ));
+        }
+        if self.default_convergence_step_size > self.default_convergence_max_simulations {
+            return Err(ValidationError::new(
+                "out_of_range",
+                "default_convergence_step_size",
+                format!(
+                    "default_convergence_step_size ({}) must be at most default_convergence_max_simulations ({})",
+                    self.default_convergence_step_size, self.default_convergence_max_simulations
+                ),
+            ));
+        }
+        let default_points: usize = self.default_convergence_max_simulations / self.default_convergence_step_size;
+        if default_points > self.max_convergence_points {
+            return Err(ValidationError::new(
+                "out_of_range",
+                "default_convergence_step_size",
+                format!(
+                    "the default convergence analysis has {} points, more than max_convergence_points ({})",
+                    default_points, self.max_convergence_points
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+// the server's SimulationConfig applied to a request, after its own Validate has passed
+pub trait WithinLimits {
+    // whether fill_defaults has anything to do, i.e. whether the body has to be looked at
+    // before it's deserialized. batches don't, so they're never buffered as a Value
+    const HAS_DEFAULTS: bool = false;
+
+    // swaps in the configured default for each simulation count `body` leaves out
+    fn fill_defaults(&mut self, _body: &Value, _config: &SimulationConfig) {}
+
+    fn check_limits(&self, _config: &SimulationConfig) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
+
+fn omits(body: &Value, field: &str) -> bool {
+    body.get(field).is_none()
+}
+
+impl WithinLimits for MonteCarloRequest {
+    const HAS_DEFAULTS: bool = true;
+
+    fn fill_defaults(&mut self, body: &Value, config: &SimulationConfig) {
+        if omits(body, "num_simulations") {
+            self.num_simulations = config.default_num_simulations;
+        }
+    }
+
+    fn check_limits(&self, config: &SimulationConfig) -> Result<(), ValidationError> {
+        config.check_simulations("num_simulations", self.num_simulations)
+    }
+}
+
+impl WithinLimits for ConvergenceRequest {
+    const HAS_DEFAULTS: bool = true;
+
+    fn fill_defaults(&mut self, body: &Value, config: &SimulationConfig) {
+        if omits(body, "max_simulations") {
+            self.max_simulations = config.default_convergence_max_simulations;
+        }
+        if omits(body, "step_size") {
+            self.step_size = config.default_convergence_step_size;
+        }
+    }
+
+    fn check_limits(&self, config: &SimulationConfig) -> Result<(), ValidationError> {
+        config.check_simulations("step_size", self.step_size)?;
+        config.check_simulations("max_simulations", self.max_simulations)?;
+        config.check_convergence_points(self.max_simulations / self.step_size)
+    }
+}
+
+impl WithinLimits for TargetPrecisionRequest {
+    const HAS_DEFAULTS: bool = true;
+
+    fn fill_defaults(&mut self, body: &Value, config: &SimulationConfig) {
+        if omits(body, "max_simulations") {
+            self.max_simulations = config.max_simulations;
+        }
+    }
+
+    fn check_limits(&self, config: &SimulationConfig) -> Result<(), ValidationError> {
+        config.check_simulations("max_simulations", self.max_simulations)
+    }
+}
+
+impl WithinLimits for SimulationRequest {
+    const HAS_DEFAULTS: bool = true;
+
+    // the pricing fields are flattened into the top level of the body
+    fn fill_defaults(&mut self, body: &Value, config: &SimulationConfig) {
+        self.params.fill_defaults(body, config);
+    }
+
+    fn check_limits(&self, config: &SimulationConfig) -> Result<(), ValidationError> {
+        self.params.check_limits(config)
+    }
+}
+
+impl WithinLimits for BatchPricingRequest {
+    fn check_limits(&self, config: &SimulationConfig) -> Result<(), ValidationError> {
+        let count: usize = match &self.options {
+            Some(options) => options.len(),
+            None => self.spot_price.as_ref().map_or(0, Vec::len),
+        };
+        config.check_batch_options("options", count)
+    }
+}
+
+impl WithinLimits for BlackScholesRequest {}
+
+impl WithinLimits for GreekRequest {}
+
+impl WithinLimits for CreateApiKeyRequest {}
+
+// the row count is only known once the table is read; price_table checks it
+impl WithinLimits for BulkPricingQuery {}
+
+// {"kind": ..., "params": {...}}: the defaults come from params
+impl WithinLimits for JobRequest {
+    const HAS_DEFAULTS: bool = true;
+
+    fn fill_defaults(&mut self, body: &Value, config: &SimulationConfig) {
+        let params: &Value = body.get("params").unwrap_or(&Value::Null);
+        match self {
+            JobRequest::BlackScholes(_) | JobRequest::Greeks(_) | JobRequest::Heatmap(_) => {}
+            JobRequest::MonteCarlo(req) | JobRequest::MonteCarloParallel(req) | JobRequest::Comparison(req) => req.fill_defaults(params, config),
+            JobRequest::Convergence(req) => req.fill_defaults(params, config),
+            JobRequest::TargetPrecision(req) => req.fill_defaults(params, config),
+        }
+    }
+
+    fn check_limits(&self, config: &SimulationConfig) -> Result<(), ValidationError> {
+        match self {
+            JobRequest::BlackScholes(_) | JobRequest::Greeks(_) | JobRequest::Heatmap(_) => Ok(()),
+            JobRequest::MonteCarlo(req) | JobRequest::MonteCarloParallel(req) | JobRequest::Comparison(req) => req.check_limits(config),
+            JobRequest::Convergence(req) => req.check_limits(config),
+            JobRequest::TargetPrecision(req) => req.check_limits(config),
+        }
+    }
+}
+
+impl WithinLimits for StreamRequest {
+    const HAS_DEFAULTS: bool = true;
+
+    fn fill_defaults(&mut self, body: &Value, config: &SimulationConfig) {
+        let params: &Value = body.get("params").unwrap_or(&Value::Null);
+        match self {
+            StreamRequest::Convergence(req) => req.fill_defaults(params, config),
+            StreamRequest::MonteCarloParallel(req) => req.fill_defaults(params, config),
+        }
+    }
+
+    fn check_limits(&self, config: &SimulationConfig) -> Result<(), ValidationError> {
+        match self {
+            StreamRequest::Convergence(req) => req.check_limits(config),
+            StreamRequest::MonteCarloParallel(req) => req.check_limits(config),
+        }
+    }
+}
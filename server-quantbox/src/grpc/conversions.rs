@@ -0,0 +1,363 @@
+use tonic::{Code, Status};
+use crate::api::api_error::ApiError;
+use crate::grpc::proto;
+use crate::models::black_scholes_models::{BatchPricingRequest, BatchValuation, BlackScholesRequest,
+                                          BlackScholesResult, GreekRequest, Greeks, HeatmapData, OptionType};
+use crate::models::monte_carlo_models::{ChunkSize, ConvergencePoint, ConvergenceRate, ConvergenceRequest, ConvergenceResponse,
+                                        DEFAULT_CONVERGENCE_MAX_SIMULATIONS, DEFAULT_CONVERGENCE_STEP_SIZE, DEFAULT_NUM_SIMULATIONS,
+                                        ExecutionStats, InstrumentStatistics, IntervalMethod, MonteCarloRequest,
+                                        MonteCarloResponse, ParityDiagnostics, PayoffSpec, SimulationKernel, WorkerStats};
+use crate::models::validation::ValidationError;
+use crate::service::batch_chunk::BatchChunk;
+use crate::service::stream_event::ConvergenceEvent;
+
+// same split as the HTTP status codes in api_error: bad input -> INVALID_ARGUMENT,
+// missing credentials -> UNAUTHENTICATED, rate limited or over quota -> RESOURCE_EXHAUSTED
+// and saturated pool -> UNAVAILABLE, the last two with the retry delay in retry-after metadata
+impl From<ApiError> for Status {
+    fn from(err: ApiError) -> Self {
+        let code: Code = match &err {
+            ApiError::BadRequest { .. } | ApiError::Validation(_) => Code::InvalidArgument,
+            ApiError::Unauthorized { .. } => Code::Unauthenticated,
+            ApiError::Forbidden { .. } => Code::PermissionDenied,
+            ApiError::NotFound { .. } => Code::NotFound,
+            ApiError::PayloadTooLarge { .. } | ApiError::RateLimited { .. } | ApiError::QuotaExceeded { .. } => Code::ResourceExhausted,
+            ApiError::Internal { .. } => Code::Internal,
+            ApiError::Overloaded { .. } => Code::Unavailable,
+            ApiError::TimedOut { .. } => Code::DeadlineExceeded,
+        };
+        let retry_after: Option<u64> = match err {
+            ApiError::Overloaded { retry_after_secs }
+            | ApiError::RateLimited { retry_after_secs }
+            | ApiError::QuotaExceeded { retry_after_secs, .. } => Some(retry_after_secs),
+            _ => None,
+        };
+
+        let body = err.into_body();
+        let message: String = match body.field {
+            Some(field) => format!("{} ({}): {}", body.code, field, body.message),
+            None => format!("{}: {}", body.code, body.message),
+        };
+
+        let mut status: Status = Status::new(code, message);
+        if let Some(secs) = retry_after {
+            status.metadata_mut().insert("retry-after", secs.into());
+        }
+        status
+    }
+}
+
+fn chunk_size(value: Option<u64>) -> Option<ChunkSize> {
+    value.map(|n| if n == 0 { ChunkSize::Auto } else { ChunkSize::Fixed(n as usize) })
+}
+
+fn kernel(value: i32) -> Result<SimulationKernel, ValidationError> {
+    match proto::SimulationKernel::try_from(value) {
+        Ok(proto::SimulationKernel::Scalar) => Ok(SimulationKernel::Scalar),
+        Ok(proto::SimulationKernel::Simd) => Ok(SimulationKernel::Simd),
+        Err(_) => Err(ValidationError::new("invalid_value", "kernel", format!("unknown simulation kernel {}", value))),
+    }
+}
+
+fn interval_method(value: i32) -> Result<IntervalMethod, ValidationError> {
+    match proto::IntervalMethod::try_from(value) {
+        Ok(proto::IntervalMethod::Normal) => Ok(IntervalMethod::Normal),
+        Ok(proto::IntervalMethod::StudentT) => Ok(IntervalMethod::StudentT),
+        Err(_) => Err(ValidationError::new("invalid_value", "interval_method", format!("unknown interval method {}", value))),
+    }
+}
+
+fn interval((lower, upper): (f64, f64)) -> proto::ConfidenceInterval {
+    proto::ConfidenceInterval { lower, upper }
+}
+
+impl From<proto::BlackScholesRequest> for BlackScholesRequest {
+    fn from(req: proto::BlackScholesRequest) -> Self {
+        BlackScholesRequest {
+            spot_price: req.spot_price,
+            strike_price: req.strike_price,
+            risk_free_rate: req.risk_free_rate,
+            volatility: req.volatility,
+            time_to_maturity: req.time_to_maturity,
+        }
+    }
+}
+
+impl TryFrom<proto::GreekRequest> for GreekRequest {
+    type Error = ValidationError;
+
+    fn try_from(req: proto::GreekRequest) -> Result<Self, Self::Error> {
+        let scholes: proto::BlackScholesRequest = req.scholes
+            .ok_or_else(|| ValidationError::new("missing_field", "scholes", "scholes is required"))?;
+        let option_type: OptionType = match proto::OptionType::try_from(req.option_type) {
+            Ok(proto::OptionType::Call) => OptionType::Call,
+            Ok(proto::OptionType::Put) => OptionType::Put,
+            _ => return Err(ValidationError::new("invalid_value", "option_type", "option_type must be CALL or PUT")),
+        };
+
+        Ok(GreekRequest { scholes: scholes.into(), option_type })
+    }
+}
+
+impl TryFrom<proto::MonteCarloRequest> for MonteCarloRequest {
+    type Error = ValidationError;
+
+    fn try_from(req: proto::MonteCarloRequest) -> Result<Self, Self::Error> {
+        let mut params: MonteCarloRequest = MonteCarloRequest::new(
+            req.spot_price,
+            req.strike_price,
+            req.time_to_expiry,
+            req.risk_free_rate,
+            req.volatility,
+            // unset optional fields get the built-in defaults; the gRPC service fills in the
+            // configured ones before converting
+            req.num_simulations.map_or(DEFAULT_NUM_SIMULATIONS, |n| n as usize),
+        );
+        if let Some(confidence_level) = req.confidence_level {
+            params.confidence_level = confidence_level;
+        }
+        params.interval_method = interval_method(req.interval_method)?;
+        params.kernel = kernel(req.kernel)?;
+        params.num_threads = req.num_threads.map(|n| n as usize);
+        params.chunk_size = chunk_size(req.chunk_size);
+
+        for payoff in req.extra_payoffs {
+            let strike: f64 = payoff.strike;
+            params.extra_payoffs.push(match proto::PayoffKind::try_from(payoff.kind) {
+                Ok(proto::PayoffKind::Call) => PayoffSpec::Call { strike },
+                Ok(proto::PayoffKind::Put) => PayoffSpec::Put { strike },
+                Ok(proto::PayoffKind::DigitalCall) => PayoffSpec::DigitalCall { strike },
+                Ok(proto::PayoffKind::DigitalPut) => PayoffSpec::DigitalPut { strike },
+                _ => return Err(ValidationError::new("invalid_value", "extra_payoffs", "every extra payoff needs a kind")),
+            });
+        }
+
+        Ok(params)
+    }
+}
+
+impl TryFrom<proto::ConvergenceRequest> for ConvergenceRequest {
+    type Error = ValidationError;
+
+    fn try_from(req: proto::ConvergenceRequest) -> Result<Self, Self::Error> {
+        Ok(ConvergenceRequest {
+            spot_price: req.spot_price,
+            strike_price: req.strike_price,
+            time_to_expiry: req.time_to_expiry,
+            risk_free_rate: req.risk_free_rate,
+            volatility: req.volatility,
+            max_simulations: req.max_simulations.map_or(DEFAULT_CONVERGENCE_MAX_SIMULATIONS, |n| n as usize),
+            step_size: req.step_size.map_or(DEFAULT_CONVERGENCE_STEP_SIZE, |n| n as usize),
+            kernel: kernel(req.kernel)?,
+            num_threads: req.num_threads.map(|n| n as usize),
+            chunk_size: chunk_size(req.chunk_size),
+        })
+    }
+}
+
+// the columns are moved over as-is; validate() then applies the REST batch rules
+impl From<proto::BatchPricingRequest> for BatchPricingRequest {
+    fn from(req: proto::BatchPricingRequest) -> Self {
+        let is_columnar: bool = req.options.is_empty();
+        let column = |values: Vec<f64>| (is_columnar || !values.is_empty()).then_some(values);
+
+        BatchPricingRequest {
+            options: if is_columnar { None } else { Some(req.options.into_iter().map(BlackScholesRequest::from).collect()) },
+            spot_price: column(req.spot_price),
+            strike_price: column(req.strike_price),
+            risk_free_rate: column(req.risk_free_rate),
+            volatility: column(req.volatility),
+            time_to_maturity: column(req.time_to_maturity),
+        }
+    }
+}
+
+impl From<BlackScholesResult> for proto::OptionsPrices {
+    fn from(result: BlackScholesResult) -> Self {
+        proto::OptionsPrices {
+            call_price: result.call_price,
+            put_price: result.put_price,
+        }
+    }
+}
+
+impl From<Greeks> for proto::Greeks {
+    fn from(greeks: Greeks) -> Self {
+        proto::Greeks {
+            delta: greeks.delta,
+            gamma: greeks.gamma,
+            theta: greeks.theta,
+            vega: greeks.vega,
+            rho: greeks.rho,
+        }
+    }
+}
+
+impl From<HeatmapData> for proto::HeatmapData {
+    fn from(heatmap: HeatmapData) -> Self {
+        let rows = |data: Vec<Vec<String>>| data.into_iter()
+            .map(|prices: Vec<String>| proto::HeatmapRow { prices })
+            .collect();
+
+        proto::HeatmapData {
+            spot_prices: heatmap.spot_prices,
+            volatilities: heatmap.volatilities,
+            call_data: rows(heatmap.call_data),
+            put_data: rows(heatmap.put_data),
+        }
+    }
+}
+
+impl From<InstrumentStatistics> for proto::InstrumentStatistics {
+    fn from(stats: InstrumentStatistics) -> Self {
+        proto::InstrumentStatistics {
+            name: stats.name,
+            strike: stats.strike,
+            price: stats.price,
+            standard_error: stats.standard_error,
+            confidence_interval: Some(interval(stats.confidence_interval)),
+            relative_error: stats.relative_error,
+            effective_sample_size: stats.effective_sample_size,
+        }
+    }
+}
+
+impl From<ParityDiagnostics> for proto::ParityDiagnostics {
+    fn from(parity: ParityDiagnostics) -> Self {
+        proto::ParityDiagnostics {
+            simulated_call_minus_put: parity.simulated_call_minus_put,
+            theoretical_call_minus_put: parity.theoretical_call_minus_put,
+            difference: parity.difference,
+            standard_error: parity.standard_error,
+            z_score: parity.z_score,
+        }
+    }
+}
+
+impl From<WorkerStats> for proto::WorkerStats {
+    fn from(worker: WorkerStats) -> Self {
+        proto::WorkerStats {
+            thread: worker.thread as u64,
+            chunks: worker.chunks as u64,
+            paths: worker.paths as u64,
+            busy_ms: worker.busy_ms,
+        }
+    }
+}
+
+impl From<ExecutionStats> for proto::ExecutionStats {
+    fn from(stats: ExecutionStats) -> Self {
+        proto::ExecutionStats {
+            num_threads: stats.num_threads as u64,
+            chunk_size: stats.chunk_size as u64,
+            auto_tuned: stats.auto_tuned,
+            num_chunks: stats.num_chunks as u64,
+            wall_time_ms: stats.wall_time_ms,
+            busy_time_ms: stats.busy_time_ms,
+            parallel_efficiency: stats.parallel_efficiency,
+            load_imbalance: stats.load_imbalance,
+            threads: stats.threads.into_iter().map(proto::WorkerStats::from).collect(),
+        }
+    }
+}
+
+impl From<MonteCarloResponse> for proto::MonteCarloResponse {
+    fn from(response: MonteCarloResponse) -> Self {
+        let interval_method: proto::IntervalMethod = match response.interval_method {
+            IntervalMethod::Normal => proto::IntervalMethod::Normal,
+            IntervalMethod::StudentT => proto::IntervalMethod::StudentT,
+        };
+
+        proto::MonteCarloResponse {
+            call_price: response.call_price,
+            put_price: response.put_price,
+            standard_error: response.standard_error,
+            confidence_interval_95: Some(interval(response.confidence_interval_95)),
+            num_simulations: response.num_simulations as u64,
+            computation_time_ms: response.computation_time_ms as u64,
+            confidence_level: response.confidence_level,
+            interval_method: interval_method.into(),
+            instruments: response.instruments.into_iter().map(proto::InstrumentStatistics::from).collect(),
+            put_call_parity: response.put_call_parity.map(proto::ParityDiagnostics::from),
+            execution: response.execution.map(proto::ExecutionStats::from),
+        }
+    }
+}
+
+impl From<ConvergencePoint> for proto::ConvergencePoint {
+    fn from(point: ConvergencePoint) -> Self {
+        proto::ConvergencePoint {
+            num_simulations: point.num_simulations as u64,
+            call_price: point.call_price,
+            put_price: point.put_price,
+            standard_error: point.standard_error,
+            put_standard_error: point.put_standard_error,
+            call_error: point.call_error,
+            put_error: point.put_error,
+            time_ms: point.time_ms as u64,
+        }
+    }
+}
+
+impl From<ConvergenceRate> for proto::ConvergenceRate {
+    fn from(rate: ConvergenceRate) -> Self {
+        proto::ConvergenceRate {
+            slope: rate.slope,
+            intercept: rate.intercept,
+            r_squared: rate.r_squared,
+            num_points: rate.num_points as u64,
+        }
+    }
+}
+
+impl From<ConvergenceResponse> for proto::ConvergenceResponse {
+    fn from(response: ConvergenceResponse) -> Self {
+        proto::ConvergenceResponse {
+            convergence_data: response.convergence_data.into_iter().map(proto::ConvergencePoint::from).collect(),
+            black_scholes_reference: response.black_scholes_reference,
+            black_scholes_put_reference: response.black_scholes_put_reference,
+            final_difference: response.final_difference,
+            final_put_difference: response.final_put_difference,
+            call_convergence_rate: response.call_convergence_rate.map(proto::ConvergenceRate::from),
+            put_convergence_rate: response.put_convergence_rate.map(proto::ConvergenceRate::from),
+        }
+    }
+}
+
+impl From<ConvergenceEvent> for proto::ConvergenceUpdate {
+    fn from(event: ConvergenceEvent) -> Self {
+        let event: proto::convergence_update::Event = match event {
+            ConvergenceEvent::Point(point) => proto::convergence_update::Event::Point(point.into()),
+            ConvergenceEvent::Complete(response) => proto::convergence_update::Event::Complete(response.into()),
+        };
+
+        proto::ConvergenceUpdate { event: Some(event) }
+    }
+}
+
+impl From<BatchValuation> for proto::BatchValuation {
+    fn from(valuation: BatchValuation) -> Self {
+        proto::BatchValuation {
+            call_price: valuation.call_price,
+            put_price: valuation.put_price,
+            call_delta: valuation.call_delta,
+            put_delta: valuation.put_delta,
+            gamma: valuation.gamma,
+            vega: valuation.vega,
+            call_theta: valuation.call_theta,
+            put_theta: valuation.put_theta,
+            call_rho: valuation.call_rho,
+            put_rho: valuation.put_rho,
+        }
+    }
+}
+
+impl From<BatchChunk> for proto::BatchChunk {
+    fn from(chunk: BatchChunk) -> Self {
+        proto::BatchChunk {
+            offset: chunk.offset as u64,
+            total: chunk.total as u64,
+            valuation: Some(chunk.valuation.into()),
+        }
+    }
+}
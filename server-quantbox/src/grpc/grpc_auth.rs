@@ -0,0 +1,96 @@
+use std::future::Future;
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use axum::http::{Request, Response};
+use tonic::Status;
+use tonic::server::NamedService;
+use tonic::transport::server::TcpConnectInfo;
+use tower::{Layer, Service};
+use crate::api::api_error::ApiError;
+use crate::api::authentication::authenticate_headers;
+use crate::api::rate_limiter::{FORWARDED_FOR_HEADER, RateLimiter};
+use crate::auth::authenticator::{Authenticator, Tenant};
+use crate::auth::usage_meter::{self, UsageMeter};
+
+// the gRPC side of api::authentication: the same credentials, sent as metadata, and the
+// same per-tenant charging. a tower layer rather than an interceptor, since the call has
+// to run inside the tenant's usage meter. calls are rate limited here too, once the tenant
+// is known: RESOURCE_EXHAUSTED with retry-after metadata once its bucket (shared with its
+// REST requests) is empty. calls without a tenant, including ones that failed to
+// authenticate, are limited by IP
+#[derive(Clone)]
+pub struct AuthenticateLayer {
+    pub authenticator: Arc<Authenticator>,
+    pub rate_limiter: Arc<RateLimiter>,
+}
+
+impl<S> Layer<S> for AuthenticateLayer {
+    type Service = Authenticate<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Authenticate {
+            inner,
+            authenticator: Arc::clone(&self.authenticator),
+            rate_limiter: Arc::clone(&self.rate_limiter),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Authenticate<S> {
+    inner: S,
+    authenticator: Arc<Authenticator>,
+    rate_limiter: Arc<RateLimiter>,
+}
+
+impl<S: NamedService> NamedService for Authenticate<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for Authenticate<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+    ResBody: Default + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        // the key lookup is awaited, so the call takes the service that was polled ready
+        // and leaves a fresh clone in its place
+        let clone: S = self.inner.clone();
+        let mut inner: S = std::mem::replace(&mut self.inner, clone);
+        let authenticator: Arc<Authenticator> = Arc::clone(&self.authenticator);
+        let rate_limiter: Arc<RateLimiter> = Arc::clone(&self.rate_limiter);
+
+        Box::pin(async move {
+            let authenticated: Result<Option<Tenant>, ApiError> = authenticate_headers(&authenticator, request.headers()).await;
+            let forwarded_for: Option<&str> = request.headers().get(FORWARDED_FOR_HEADER).and_then(|value| value.to_str().ok());
+            let peer: Option<IpAddr> = request.extensions().get::<TcpConnectInfo>()
+                .and_then(TcpConnectInfo::remote_addr)
+                .map(|addr| addr.ip());
+            let admitted: Result<(), ApiError> = rate_limiter.admit(authenticated.as_ref().ok().and_then(Option::as_ref), forwarded_for, peer);
+
+            let meter: Option<UsageMeter> = match admitted.and(authenticated) {
+                Ok(Some(tenant)) => {
+                    let meter: UsageMeter = UsageMeter::new(Arc::clone(&authenticator), tenant.clone());
+                    request.extensions_mut().insert::<Tenant>(tenant);
+                    Some(meter)
+                }
+                Ok(None) => None,
+                Err(err) => return Ok(Status::from(err).into_http()),
+            };
+
+            usage_meter::metered(meter, inner.call(request)).await
+        })
+    }
+}
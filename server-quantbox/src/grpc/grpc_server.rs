@@ -0,0 +1,51 @@
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use axum::http::HeaderName;
+use tonic::transport::Server;
+use tower::{Layer, ServiceBuilder};
+use tower_http::LatencyUnit;
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::trace::{DefaultOnResponse, TraceLayer};
+use tracing::Level;
+use crate::api::rate_limiter::RateLimiter;
+use crate::auth::authenticator::Authenticator;
+use crate::config::server_config::ServerConfig;
+use crate::grpc::grpc_auth::AuthenticateLayer;
+use crate::grpc::pricing_grpc_service::PricingGrpcService;
+use crate::grpc::proto::pricing_server::PricingServer;
+use crate::metrics::RecordRequestLayer;
+use crate::service::pricing_service::PricingService;
+use crate::telemetry::{MakeRequestSpan, REQUEST_ID_HEADER};
+
+// serves the Pricing service on its own listener, next to (not inside) the axum router
+pub async fn serve_grpc(
+    listener: TcpListener,
+    pricing: PricingService,
+    rate_limiter: Arc<RateLimiter>,
+    authenticator: Arc<Authenticator>,
+    config: &ServerConfig,
+) -> Result<(), tonic::transport::Error> {
+    let pricing_server: PricingServer<PricingGrpcService> = PricingServer::new(PricingGrpcService::new(pricing, config.simulation))
+        .max_decoding_message_size(config.grpc.max_message_bytes);
+    // authenticated first, then rate limited by tenant, as over REST
+    let service = AuthenticateLayer { authenticator, rate_limiter }.layer(pricing_server);
+
+    // same request span and x-request-id handling as the REST router; the route is the
+    // gRPC method path, e.g. /quantbox.v1.Pricing/GetGreeks
+    let request_id: HeaderName = HeaderName::from_static(REQUEST_ID_HEADER);
+    let trace_layers = ServiceBuilder::new()
+        .layer(SetRequestIdLayer::new(request_id.clone(), MakeRequestUuid))
+        .layer(TraceLayer::new_for_grpc()
+            .make_span_with(MakeRequestSpan { transport: "grpc" })
+            .on_response(DefaultOnResponse::new().level(Level::INFO).latency_unit(LatencyUnit::Millis)))
+        .layer(PropagateRequestIdLayer::new(request_id));
+
+    Server::builder()
+        .timeout(config.request_timeout())
+        .layer(trace_layers)
+        .layer(RecordRequestLayer { transport: "grpc" })
+        .add_service(service)
+        .serve_with_incoming(TcpListenerStream::new(listener))
+        .await
+}
@@ -0,0 +1,9 @@
+pub mod grpc_auth;
+pub mod grpc_server;
+mod conversions;
+mod pricing_grpc_service;
+
+// generated from proto/quantbox.proto by build.rs
+pub mod proto {
+    tonic::include_proto!("quantbox.v1");
+}
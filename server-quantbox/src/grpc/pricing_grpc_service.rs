@@ -0,0 +1,120 @@
+use std::pin::Pin;
+use tokio_stream::{Stream, StreamExt, wrappers::ReceiverStream};
+use tonic::{Request, Response, Status};
+use crate::api::api_error::ApiError;
+use crate::config::simulation_config::{SimulationConfig, WithinLimits};
+use crate::grpc::proto;
+use crate::grpc::proto::pricing_server::Pricing;
+use crate::models::black_scholes_models::{BatchPricingRequest, BlackScholesRequest, GreekRequest};
+use crate::models::monte_carlo_models::{ConvergenceRequest, MonteCarloRequest};
+use crate::models::validation::{Validate, ValidationError};
+use crate::service::pricing_service::{DEFAULT_BATCH_CHUNK_LEN, PricingService};
+
+type UpdateStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+// the gRPC transport: decodes and validates each message, then hands it to the same
+// PricingService the REST handlers use
+pub struct PricingGrpcService {
+    pricing: PricingService,
+    simulation: SimulationConfig,
+}
+
+impl PricingGrpcService {
+    pub fn new(pricing: PricingService, simulation: SimulationConfig) -> Self {
+        PricingGrpcService { pricing, simulation }
+    }
+
+    // the same Validate rules and configured caps the JSON extractors apply
+    fn validated<T: Validate + WithinLimits>(&self, req: T) -> Result<T, Status> {
+        req.validate().map_err(ApiError::from)?;
+        req.check_limits(&self.simulation).map_err(ApiError::from)?;
+        Ok(req)
+    }
+
+    // for messages whose enums or required sub-messages can fail to convert
+    fn decode<P, T>(&self, message: P) -> Result<T, Status>
+    where
+        T: TryFrom<P, Error = ValidationError> + Validate + WithinLimits,
+    {
+        self.validated(T::try_from(message).map_err(ApiError::from)?)
+    }
+
+    // unset simulation counts get the configured defaults, as in the JSON bodies
+    fn monte_carlo_defaults(&self, mut message: proto::MonteCarloRequest) -> proto::MonteCarloRequest {
+        message.num_simulations.get_or_insert(self.simulation.default_num_simulations as u64);
+        message
+    }
+
+    fn convergence_defaults(&self, mut message: proto::ConvergenceRequest) -> proto::ConvergenceRequest {
+        message.max_simulations.get_or_insert(self.simulation.default_convergence_max_simulations as u64);
+        message.step_size.get_or_insert(self.simulation.default_convergence_step_size as u64);
+        message
+    }
+}
+
+#[tonic::async_trait]
+impl Pricing for PricingGrpcService {
+    async fn get_options_prices(&self, request: Request<proto::BlackScholesRequest>) -> Result<Response<proto::OptionsPrices>, Status> {
+        let req: BlackScholesRequest = self.validated(request.into_inner().into())?;
+
+        Ok(Response::new(self.pricing.options_prices(&req).into()))
+    }
+
+    async fn get_greeks(&self, request: Request<proto::GreekRequest>) -> Result<Response<proto::Greeks>, Status> {
+        let req: GreekRequest = self.decode(request.into_inner())?;
+
+        Ok(Response::new(self.pricing.greeks(&req).into()))
+    }
+
+    async fn get_heatmap(&self, request: Request<proto::BlackScholesRequest>) -> Result<Response<proto::HeatmapData>, Status> {
+        let req: BlackScholesRequest = self.validated(request.into_inner().into())?;
+
+        Ok(Response::new(self.pricing.heatmap(&req).into()))
+    }
+
+    async fn price_monte_carlo(&self, request: Request<proto::MonteCarloRequest>) -> Result<Response<proto::MonteCarloResponse>, Status> {
+        let req: MonteCarloRequest = self.decode(self.monte_carlo_defaults(request.into_inner()))?;
+
+        Ok(Response::new(self.pricing.monte_carlo(req).await?.into()))
+    }
+
+    async fn price_monte_carlo_parallel(&self, request: Request<proto::MonteCarloRequest>) -> Result<Response<proto::MonteCarloResponse>, Status> {
+        let req: MonteCarloRequest = self.decode(self.monte_carlo_defaults(request.into_inner()))?;
+
+        Ok(Response::new(self.pricing.monte_carlo_parallel(req).await?.into()))
+    }
+
+    async fn get_convergence(&self, request: Request<proto::ConvergenceRequest>) -> Result<Response<proto::ConvergenceResponse>, Status> {
+        let req: ConvergenceRequest = self.decode(self.convergence_defaults(request.into_inner()))?;
+
+        Ok(Response::new(self.pricing.convergence(req).await?.into()))
+    }
+
+    type StreamConvergenceStream = UpdateStream<proto::ConvergenceUpdate>;
+
+    async fn stream_convergence(&self, request: Request<proto::ConvergenceRequest>) -> Result<Response<Self::StreamConvergenceStream>, Status> {
+        let req: ConvergenceRequest = self.decode(self.convergence_defaults(request.into_inner()))?;
+
+        let rx = self.pricing.stream_convergence(req).await?;
+        let updates = ReceiverStream::new(rx).map(|item| item.map(proto::ConvergenceUpdate::from).map_err(Status::from));
+
+        Ok(Response::new(Box::pin(updates)))
+    }
+
+    type StreamBatchPricesStream = UpdateStream<proto::BatchChunk>;
+
+    async fn stream_batch_prices(&self, request: Request<proto::BatchPricingRequest>) -> Result<Response<Self::StreamBatchPricesStream>, Status> {
+        let message: proto::BatchPricingRequest = request.into_inner();
+        let chunk_len: usize = message.chunk_len.map_or(DEFAULT_BATCH_CHUNK_LEN, |n| n as usize);
+        if chunk_len == 0 {
+            return Err(ApiError::from(ValidationError::new("not_positive", "chunk_len", "chunk_len must be greater than 0")).into());
+        }
+
+        let req: BatchPricingRequest = self.validated(message.into())?;
+
+        let rx = self.pricing.stream_batch(req.into_columns(), chunk_len).await?;
+        let chunks = ReceiverStream::new(rx).map(|item| item.map(proto::BatchChunk::from).map_err(Status::from));
+
+        Ok(Response::new(Box::pin(chunks)))
+    }
+}
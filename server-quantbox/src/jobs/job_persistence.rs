@@ -0,0 +1,62 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use crate::models::job_models::JobSnapshot;
+
+// somewhere for job snapshots to outlive the process
+pub trait JobPersistence: Send + Sync {
+    fn save(&self, snapshot: &JobSnapshot) -> io::Result<()>;
+    fn remove(&self, id: &str) -> io::Result<()>;
+    fn load_all(&self) -> io::Result<Vec<JobSnapshot>>;
+}
+
+// one <id>.json file per job in a directory
+pub struct DiskJobPersistence {
+    dir: PathBuf,
+}
+
+impl DiskJobPersistence {
+    pub fn new(dir: PathBuf) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(DiskJobPersistence { dir })
+    }
+
+    fn path_for(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+}
+
+impl JobPersistence for DiskJobPersistence {
+    fn save(&self, snapshot: &JobSnapshot) -> io::Result<()> {
+        // write then rename, so a crash mid-write never leaves a truncated file behind
+        let tmp_path: PathBuf = self.dir.join(format!("{}.json.tmp", snapshot.id));
+        let bytes: Vec<u8> = serde_json::to_vec(snapshot)?;
+        fs::write(&tmp_path, bytes)?;
+        fs::rename(&tmp_path, self.path_for(&snapshot.id))
+    }
+
+    fn remove(&self, id: &str) -> io::Result<()> {
+        match fs::remove_file(self.path_for(id)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    fn load_all(&self) -> io::Result<Vec<JobSnapshot>> {
+        let mut snapshots: Vec<JobSnapshot> = Vec::new();
+
+        for entry in fs::read_dir(&self.dir)? {
+            let path: PathBuf = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+
+            match fs::read(&path).map(|bytes| serde_json::from_slice::<JobSnapshot>(&bytes)) {
+                Ok(Ok(snapshot)) => snapshots.push(snapshot),
+                _ => tracing::warn!(path = %path.display(), "skipping unreadable job file"),
+            }
+        }
+
+        Ok(snapshots)
+    }
+}
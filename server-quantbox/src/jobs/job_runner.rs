@@ -0,0 +1,236 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::{Instrument, Span};
+use tracing::field::Empty;
+use crate::auth::usage_meter::PathCharge;
+use crate::compute::black_scholes::{calculate_greeks, calculate_options_prices, generate_heatmap_data};
+use crate::compute::compute_pool::{ComputeError, ComputePool, PendingJob};
+use crate::compute::convergence::run_convergence_analysis;
+use crate::compute::monte_carlo_engine::MonteCarloEngine;
+use crate::compute::parallel_monte_carlo_engine::ParallelMonteCarloEngine;
+use crate::jobs::job_store::JobStore;
+use crate::models::job_models::{JobRequest, JobStatus};
+use crate::models::monte_carlo_models::{ComparisonResponse, ComparisonResult, ConvergenceResponse,
+                                        MonteCarloResponse, MonteCarloResult, StopReason,
+                                        TargetPrecisionResponse, TargetPrecisionResult, TargetType};
+use crate::service::pricing_service::convergence_paths;
+use crate::telemetry::{record_run, simulation_count};
+
+struct JobOutcome {
+    status: JobStatus,
+    result: Value,
+    // what ran and how many paths it got through, for the job span and the engine metrics
+    engine: &'static str,
+    simulated: Option<usize>,
+}
+
+fn to_json<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+// queues the job on the compute pool and records the outcome in the store. `pending` is
+// the job's admission from ComputePool::admit_job, given back once it's done, and `charge`
+// its paths, reserved against the submitting tenant's quota
+pub fn spawn_job(
+    compute_pool: Arc<ComputePool>,
+    pending: PendingJob,
+    mut charge: PathCharge,
+    store: Arc<JobStore>,
+    id: String,
+    request: JobRequest,
+    cancel: Arc<AtomicBool>,
+) {
+    // created while the submitting request's span is current, so the job is traced as its child
+    let span: Span = tracing::info_span!(
+        "job",
+        job_id = %id,
+        kind = request.kind(),
+        simulations = request.simulations().map(simulation_count),
+        engine = Empty,
+        compute_ms = Empty,
+    );
+    tokio::spawn(async move {
+        let job_store: Arc<JobStore> = Arc::clone(&store);
+        let job_id: String = id.clone();
+
+        let outcome: Result<Option<(JobOutcome, Duration)>, ComputeError> = compute_pool.run_queued(pending, move || {
+            // cancelled while it sat in the queue
+            if !job_store.mark_running(&job_id) {
+                return None;
+            }
+            let start_time: std::time::Instant = std::time::Instant::now();
+            let outcome: JobOutcome = execute(&job_store, &job_id, request, &cancel, &mut charge);
+            Some((outcome, start_time.elapsed()))
+        }).await;
+
+        match outcome {
+            Ok(Some((outcome, computation_time))) => {
+                record_run(&Span::current(), outcome.engine, outcome.simulated, computation_time);
+                tracing::info!(status = ?outcome.status, "job finished");
+                store.finish(&id, outcome.status, Some(outcome.result), None);
+            }
+            Ok(None) => tracing::info!("job cancelled while queued"),
+            Err(_) => {
+                tracing::error!("job failed");
+                store.finish(&id, JobStatus::Failed, None, Some("pricing computation failed".to_string()));
+            }
+        }
+    }.instrument(span));
+}
+
+// records the paths simulated on `charge` as it goes, so a job that panics part way is
+// still charged for them
+fn execute(store: &JobStore, id: &str, request: JobRequest, cancel: &AtomicBool, charge: &mut PathCharge) -> JobOutcome {
+    let start_time: std::time::Instant = std::time::Instant::now();
+
+    match request {
+        JobRequest::BlackScholes(req) => completed("black_scholes", None, to_json(&calculate_options_prices(
+            req.spot_price,
+            req.strike_price,
+            req.risk_free_rate,
+            req.volatility,
+            req.time_to_maturity,
+        ))),
+        JobRequest::Greeks(req) => completed("black_scholes", None, to_json(&calculate_greeks(
+            req.scholes.spot_price,
+            req.scholes.strike_price,
+            req.scholes.risk_free_rate,
+            req.scholes.volatility,
+            req.scholes.time_to_maturity,
+            req.option_type,
+        ))),
+        JobRequest::Heatmap(req) => completed("black_scholes", None, to_json(&generate_heatmap_data(
+            req.spot_price,
+            req.strike_price,
+            req.risk_free_rate,
+            req.volatility,
+            req.time_to_maturity,
+        ))),
+        // the sequential engine reports no estimates along the way, only where it's got to
+        JobRequest::MonteCarlo(req) => {
+            let mut stopped_at: Option<usize> = None;
+            let result: MonteCarloResult = MonteCarloEngine::price_european_option_with_progress(&req, |done| {
+                charge.record(done);
+                store.update_progress(id, done as f64 / req.num_simulations as f64, None);
+                if cancel.load(Ordering::SeqCst) {
+                    stopped_at = Some(done);
+                }
+                stopped_at.is_none()
+            });
+            let simulated: usize = stopped_at.unwrap_or(req.num_simulations);
+            charge.record(simulated);
+
+            JobOutcome {
+                status: finished_status(simulated, req.num_simulations),
+                result: to_json(&MonteCarloResponse::from_result(&result, simulated, start_time.elapsed())),
+                engine: "sequential",
+                simulated: Some(simulated),
+            }
+        }
+        // checkpoints as for MonteCarlo; the Black-Scholes side is instant
+        JobRequest::Comparison(req) => {
+            let mut stopped_at: Option<usize> = None;
+            let comparison: ComparisonResult = MonteCarloEngine::compare_with_black_scholes_with_progress(&req, |done| {
+                charge.record(done);
+                store.update_progress(id, done as f64 / req.num_simulations as f64, None);
+                if cancel.load(Ordering::SeqCst) {
+                    stopped_at = Some(done);
+                }
+                stopped_at.is_none()
+            });
+            let simulated: usize = stopped_at.unwrap_or(req.num_simulations);
+            charge.record(simulated);
+
+            JobOutcome {
+                status: finished_status(simulated, req.num_simulations),
+                result: to_json(&ComparisonResponse::from_comparison(comparison, simulated, start_time.elapsed())),
+                engine: "sequential",
+                simulated: Some(simulated),
+            }
+        }
+        JobRequest::MonteCarloParallel(req) => {
+            let mut simulated: usize = 0;
+            let result: MonteCarloResult = ParallelMonteCarloEngine::price_european_option_with_progress(&req, |estimate, done| {
+                simulated = done;
+                charge.record(done);
+                let partial: MonteCarloResponse = MonteCarloResponse::from_result(estimate, done, start_time.elapsed());
+                store.update_progress(id, done as f64 / req.num_simulations as f64, Some(to_json(&partial)));
+                !cancel.load(Ordering::SeqCst)
+            });
+
+            JobOutcome {
+                status: finished_status(simulated, req.num_simulations),
+                result: to_json(&MonteCarloResponse::from_result(&result, simulated, start_time.elapsed())),
+                engine: "parallel",
+                simulated: Some(simulated),
+            }
+        }
+        JobRequest::TargetPrecision(req) => {
+            let outcome: TargetPrecisionResult = ParallelMonteCarloEngine::price_to_target(&req, |estimate, done| {
+                charge.record(done);
+                let partial: MonteCarloResponse = MonteCarloResponse::from_result(estimate, done, start_time.elapsed());
+                let progress: f64 = precision_progress(estimate, req.target_standard_error, req.target_type);
+                store.update_progress(id, progress, Some(to_json(&partial)));
+                !cancel.load(Ordering::SeqCst)
+            });
+
+            let response: TargetPrecisionResponse = TargetPrecisionResponse::from_result(&req, &outcome);
+
+            JobOutcome {
+                status: if outcome.stop_reason == StopReason::Cancelled { JobStatus::Cancelled } else { JobStatus::Completed },
+                result: to_json(&response),
+                engine: "target_precision",
+                simulated: Some(response.monte_carlo.num_simulations),
+            }
+        }
+        JobRequest::Convergence(req) => {
+            let response: ConvergenceResponse = run_convergence_analysis(&req, |point, done, total| {
+                charge.record(point.num_simulations);
+                store.append_progress(id, done as f64 / total.max(1) as f64, "convergence_data", to_json(point));
+                !cancel.load(Ordering::SeqCst)
+            });
+
+            JobOutcome {
+                status: finished_status(response.convergence_data.len(), req.max_simulations / req.step_size),
+                result: to_json(&response),
+                engine: "convergence",
+                simulated: Some(convergence_paths(&response)),
+            }
+        }
+    }
+}
+
+// how far along a target-precision run is: SE shrinks like 1/√N, so (target / error)² is
+// roughly the fraction of the paths it will end up needing
+fn precision_progress(result: &MonteCarloResult, target: f64, target_type: TargetType) -> f64 {
+    let error: f64 = result.instruments.iter()
+        .take(2)
+        .map(|stats| match target_type {
+            TargetType::Absolute => stats.standard_error,
+            TargetType::Relative => stats.relative_error.unwrap_or(f64::INFINITY),
+        })
+        .fold(0.0, f64::max);
+
+    if error <= 0.0 { 1.0 } else { (target / error).powi(2).min(1.0) }
+}
+
+fn completed(engine: &'static str, simulated: Option<usize>, result: Value) -> JobOutcome {
+    JobOutcome {
+        status: JobStatus::Completed,
+        result,
+        engine,
+        simulated,
+    }
+}
+
+// a job that stopped early on the cancel flag keeps whatever it computed, marked cancelled
+fn finished_status(done: usize, total: usize) -> JobStatus {
+    if done < total {
+        JobStatus::Cancelled
+    } else {
+        JobStatus::Completed
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde_json::Value;
+use uuid::Uuid;
+use crate::jobs::job_persistence::{DiskJobPersistence, JobPersistence};
+use crate::models::job_models::{JobSnapshot, JobStatus};
+
+#[derive(Debug, Clone)]
+pub struct JobStoreConfig {
+    // how long a finished job stays retrievable
+    pub ttl: Duration,
+    // when set, job snapshots are also written here and reloaded on startup
+    pub persistence_dir: Option<PathBuf>,
+}
+
+struct JobEntry {
+    snapshot: JobSnapshot,
+    cancel: Arc<AtomicBool>,
+}
+
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// in-process registry of submitted jobs. every status change is mirrored to the
+// persistence backend when one is configured; progress updates stay in memory
+pub struct JobStore {
+    jobs: Mutex<HashMap<String, JobEntry>>,
+    ttl_ms: u64,
+    persistence: Option<Box<dyn JobPersistence>>,
+}
+
+impl JobStore {
+    pub fn new(config: JobStoreConfig) -> std::io::Result<Self> {
+        let persistence: Option<Box<dyn JobPersistence>> = match config.persistence_dir {
+            Some(dir) => Some(Box::new(DiskJobPersistence::new(dir)?)),
+            None => None,
+        };
+
+        let store: JobStore = JobStore {
+            jobs: Mutex::new(HashMap::new()),
+            ttl_ms: config.ttl.as_millis() as u64,
+            persistence,
+        };
+        store.restore()?;
+
+        Ok(store)
+    }
+
+    // reload persisted jobs. anything that was still queued or running when the
+    // previous process died can't be resumed, so it comes back as failed
+    fn restore(&self) -> std::io::Result<()> {
+        let Some(persistence) = &self.persistence else {
+            return Ok(());
+        };
+
+        let now: u64 = now_ms();
+        for mut snapshot in persistence.load_all()? {
+            if !snapshot.status.is_finished() {
+                snapshot.status = JobStatus::Failed;
+                snapshot.error = Some("interrupted by server restart".to_string());
+                snapshot.finished_at_ms = Some(now);
+                snapshot.expires_at_ms = Some(now + self.ttl_ms);
+                self.persist(&snapshot);
+            }
+
+            self.lock().insert(snapshot.id.clone(), JobEntry {
+                snapshot,
+                cancel: Arc::new(AtomicBool::new(false)),
+            });
+        }
+
+        self.purge_expired();
+        Ok(())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, JobEntry>> {
+        // a panic while holding the lock can't leave a half-written entry, so recover
+        self.jobs.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn persist(&self, snapshot: &JobSnapshot) {
+        if let Some(persistence) = &self.persistence
+            && let Err(err) = persistence.save(snapshot) {
+            tracing::warn!(job_id = %snapshot.id, error = %err, "failed to persist job");
+        }
+    }
+
+    // applies `update` to a job and returns the new snapshot
+    fn update<F>(&self, id: &str, update: F) -> Option<JobSnapshot>
+    where
+        F: FnOnce(&mut JobEntry),
+    {
+        let mut jobs = self.lock();
+        let entry: &mut JobEntry = jobs.get_mut(id)?;
+        update(entry);
+        Some(entry.snapshot.clone())
+    }
+
+    // update without the snapshot copy, for progress reports that nothing reads back
+    fn update_in_place<F>(&self, id: &str, update: F)
+    where
+        F: FnOnce(&mut JobEntry),
+    {
+        if let Some(entry) = self.lock().get_mut(id) {
+            update(entry);
+        }
+    }
+
+    pub fn create(&self, kind: &str, tenant: Option<&str>) -> (JobSnapshot, Arc<AtomicBool>) {
+        let snapshot: JobSnapshot = JobSnapshot {
+            id: Uuid::new_v4().to_string(),
+            kind: kind.to_string(),
+            status: JobStatus::Queued,
+            progress: 0.0,
+            cancel_requested: false,
+            partial_result: None,
+            result: None,
+            error: None,
+            created_at_ms: now_ms(),
+            started_at_ms: None,
+            finished_at_ms: None,
+            expires_at_ms: None,
+            tenant: tenant.map(str::to_string),
+        };
+        let cancel: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+
+        self.lock().insert(snapshot.id.clone(), JobEntry {
+            snapshot: snapshot.clone(),
+            cancel: Arc::clone(&cancel),
+        });
+        self.persist(&snapshot);
+
+        (snapshot, cancel)
+    }
+
+    // a job another tenant submitted looks just like an unknown one
+    pub fn get(&self, id: &str, tenant: Option<&str>) -> Option<JobSnapshot> {
+        let jobs = self.lock();
+        let entry: &JobEntry = jobs.get(id).filter(|entry| entry.snapshot.tenant.as_deref() == tenant)?;
+        match entry.snapshot.expires_at_ms {
+            Some(expires_at) if expires_at <= now_ms() => None,
+            _ => Some(entry.snapshot.clone()),
+        }
+    }
+
+    // flips a queued job to running. returns false if it was cancelled while queued
+    pub fn mark_running(&self, id: &str) -> bool {
+        let snapshot: Option<JobSnapshot> = self.update(id, |entry| {
+            if entry.snapshot.status == JobStatus::Queued {
+                entry.snapshot.status = JobStatus::Running;
+                entry.snapshot.started_at_ms = Some(now_ms());
+            }
+        });
+
+        match snapshot {
+            Some(snapshot) if snapshot.status == JobStatus::Running => {
+                self.persist(&snapshot);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn update_progress(&self, id: &str, progress: f64, partial_result: Option<Value>) {
+        self.update_in_place(id, |entry| {
+            entry.snapshot.progress = progress.clamp(0.0, 1.0);
+            if partial_result.is_some() {
+                entry.snapshot.partial_result = partial_result;
+            }
+        });
+    }
+
+    // like update_progress, but adds `item` to the `field` array of the partial result
+    // rather than replacing it, so a job building up a series only serializes each entry once
+    pub fn append_progress(&self, id: &str, progress: f64, field: &str, item: Value) {
+        self.update_in_place(id, |entry| {
+            entry.snapshot.progress = progress.clamp(0.0, 1.0);
+            let partial: &mut Value = entry.snapshot.partial_result.get_or_insert_with(|| serde_json::json!({}));
+            if let Some(series) = partial.as_object_mut()
+                .map(|partial| partial.entry(field).or_insert_with(|| Value::Array(Vec::new())))
+                .and_then(Value::as_array_mut) {
+                series.push(item);
+            }
+        });
+    }
+
+    pub fn finish(&self, id: &str, status: JobStatus, result: Option<Value>, error: Option<String>) {
+        let ttl_ms: u64 = self.ttl_ms;
+        let snapshot: Option<JobSnapshot> = self.update(id, |entry| {
+            let now: u64 = now_ms();
+            if status == JobStatus::Completed {
+                entry.snapshot.progress = 1.0;
+            }
+            entry.snapshot.status = status;
+            entry.snapshot.result = result;
+            entry.snapshot.error = error;
+            entry.snapshot.finished_at_ms = Some(now);
+            entry.snapshot.expires_at_ms = Some(now + ttl_ms);
+        });
+
+        if let Some(snapshot) = snapshot {
+            self.persist(&snapshot);
+        }
+    }
+
+    // cooperative cancel: a queued job is cancelled on the spot, a running one is
+    // flagged and stops at its next progress checkpoint. finished jobs are left alone
+    pub fn cancel(&self, id: &str, tenant: Option<&str>) -> Option<JobSnapshot> {
+        self.get(id, tenant)?;
+
+        let ttl_ms: u64 = self.ttl_ms;
+        let snapshot: JobSnapshot = self.update(id, |entry| {
+            if entry.snapshot.status.is_finished() {
+                return;
+            }
+
+            entry.cancel.store(true, Ordering::SeqCst);
+            entry.snapshot.cancel_requested = true;
+            if entry.snapshot.status == JobStatus::Queued {
+                let now: u64 = now_ms();
+                entry.snapshot.status = JobStatus::Cancelled;
+                entry.snapshot.finished_at_ms = Some(now);
+                entry.snapshot.expires_at_ms = Some(now + ttl_ms);
+            }
+        })?;
+
+        self.persist(&snapshot);
+        Some(snapshot)
+    }
+
+    // drops finished jobs whose TTL has passed, returning how many were removed
+    pub fn purge_expired(&self) -> usize {
+        let now: u64 = now_ms();
+        let expired: Vec<String> = {
+            let mut jobs = self.lock();
+            let expired: Vec<String> = jobs.iter()
+                .filter(|(_, entry)| entry.snapshot.expires_at_ms.is_some_and(|t| t <= now))
+                .map(|(id, _)| id.clone())
+                .collect();
+            for id in &expired {
+                jobs.remove(id);
+            }
+            expired
+        };
+
+        if let Some(persistence) = &self.persistence {
+            for id in &expired {
+                if let Err(err) = persistence.remove(id) {
+                    tracing::warn!(job_id = %id, error = %err, "failed to remove persisted job");
+                }
+            }
+        }
+
+        expired.len()
+    }
+}
+
+// sweeps expired jobs once a minute for as long as the server runs
+pub fn spawn_expiry_sweep(store: Arc<JobStore>) {
+    tokio::spawn(async move {
+        let mut interval: tokio::time::Interval = tokio::time::interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            let purged: usize = store.purge_expired();
+            if purged > 0 {
+                tracing::info!(purged, "purged expired jobs");
+            }
+        }
+    });
+}
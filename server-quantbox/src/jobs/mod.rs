@@ -0,0 +1,3 @@
+pub mod job_persistence;
+pub mod job_runner;
+pub mod job_store;
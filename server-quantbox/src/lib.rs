@@ -0,0 +1,11 @@
+pub mod api;
+pub mod auth;
+pub mod bulk;
+pub mod models;
+pub mod compute;
+pub mod config;
+pub mod grpc;
+pub mod jobs;
+pub mod metrics;
+pub mod service;
+pub mod telemetry;
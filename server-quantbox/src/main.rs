@@ -1,21 +1,9 @@
-mod api;
-mod models;
-mod compute;
+mod cli;
 
+use std::process::ExitCode;
 
-use axum::Router;
-use tokio::net::TcpListener;
-use api::routes::create_router;
-
-#[tokio::main]
-async fn main() {
-    let app: Router = create_router();
-
-    // run the server with hyper, listening globally on port 8080
-    let listener: TcpListener = TcpListener::bind("0.0.0.0:8080")
-        .await.unwrap();
-
-    println!("Listening on {}", listener.local_addr().unwrap());
-    axum::serve(listener, app).await.unwrap();
+// `quantbox serve` (or no command at all) runs the HTTP API; the other subcommands price
+// from the command line without it -- see `quantbox --help`
+fn main() -> ExitCode {
+    cli::run()
 }
-
@@ -0,0 +1,246 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::LazyLock;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use axum::extract::MatchedPath;
+use axum::http::{Method, Request, Response};
+use prometheus::core::Collector;
+use prometheus::proto::MetricFamily;
+use prometheus::{Counter, Gauge, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry, TextEncoder};
+use tower::{Layer, Service};
+use crate::models::compute_models::ComputeStats;
+
+// content type of the text exposition format served on /metrics
+pub const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+// seconds, from an analytic price up to a Monte Carlo run that uses most of the request timeout
+const LATENCY_BUCKETS: [f64; 16] = [0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 300.0];
+
+// the process-wide counters. everything here is cumulative and fed as things happen; the
+// compute pool's current load is read from its stats when /metrics is scraped
+struct Metrics {
+    registry: Registry,
+    requests: IntCounterVec,
+    request_duration: HistogramVec,
+    engine_duration: HistogramVec,
+    simulated_paths: IntCounterVec,
+    paths_per_second: HistogramVec,
+    compute_busy_seconds: Counter,
+    cancelled_jobs: IntCounter,
+    validation_failures: IntCounterVec,
+    panics: IntCounterVec,
+}
+
+static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+impl Metrics {
+    fn new() -> Self {
+        let registry: Registry = Registry::new();
+        let metrics: Metrics = Metrics {
+            requests: IntCounterVec::new(
+                Opts::new("quantbox_requests_total", "REST and gRPC requests answered, by route and status"),
+                &["transport", "method", "route", "status"],
+            ).unwrap(),
+            request_duration: HistogramVec::new(
+                HistogramOpts::new("quantbox_request_duration_seconds", "time until the response started, by route")
+                    .buckets(LATENCY_BUCKETS.to_vec()),
+                &["transport", "method", "route"],
+            ).unwrap(),
+            engine_duration: HistogramVec::new(
+                HistogramOpts::new("quantbox_engine_compute_seconds", "compute time of each pricing run, by engine")
+                    .buckets(LATENCY_BUCKETS.to_vec()),
+                &["engine"],
+            ).unwrap(),
+            simulated_paths: IntCounterVec::new(
+                Opts::new("quantbox_simulated_paths_total", "Monte Carlo paths simulated, by engine"),
+                &["engine"],
+            ).unwrap(),
+            // 10k to 1G paths/s in half-decade steps
+            paths_per_second: HistogramVec::new(
+                HistogramOpts::new("quantbox_engine_paths_per_second", "throughput of each Monte Carlo run, by engine")
+                    .buckets(prometheus::exponential_buckets(1e4, 10f64.sqrt(), 11).unwrap()),
+                &["engine"],
+            ).unwrap(),
+            compute_busy_seconds: Counter::new(
+                "quantbox_compute_busy_seconds_total",
+                "time compute slots spent running jobs; its rate over the slot count is the pool's utilisation",
+            ).unwrap(),
+            cancelled_jobs: IntCounter::new(
+                "quantbox_compute_cancelled_total",
+                "jobs stopped early because their request timed out or was abandoned",
+            ).unwrap(),
+            validation_failures: IntCounterVec::new(
+                Opts::new("quantbox_validation_failures_total", "requests rejected for bad input, by error code and field"),
+                &["code", "field"],
+            ).unwrap(),
+            panics: IntCounterVec::new(
+                Opts::new("quantbox_panics_caught_total", "panics caught and answered with an error, by where they were caught"),
+                &["source"],
+            ).unwrap(),
+            registry,
+        };
+
+        let collectors: [Box<dyn Collector>; 9] = [
+            Box::new(metrics.requests.clone()),
+            Box::new(metrics.request_duration.clone()),
+            Box::new(metrics.engine_duration.clone()),
+            Box::new(metrics.simulated_paths.clone()),
+            Box::new(metrics.paths_per_second.clone()),
+            Box::new(metrics.compute_busy_seconds.clone()),
+            Box::new(metrics.cancelled_jobs.clone()),
+            Box::new(metrics.validation_failures.clone()),
+            Box::new(metrics.panics.clone()),
+        ];
+        for collector in collectors {
+            metrics.registry.register(collector).expect("metric names are unique");
+        }
+        metrics
+    }
+}
+
+// simulations is None for the analytic engines, which only get a compute time
+pub fn record_engine_run(engine: &'static str, simulations: Option<usize>, compute_time: Duration) {
+    METRICS.engine_duration.with_label_values(&[engine]).observe(compute_time.as_secs_f64());
+    if let Some(simulations) = simulations {
+        METRICS.simulated_paths.with_label_values(&[engine]).inc_by(simulations as u64);
+        if simulations > 0 && !compute_time.is_zero() {
+            METRICS.paths_per_second.with_label_values(&[engine]).observe(simulations as f64 / compute_time.as_secs_f64());
+        }
+    }
+}
+
+// field is empty for rejections that aren't about one field, e.g. malformed JSON
+pub fn record_validation_failure(code: &str, field: &str) {
+    METRICS.validation_failures.with_label_values(&[code, field]).inc();
+}
+
+// a job that ran on the compute pool, and whether it panicked
+pub fn record_compute_job(busy_time: Duration, panicked: bool) {
+    METRICS.compute_busy_seconds.inc_by(busy_time.as_secs_f64());
+    if panicked {
+        record_panic("compute");
+    }
+}
+
+pub fn record_cancelled_job() {
+    METRICS.cancelled_jobs.inc();
+}
+
+pub fn record_panic(source: &'static str) {
+    METRICS.panics.with_label_values(&[source]).inc();
+}
+
+// everything in the text exposition format, with the pool's load as of now
+pub fn render(compute: &ComputeStats) -> String {
+    let mut families: Vec<MetricFamily> = METRICS.registry.gather();
+    families.extend(compute_pool_families(compute));
+    families.sort_by(|a, b| a.name().cmp(b.name()));
+
+    TextEncoder::new().encode_to_string(&families).unwrap_or_else(|e| format!("# failed to encode metrics: {}\n", e))
+}
+
+// point-in-time readings, so they're built fresh on each scrape rather than kept up to date
+fn compute_pool_families(compute: &ComputeStats) -> Vec<MetricFamily> {
+    let gauge = |name: &str, help: &str, value: f64| -> Box<dyn Collector> {
+        let gauge: Gauge = Gauge::new(name, help).unwrap();
+        gauge.set(value);
+        Box::new(gauge)
+    };
+    let int_gauge = |name: &str, help: &str, value: usize| -> Box<dyn Collector> {
+        let gauge: IntGauge = IntGauge::new(name, help).unwrap();
+        gauge.set(i64::try_from(value).unwrap_or(i64::MAX));
+        Box::new(gauge)
+    };
+    let rejected: IntCounter = IntCounter::new("quantbox_compute_rejected_total", "jobs turned away because the pool was saturated").unwrap();
+    rejected.inc_by(compute.rejected_total);
+
+    let collectors: [Box<dyn Collector>; 9] = [
+        int_gauge("quantbox_compute_threads", "threads in the rayon compute pool", compute.threads),
+        int_gauge("quantbox_compute_slots", "jobs the pool runs at once", compute.max_concurrent_jobs),
+        int_gauge("quantbox_compute_in_flight", "jobs running on the pool", compute.in_flight),
+        int_gauge("quantbox_compute_queued", "jobs waiting for a slot", compute.queued),
+        gauge(
+            "quantbox_compute_utilisation",
+            "fraction of compute slots in use",
+            compute.in_flight as f64 / compute.max_concurrent_jobs.max(1) as f64,
+        ),
+        int_gauge("quantbox_compute_max_queued", "jobs that may wait before new ones are rejected", compute.max_queued_jobs),
+        int_gauge("quantbox_compute_pending_jobs", "background jobs queued or running", compute.pending_jobs),
+        int_gauge("quantbox_compute_max_pending_jobs", "background jobs allowed before new ones are rejected", compute.max_pending_jobs),
+        Box::new(rejected),
+    ];
+    collectors.iter().flat_map(|collector| collector.collect()).collect()
+}
+
+// counts every response and times it to the first byte (streams are answered before they
+// finish). routes are the matched templates, so ids and stray paths don't add label values
+#[derive(Debug, Clone, Copy)]
+pub struct RecordRequestLayer {
+    pub transport: &'static str,
+}
+
+impl<S> Layer<S> for RecordRequestLayer {
+    type Service = RecordRequest<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RecordRequest { inner, transport: self.transport }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordRequest<S> {
+    inner: S,
+    transport: &'static str,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for RecordRequest<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let transport: &'static str = self.transport;
+        let method: Method = request.method().clone();
+        // gRPC routes are method paths, which the tonic router doesn't expose as a MatchedPath
+        let route: Option<String> = match request.extensions().get::<MatchedPath>() {
+            Some(path) => Some(path.as_str().to_string()),
+            None if transport == "grpc" => Some(request.uri().path().to_string()),
+            None => None,
+        };
+        let start_time: Instant = Instant::now();
+        let response = self.inner.call(request);
+
+        Box::pin(async move {
+            let response: Response<ResBody> = response.await?;
+            // gRPC answers 200 and puts its own code in grpc-status, which is only in the
+            // headers when the call failed before sending anything
+            let status: String = match transport {
+                "grpc" => response.headers().get("grpc-status")
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or("0")
+                    .to_string(),
+                _ => response.status().as_str().to_string(),
+            };
+            // 12 is UNIMPLEMENTED: an unknown gRPC method
+            let route: &str = match route.as_deref() {
+                Some(_) if transport == "grpc" && status == "12" => "unmatched",
+                Some(route) => route,
+                None => "unmatched",
+            };
+
+            METRICS.requests.with_label_values(&[transport, method.as_str(), route, &status]).inc();
+            METRICS.request_duration.with_label_values(&[transport, method.as_str(), route])
+                .observe(start_time.elapsed().as_secs_f64());
+            Ok(response)
+        })
+    }
+}
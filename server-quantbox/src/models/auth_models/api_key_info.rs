@@ -0,0 +1,12 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+// an API key as the admin API lists it. the key itself is only ever returned on creation,
+// and only its hash is stored. timestamps are unix milliseconds
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiKeyInfo {
+    pub id: String,
+    pub tenant: String,
+    pub created_at_ms: u64,
+    pub revoked_at_ms: Option<u64>,
+}
@@ -0,0 +1,39 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+use crate::models::validation::{Validate, ValidationError};
+
+const MAX_TENANT_LENGTH: usize = 64;
+
+// body of POST /api/v1/admin/keys. a tenant can hold any number of keys; giving a quota
+// sets it for the tenant (and so every one of its keys), leaving it out keeps the current one
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateApiKeyRequest {
+    pub tenant: String,
+    // simulated paths per UTC day
+    pub daily_path_quota: Option<u64>,
+}
+
+impl Validate for CreateApiKeyRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        // tenants end up in URLs, logs and token subjects, so keep them plain
+        let valid: bool = !self.tenant.is_empty()
+            && self.tenant.len() <= MAX_TENANT_LENGTH
+            && self.tenant.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err(ValidationError::new(
+                "invalid_value",
+                "tenant",
+                format!("tenant must be 1 to {} letters, digits, '-', '_' or '.'", MAX_TENANT_LENGTH),
+            ));
+        }
+        if self.daily_path_quota == Some(0) {
+            return Err(ValidationError::new(
+                "not_positive",
+                "daily_path_quota",
+                "daily_path_quota must be greater than 0",
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,13 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+// what POST /api/v1/admin/keys returns. `key` goes in the x-api-key header and can't be
+// retrieved again
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedApiKey {
+    pub id: String,
+    pub tenant: String,
+    pub key: String,
+    pub created_at_ms: u64,
+    pub daily_path_quota: u64,
+}
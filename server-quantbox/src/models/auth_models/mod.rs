@@ -0,0 +1,10 @@
+mod api_key_info;
+mod create_api_key_request;
+mod created_api_key;
+mod tenant_usage;
+
+
+pub use api_key_info::ApiKeyInfo;
+pub use create_api_key_request::CreateApiKeyRequest;
+pub use created_api_key::CreatedApiKey;
+pub use tenant_usage::TenantUsage;
@@ -0,0 +1,13 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+// a tenant's usage for one UTC day (YYYY-MM-DD)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TenantUsage {
+    pub tenant: String,
+    pub day: String,
+    pub requests: u64,
+    pub simulated_paths: u64,
+    pub daily_path_quota: u64,
+    pub remaining_paths: u64,
+}
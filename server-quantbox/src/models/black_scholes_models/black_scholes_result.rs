@@ -1,7 +0,0 @@
-use serde::Serialize;
-
-#[derive(Debug, Serialize)]
-pub struct BlackScholesResult {
-    pub call_price: f64,
-    pub put_price: f64,
-}
\ No newline at end of file
@@ -1,14 +0,0 @@
-mod greeks;
-mod option_type;
-mod heatmap;
-mod black_scholes_request;
-mod greek_request;
-mod black_scholes_result;
-
-
-pub use greeks::Greeks;
-pub use heatmap::HeatmapData;
-pub use option_type::OptionType;
-pub use black_scholes_request::BlackScholesRequest;
-pub use greek_request::GreekRequest;
-pub use black_scholes_result::BlackScholesResult;
\ No newline at end of file
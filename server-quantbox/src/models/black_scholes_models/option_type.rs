@@ -1,7 +0,0 @@
-use serde::{Serialize, Deserialize};
-
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-pub enum OptionType {
-    Call,
-    Put,
-}
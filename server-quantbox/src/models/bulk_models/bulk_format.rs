@@ -0,0 +1,54 @@
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+
+// table encodings the bulk pricer reads and writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkFormat {
+    Csv,
+    // Arrow IPC; both the stream and file variants are read, the stream variant is written
+    Arrow,
+    Parquet,
+}
+
+impl BulkFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            BulkFormat::Csv => "text/csv",
+            BulkFormat::Arrow => "application/vnd.apache.arrow.stream",
+            BulkFormat::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+
+    // ignores parameters such as "; charset=utf-8"
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        match content_type.split(';').next()?.trim() {
+            "text/csv" => Some(BulkFormat::Csv),
+            "application/vnd.apache.arrow.stream" | "application/vnd.apache.arrow.file" => Some(BulkFormat::Arrow),
+            "application/vnd.apache.parquet" | "application/x-parquet" => Some(BulkFormat::Parquet),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "csv" => Some(BulkFormat::Csv),
+            "arrow" | "arrows" | "ipc" | "feather" => Some(BulkFormat::Arrow),
+            "parquet" | "pq" => Some(BulkFormat::Parquet),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for BulkFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(BulkFormat::Csv),
+            "arrow" => Ok(BulkFormat::Arrow),
+            "parquet" => Ok(BulkFormat::Parquet),
+            other => Err(format!("unknown format {:?}, expected csv, arrow or parquet", other)),
+        }
+    }
+}
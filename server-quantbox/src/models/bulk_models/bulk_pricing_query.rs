@@ -0,0 +1,20 @@
+use serde::Deserialize;
+use crate::models::bulk_models::{BulkFormat, ColumnMapping};
+use crate::models::validation::{Validate, ValidationError};
+
+// query string for POST /api/black-scholes/bulk, e.g.
+//   ?output=parquet&spot_price=S&strike_price=K&time_to_maturity=T
+// input defaults to the Content-Type, output to the Accept header and then the input format
+#[derive(Debug, Deserialize)]
+pub struct BulkPricingQuery {
+    pub input: Option<BulkFormat>,
+    pub output: Option<BulkFormat>,
+    #[serde(flatten)]
+    pub mapping: ColumnMapping,
+}
+
+impl Validate for BulkPricingQuery {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.mapping.validate()
+    }
+}
@@ -0,0 +1,80 @@
+use serde::Deserialize;
+use crate::models::validation::{Validate, ValidationError};
+
+// which table column holds each pricing input. anything not mapped is looked up under its
+// own name, e.g. a "spot_price" column. market_price and option_type are optional: when a
+// market_price column is present an implied_volatility column is added, reading each row as
+// a call unless an option_type column ("call"/"put", "c"/"p") says otherwise
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ColumnMapping {
+    pub spot_price: Option<String>,
+    pub strike_price: Option<String>,
+    pub risk_free_rate: Option<String>,
+    pub volatility: Option<String>,
+    pub time_to_maturity: Option<String>,
+    pub market_price: Option<String>,
+    pub option_type: Option<String>,
+}
+
+impl ColumnMapping {
+    // mapped column name for a field, or the field's own name
+    pub fn column<'a>(&'a self, field: &'a str) -> &'a str {
+        let mapped: &Option<String> = match field {
+            "spot_price" => &self.spot_price,
+            "strike_price" => &self.strike_price,
+            "risk_free_rate" => &self.risk_free_rate,
+            "volatility" => &self.volatility,
+            "time_to_maturity" => &self.time_to_maturity,
+            "market_price" => &self.market_price,
+            "option_type" => &self.option_type,
+            _ => &None,
+        };
+        mapped.as_deref().unwrap_or(field)
+    }
+
+    pub fn is_mapped(&self, field: &str) -> bool {
+        self.column(field) != field
+    }
+
+    // sets one field from "field=column", as given on the command line
+    pub fn set(&mut self, assignment: &str) -> Result<(), String> {
+        let (field, column) = assignment.split_once('=')
+            .ok_or_else(|| format!("expected field=column, got {:?}", assignment))?;
+        let slot: &mut Option<String> = match field.trim() {
+            "spot_price" => &mut self.spot_price,
+            "strike_price" => &mut self.strike_price,
+            "risk_free_rate" => &mut self.risk_free_rate,
+            "volatility" => &mut self.volatility,
+            "time_to_maturity" => &mut self.time_to_maturity,
+            "market_price" => &mut self.market_price,
+            "option_type" => &mut self.option_type,
+            other => return Err(format!("unknown field {:?}", other)),
+        };
+        *slot = Some(column.trim().to_string());
+        Ok(())
+    }
+}
+
+impl Validate for ColumnMapping {
+    fn validate(&self) -> Result<(), ValidationError> {
+        let mappings: [(&'static str, &Option<String>); 7] = [
+            ("spot_price", &self.spot_price),
+            ("strike_price", &self.strike_price),
+            ("risk_free_rate", &self.risk_free_rate),
+            ("volatility", &self.volatility),
+            ("time_to_maturity", &self.time_to_maturity),
+            ("market_price", &self.market_price),
+            ("option_type", &self.option_type),
+        ];
+        for (field, column) in mappings {
+            if column.as_deref().is_some_and(|c| c.trim().is_empty()) {
+                return Err(ValidationError::new(
+                    "empty_column_name",
+                    field,
+                    format!("the column mapped to {} must have a name", field),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
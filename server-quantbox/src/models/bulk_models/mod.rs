@@ -0,0 +1,8 @@
+mod bulk_format;
+mod column_mapping;
+mod bulk_pricing_query;
+
+
+pub use bulk_format::BulkFormat;
+pub use column_mapping::ColumnMapping;
+pub use bulk_pricing_query::BulkPricingQuery;
@@ -0,0 +1,15 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ComputeStats {
+    pub threads: usize,
+    pub max_concurrent_jobs: usize,
+    pub max_queued_jobs: usize,
+    pub in_flight: usize,
+    pub queued: usize,
+    pub max_pending_jobs: usize,
+    // background jobs queued or running
+    pub pending_jobs: usize,
+    pub rejected_total: u64,
+}
@@ -0,0 +1,4 @@
+mod compute_stats;
+
+
+pub use compute_stats::ComputeStats;
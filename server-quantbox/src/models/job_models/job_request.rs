@@ -0,0 +1,57 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+use crate::models::black_scholes_models::{BlackScholesRequest, GreekRequest};
+use crate::models::monte_carlo_models::{ConvergenceRequest, MonteCarloRequest, TargetPrecisionRequest};
+use crate::models::validation::{Validate, ValidationError};
+
+// anything the synchronous endpoints can price, submitted as
+// {"kind": "...", "params": { <same body as the matching endpoint> }}
+#[derive(Deserialize, ToSchema)]
+#[serde(tag = "kind", content = "params", rename_all = "snake_case")]
+pub enum JobRequest {
+    BlackScholes(BlackScholesRequest),
+    Greeks(GreekRequest),
+    Heatmap(BlackScholesRequest),
+    MonteCarlo(MonteCarloRequest),
+    MonteCarloParallel(MonteCarloRequest),
+    Comparison(MonteCarloRequest),
+    Convergence(ConvergenceRequest),
+    TargetPrecision(TargetPrecisionRequest),
+}
+
+impl JobRequest {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JobRequest::BlackScholes(_) => "black_scholes",
+            JobRequest::Greeks(_) => "greeks",
+            JobRequest::Heatmap(_) => "heatmap",
+            JobRequest::MonteCarlo(_) => "monte_carlo",
+            JobRequest::MonteCarloParallel(_) => "monte_carlo_parallel",
+            JobRequest::Comparison(_) => "comparison",
+            JobRequest::Convergence(_) => "convergence",
+            JobRequest::TargetPrecision(_) => "target_precision",
+        }
+    }
+
+    // paths the job asks for (at most, for convergence and target precision); None for the analytic kinds
+    pub fn simulations(&self) -> Option<usize> {
+        match self {
+            JobRequest::BlackScholes(_) | JobRequest::Greeks(_) | JobRequest::Heatmap(_) => None,
+            JobRequest::MonteCarlo(req) | JobRequest::MonteCarloParallel(req) | JobRequest::Comparison(req) => Some(req.num_simulations),
+            JobRequest::Convergence(req) => Some(req.max_simulations),
+            JobRequest::TargetPrecision(req) => Some(req.max_simulations),
+        }
+    }
+}
+
+impl Validate for JobRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        match self {
+            JobRequest::BlackScholes(req) | JobRequest::Heatmap(req) => req.validate(),
+            JobRequest::Greeks(req) => req.validate(),
+            JobRequest::MonteCarlo(req) | JobRequest::MonteCarloParallel(req) | JobRequest::Comparison(req) => req.validate(),
+            JobRequest::Convergence(req) => req.validate(),
+            JobRequest::TargetPrecision(req) => req.validate(),
+        }
+    }
+}
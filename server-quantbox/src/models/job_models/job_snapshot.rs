@@ -0,0 +1,27 @@
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+use crate::models::job_models::JobStatus;
+
+// what GET /api/jobs/{id} returns, and what the disk backend writes per job.
+// timestamps are unix milliseconds
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct JobSnapshot {
+    pub id: String,
+    pub kind: String,
+    pub status: JobStatus,
+    // 0.0 -> 1.0
+    pub progress: f64,
+    pub cancel_requested: bool,
+    pub partial_result: Option<Value>,
+    pub result: Option<Value>,
+    pub error: Option<String>,
+    pub created_at_ms: u64,
+    pub started_at_ms: Option<u64>,
+    pub finished_at_ms: Option<u64>,
+    pub expires_at_ms: Option<u64>,
+    // the tenant that submitted the job; only that tenant can see or cancel it.
+    // absent when authentication is off
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+}
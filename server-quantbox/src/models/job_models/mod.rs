@@ -0,0 +1,8 @@
+mod job_request;
+mod job_status;
+mod job_snapshot;
+
+
+pub use job_request::JobRequest;
+pub use job_status::JobStatus;
+pub use job_snapshot::JobSnapshot;
@@ -1,2 +1,8 @@
-pub mod black_scholes_models;
-pub mod monte_carlo_models;
\ No newline at end of file
+pub use quantbox_core::models::{black_scholes_models, monte_carlo_models, validation};
+
+pub mod auth_models;
+pub mod bulk_models;
+pub mod compute_models;
+pub mod job_models;
+pub mod response_models;
+pub mod simulation_models;
@@ -1,9 +0,0 @@
-use serde::Serialize;
-
-#[derive(Debug, Serialize)]
-pub struct ConvergencePoint {
-    pub num_simulations: usize,
-    pub call_price: f64,
-    pub standard_error: f64,
-    pub time_ms: u128,
-}
\ No newline at end of file
@@ -1,20 +0,0 @@
-mod monte_carlo_request;
-mod monte_carlo_response;
-mod monte_carlo_result;
-mod comparison_result;
-mod comparison_response;
-mod price_differences;
-mod convergence_request;
-mod convergence_response;
-mod convergence_point;
-
-
-pub use monte_carlo_request::MonteCarloRequest;
-pub use monte_carlo_response::MonteCarloResponse;
-pub use monte_carlo_result::MonteCarloResult;
-pub use comparison_result::ComparisonResult;
-pub use price_differences::PriceDifferences;
-pub use comparison_response::ComparisonResponse;
-pub use convergence_request::ConvergenceRequest;
-pub use convergence_response::ConvergenceResponse;
-pub use convergence_point::ConvergencePoint;
\ No newline at end of file
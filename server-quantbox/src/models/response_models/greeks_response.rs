@@ -0,0 +1,23 @@
+use rust_decimal::Decimal;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+// Greeks for one option, rounded to cents. theta is per calendar day, vega and rho per 1%
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GreeksResponse {
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(value_type = f64)]
+    pub delta: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(value_type = f64)]
+    pub gamma: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(value_type = f64)]
+    pub theta: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(value_type = f64)]
+    pub vega: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(value_type = f64)]
+    pub rho: Decimal,
+}
@@ -0,0 +1,6 @@
+mod greeks_response;
+mod option_prices_response;
+
+
+pub use greeks_response::GreeksResponse;
+pub use option_prices_response::OptionPricesResponse;
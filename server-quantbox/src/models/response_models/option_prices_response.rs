@@ -0,0 +1,14 @@
+use rust_decimal::Decimal;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+// single-option prices, rounded to cents and written as JSON numbers
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OptionPricesResponse {
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(value_type = f64, example = 10.45)]
+    pub call_price: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    #[schema(value_type = f64, example = 5.57)]
+    pub put_price: Decimal,
+}
@@ -0,0 +1,6 @@
+mod simulation_engine;
+mod simulation_request;
+
+
+pub use simulation_engine::SimulationEngine;
+pub use simulation_request::SimulationRequest;
@@ -0,0 +1,13 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+// which Monte Carlo engine prices a simulation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulationEngine {
+    // MonteCarloEngine on one thread; ignores kernel, num_threads and chunk_size
+    Sequential,
+    // ParallelMonteCarloEngine across the compute pool
+    #[default]
+    Parallel,
+}
@@ -0,0 +1,20 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+use crate::models::monte_carlo_models::MonteCarloRequest;
+use crate::models::simulation_models::SimulationEngine;
+use crate::models::validation::{Validate, ValidationError};
+
+// body of POST /api/v1/monte-carlo/simulations: the Monte Carlo pricing fields plus the engine
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SimulationRequest {
+    #[serde(default)]
+    pub engine: SimulationEngine,
+    #[serde(flatten)]
+    pub params: MonteCarloRequest,
+}
+
+impl Validate for SimulationRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.params.validate()
+    }
+}
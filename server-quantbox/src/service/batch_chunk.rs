@@ -0,0 +1,9 @@
+use crate::models::black_scholes_models::BatchValuation;
+
+// one piece of a streamed batch: valuations for options offset..offset + len, in request order
+#[derive(Debug)]
+pub struct BatchChunk {
+    pub offset: usize,
+    pub total: usize,
+    pub valuation: BatchValuation,
+}
@@ -0,0 +1,4 @@
+pub mod batch_chunk;
+pub mod pricing_service;
+pub mod stream_event;
+pub mod stream_sink;
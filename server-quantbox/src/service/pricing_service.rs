@@ -0,0 +1,339 @@
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+use tokio::runtime::Handle;
+use tokio::sync::mpsc;
+use tracing::Span;
+use crate::api::api_error::ApiError;
+use crate::auth::usage_meter::PathCharge;
+use crate::compute::black_scholes::{calculate_greeks, calculate_options_prices, generate_heatmap_data, price_batch_slices};
+use crate::compute::compute_pool::{ComputePool, ComputeSlot};
+use crate::compute::convergence::run_convergence_analysis;
+use crate::compute::monte_carlo_engine::MonteCarloEngine;
+use crate::compute::parallel_monte_carlo_engine::ParallelMonteCarloEngine;
+use crate::models::black_scholes_models::{BatchColumns, BatchPricingResponse, BatchValuation, BlackScholesRequest,
+                                          BlackScholesResult, GreekRequest, Greeks, HeatmapData};
+use crate::models::monte_carlo_models::{ComparisonResponse, ComparisonResult, ConvergenceRequest, ConvergenceResponse,
+                                        MonteCarloRequest, MonteCarloResponse, MonteCarloResult,
+                                        TargetPrecisionRequest, TargetPrecisionResponse, TargetPrecisionResult};
+use crate::service::batch_chunk::BatchChunk;
+use crate::service::stream_event::{ConvergenceEvent, MonteCarloEvent};
+use crate::service::stream_sink::StreamSink;
+use crate::telemetry::{option_summary, record_inputs, record_run};
+
+// options per message when a streamed batch doesn't ask for a size
+pub const DEFAULT_BATCH_CHUNK_LEN: usize = 10_000;
+
+// items on a streaming channel; a failed run ends with one Err
+pub type StreamReceiver<T> = mpsc::Receiver<Result<T, ApiError>>;
+
+fn black_scholes_summary(req: &BlackScholesRequest) -> String {
+    option_summary(req.spot_price, req.strike_price, req.risk_free_rate, req.volatility, req.time_to_maturity)
+}
+
+fn monte_carlo_summary(req: &MonteCarloRequest) -> String {
+    option_summary(req.spot_price, req.strike_price, req.risk_free_rate, req.volatility, req.time_to_expiry)
+}
+
+// the pricing operations behind every transport. the REST handlers and the gRPC service
+// decode and validate their own request formats and then call in here, so both go through
+// the same compute pool admission and the same pricing code. each call also fills in the
+// pricing fields of the caller's request span
+#[derive(Clone)]
+pub struct PricingService {
+    compute_pool: Arc<ComputePool>,
+    // how long a stream may run, counted from when it's opened
+    stream_timeout: Duration,
+}
+
+impl PricingService {
+    pub fn new(compute_pool: Arc<ComputePool>, stream_timeout: Duration) -> Self {
+        PricingService { compute_pool, stream_timeout }
+    }
+
+    pub fn options_prices(&self, req: &BlackScholesRequest) -> BlackScholesResult {
+        record_inputs(black_scholes_summary(req));
+        let start_time: Instant = Instant::now();
+        let prices: BlackScholesResult = calculate_options_prices(
+            req.spot_price, req.strike_price, req.risk_free_rate, req.volatility, req.time_to_maturity,
+        );
+        record_run(&Span::current(), "black_scholes", None, start_time.elapsed());
+        prices
+    }
+
+    pub fn greeks(&self, req: &GreekRequest) -> Greeks {
+        record_inputs(format!("{} type={:?}", black_scholes_summary(&req.scholes), req.option_type));
+        let start_time: Instant = Instant::now();
+        let greeks: Greeks = calculate_greeks(
+            req.scholes.spot_price,
+            req.scholes.strike_price,
+            req.scholes.risk_free_rate,
+            req.scholes.volatility,
+            req.scholes.time_to_maturity,
+            req.option_type,
+        );
+        record_run(&Span::current(), "black_scholes", None, start_time.elapsed());
+        greeks
+    }
+
+    pub fn heatmap(&self, req: &BlackScholesRequest) -> HeatmapData {
+        record_inputs(black_scholes_summary(req));
+        let start_time: Instant = Instant::now();
+        let heatmap: HeatmapData = generate_heatmap_data(
+            req.spot_price, req.strike_price, req.risk_free_rate, req.volatility, req.time_to_maturity,
+        );
+        record_run(&Span::current(), "black_scholes", None, start_time.elapsed());
+        heatmap
+    }
+
+    pub async fn batch(&self, columns: BatchColumns) -> Result<BatchPricingResponse, ApiError> {
+        let count: usize = columns.len();
+        record_inputs(format!("options={}", count));
+        // priced a chunk at a time, so a request that times out stops between chunks
+        // rather than holding its slot until the whole batch is done
+        let (valuation, computation_time) = self.compute_pool.run_cancellable(move |cancel| {
+            let start_time: Instant = Instant::now();
+            let mut valuation: BatchValuation = BatchValuation::default();
+            for offset in (0..count).step_by(DEFAULT_BATCH_CHUNK_LEN) {
+                if cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+                let end: usize = (offset + DEFAULT_BATCH_CHUNK_LEN).min(count);
+                match price_batch_slices(
+                    &columns.spot_price[offset..end],
+                    &columns.strike_price[offset..end],
+                    &columns.risk_free_rate[offset..end],
+                    &columns.volatility[offset..end],
+                    &columns.time_to_maturity[offset..end],
+                ) {
+                    Ok(chunk) => valuation.append(chunk),
+                    Err(err) => return (Err(err), start_time.elapsed()),
+                }
+            }
+            (Ok(valuation), start_time.elapsed())
+        }).await?;
+        let valuation: BatchValuation = valuation?;
+        record_run(&Span::current(), "black_scholes_batch", None, computation_time);
+
+        Ok(BatchPricingResponse {
+            count,
+            computation_time_ms: computation_time.as_millis(),
+            valuation,
+        })
+    }
+
+    // prices chunk_len options at a time and sends each chunk as soon as it's done.
+    // dropping the receiver stops the run before the next chunk, and a client reading
+    // slower than that holds it up at most until the stream's deadline
+    pub async fn stream_batch(&self, columns: BatchColumns, chunk_len: usize) -> Result<StreamReceiver<BatchChunk>, ApiError> {
+        record_inputs(format!("options={} chunk_len={}", columns.len(), chunk_len));
+        let slot: ComputeSlot = self.compute_pool.reserve().await?;
+        let span: Span = Span::current();
+
+        Ok(self.spawn_stream(slot, move |sink| {
+            let start_time: Instant = Instant::now();
+            let total: usize = columns.len();
+            for offset in (0..total).step_by(chunk_len.max(1)) {
+                let end: usize = (offset + chunk_len).min(total);
+                let chunk: Result<BatchChunk, ApiError> = price_batch_slices(
+                    &columns.spot_price[offset..end],
+                    &columns.strike_price[offset..end],
+                    &columns.risk_free_rate[offset..end],
+                    &columns.volatility[offset..end],
+                    &columns.time_to_maturity[offset..end],
+                ).map(|valuation| BatchChunk { offset, total, valuation }).map_err(ApiError::from);
+                if !sink.send(chunk) {
+                    break;
+                }
+            }
+            finish_stream(&span, "black_scholes_batch", None, start_time.elapsed());
+        }))
+    }
+
+    pub async fn monte_carlo(&self, req: MonteCarloRequest) -> Result<MonteCarloResponse, ApiError> {
+        record_inputs(monte_carlo_summary(&req));
+        let mut charge: PathCharge = PathCharge::reserve(req.num_simulations).await?;
+        let (result, simulated, computation_time) = self.compute_pool.run_cancellable(move |cancel| {
+            let start_time: Instant = Instant::now();
+            let mut stopped_at: Option<usize> = None;
+            let result: MonteCarloResult = MonteCarloEngine::price_european_option_with_progress(&req, |done| {
+                charge.record(done);
+                if cancel.load(Ordering::Relaxed) {
+                    stopped_at = Some(done);
+                }
+                stopped_at.is_none()
+            });
+            let simulated: usize = stopped_at.unwrap_or(req.num_simulations);
+            charge.record(simulated);
+            (result, simulated, start_time.elapsed())
+        }).await?;
+        record_run(&Span::current(), "sequential", Some(simulated), computation_time);
+
+        Ok(MonteCarloResponse::from_result(&result, simulated, computation_time))
+    }
+
+    pub async fn monte_carlo_parallel(&self, req: MonteCarloRequest) -> Result<MonteCarloResponse, ApiError> {
+        record_inputs(monte_carlo_summary(&req));
+        let mut charge: PathCharge = PathCharge::reserve(req.num_simulations).await?;
+        let (result, simulated, computation_time) = self.compute_pool.run_cancellable(move |cancel| {
+            let start_time: Instant = Instant::now();
+            let mut simulated: usize = 0;
+            let result: MonteCarloResult = ParallelMonteCarloEngine::price_european_option_with_progress(&req, |_, done| {
+                simulated = done;
+                charge.record(done);
+                !cancel.load(Ordering::Relaxed)
+            });
+            (result, simulated, start_time.elapsed())
+        }).await?;
+        record_run(&Span::current(), "parallel", Some(simulated), computation_time);
+
+        Ok(MonteCarloResponse::from_result(&result, simulated, computation_time))
+    }
+
+    pub async fn comparison(&self, req: MonteCarloRequest) -> Result<ComparisonResponse, ApiError> {
+        record_inputs(monte_carlo_summary(&req));
+        let mut charge: PathCharge = PathCharge::reserve(req.num_simulations).await?;
+        let (comparison, simulated, computation_time) = self.compute_pool.run_cancellable(move |cancel| {
+            let start_time: Instant = Instant::now();
+            let mut stopped_at: Option<usize> = None;
+            let comparison: ComparisonResult = MonteCarloEngine::compare_with_black_scholes_with_progress(&req, |done| {
+                charge.record(done);
+                if cancel.load(Ordering::Relaxed) {
+                    stopped_at = Some(done);
+                }
+                stopped_at.is_none()
+            });
+            let simulated: usize = stopped_at.unwrap_or(req.num_simulations);
+            charge.record(simulated);
+            (comparison, simulated, start_time.elapsed())
+        }).await?;
+        record_run(&Span::current(), "sequential", Some(simulated), computation_time);
+
+        Ok(ComparisonResponse::from_comparison(comparison, simulated, computation_time))
+    }
+
+    pub async fn convergence(&self, req: ConvergenceRequest) -> Result<ConvergenceResponse, ApiError> {
+        record_inputs(convergence_summary(&req));
+        let mut charge: PathCharge = PathCharge::reserve(req.max_simulations).await?;
+        let (response, computation_time) = self.compute_pool.run_cancellable(move |cancel| {
+            let start_time: Instant = Instant::now();
+            let response: ConvergenceResponse = run_convergence_analysis(&req, |point, _, _| {
+                charge.record(point.num_simulations);
+                !cancel.load(Ordering::Relaxed)
+            });
+            (response, start_time.elapsed())
+        }).await?;
+        record_run(&Span::current(), "convergence", Some(convergence_paths(&response)), computation_time);
+
+        Ok(response)
+    }
+
+    pub async fn target_precision(&self, req: TargetPrecisionRequest) -> Result<TargetPrecisionResponse, ApiError> {
+        record_inputs(format!(
+            "{} target={} max_simulations={}",
+            option_summary(req.spot_price, req.strike_price, req.risk_free_rate, req.volatility, req.time_to_expiry),
+            req.target_standard_error, req.max_simulations,
+        ));
+        let mut charge: PathCharge = PathCharge::reserve(req.max_simulations).await?;
+        let (response, computation_time) = self.compute_pool.run_cancellable(move |cancel| {
+            let start_time: Instant = Instant::now();
+            let outcome: TargetPrecisionResult = ParallelMonteCarloEngine::price_to_target(&req, |_, done| {
+                charge.record(done);
+                !cancel.load(Ordering::Relaxed)
+            });
+            (TargetPrecisionResponse::from_result(&req, &outcome), start_time.elapsed())
+        }).await?;
+        record_run(&Span::current(), "target_precision", Some(response.monte_carlo.num_simulations), computation_time);
+
+        Ok(response)
+    }
+
+    // takes a compute slot up front, so a saturated pool is reported before anything is streamed
+    pub async fn stream_convergence(&self, req: ConvergenceRequest) -> Result<StreamReceiver<ConvergenceEvent>, ApiError> {
+        record_inputs(convergence_summary(&req));
+        let slot: ComputeSlot = self.compute_pool.reserve().await?;
+        let mut charge: PathCharge = PathCharge::reserve(req.max_simulations).await?;
+        let span: Span = Span::current();
+
+        // every point is its own data point, so each one waits for room rather than being dropped
+        Ok(self.spawn_stream(slot, move |sink| {
+            let start_time: Instant = Instant::now();
+            let response: ConvergenceResponse = run_convergence_analysis(&req, |point, _, _| {
+                charge.record(point.num_simulations);
+                sink.send(Ok(ConvergenceEvent::Point(point.clone())))
+            });
+            finish_stream(&span, "convergence", Some(convergence_paths(&response)), start_time.elapsed());
+            sink.send(Ok(ConvergenceEvent::Complete(response)));
+        }))
+    }
+
+    pub async fn stream_monte_carlo_parallel(&self, req: MonteCarloRequest) -> Result<StreamReceiver<MonteCarloEvent>, ApiError> {
+        record_inputs(monte_carlo_summary(&req));
+        let slot: ComputeSlot = self.compute_pool.reserve().await?;
+        let mut charge: PathCharge = PathCharge::reserve(req.num_simulations).await?;
+        let span: Span = Span::current();
+
+        Ok(self.spawn_stream(slot, move |sink| {
+            let start_time: Instant = Instant::now();
+            let mut simulated: usize = 0;
+
+            let result: MonteCarloResult = ParallelMonteCarloEngine::price_european_option_with_progress(&req, |estimate, done| {
+                simulated = done;
+                charge.record(done);
+                let running: MonteCarloResponse = MonteCarloResponse::from_result(estimate, done, start_time.elapsed());
+                sink.progress(MonteCarloEvent::Progress(running))
+            });
+
+            let response: MonteCarloResponse = MonteCarloResponse::from_result(&result, simulated, start_time.elapsed());
+            finish_stream(&span, "parallel", Some(simulated), start_time.elapsed());
+            sink.send(Ok(MonteCarloEvent::Complete(response)));
+        }))
+    }
+
+    // runs the job on the reserved slot, feeding its results into the returned channel.
+    // once the receiver is dropped (client went away) or stream_timeout has passed, the
+    // job stops at its next checkpoint and gives the slot back
+    fn spawn_stream<T, F>(&self, slot: ComputeSlot, job: F) -> StreamReceiver<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&StreamSink<T>) + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel::<Result<T, ApiError>>(64);
+        let deadline: Instant = Instant::now() + self.stream_timeout;
+        let sink: StreamSink<T> = StreamSink::new(tx.clone(), deadline, Handle::current());
+        let compute_pool: Arc<ComputePool> = Arc::clone(&self.compute_pool);
+
+        tokio::spawn(async move {
+            let outcome = compute_pool.run_reserved(slot, move || {
+                job(&sink);
+                sink.close_timed_out();
+            }).await;
+
+            if let Err(err) = outcome {
+                let _ = tokio::time::timeout_at(deadline.into(), tx.send(Err(ApiError::from(err)))).await;
+            }
+        });
+
+        rx
+    }
+}
+
+fn convergence_summary(req: &ConvergenceRequest) -> String {
+    format!(
+        "{} max_simulations={} step_size={}",
+        option_summary(req.spot_price, req.strike_price, req.risk_free_rate, req.volatility, req.time_to_expiry),
+        req.max_simulations, req.step_size,
+    )
+}
+
+// paths behind the last convergence point; fewer than max_simulations if the run was stopped
+pub fn convergence_paths(response: &ConvergenceResponse) -> usize {
+    response.convergence_data.last().map_or(0, |point| point.num_simulations)
+}
+
+// a stream's response event fires before any pricing is done, so the outcome also
+// goes out as an event of its own
+fn finish_stream(span: &Span, engine: &'static str, simulations: Option<usize>, compute_time: Duration) {
+    record_run(span, engine, simulations, compute_time);
+    tracing::info!(parent: span, engine, simulations, compute_ms = compute_time.as_secs_f64() * 1000.0, "stream finished");
+}
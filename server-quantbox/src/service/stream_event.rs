@@ -0,0 +1,15 @@
+use crate::models::monte_carlo_models::{ConvergencePoint, ConvergenceResponse, MonteCarloResponse};
+
+// what a streamed convergence run yields: every point as it's measured, then the full analysis
+#[derive(Debug)]
+pub enum ConvergenceEvent {
+    Point(ConvergencePoint),
+    Complete(ConvergenceResponse),
+}
+
+// a streamed parallel Monte Carlo run: a running estimate after each round, then the final price
+#[derive(Debug)]
+pub enum MonteCarloEvent {
+    Progress(MonteCarloResponse),
+    Complete(MonteCarloResponse),
+}
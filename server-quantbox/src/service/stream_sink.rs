@@ -0,0 +1,60 @@
+use std::time::Instant;
+use tokio::runtime::Handle;
+use tokio::sync::mpsc;
+use crate::api::api_error::ApiError;
+
+// the compute side of a streaming channel. a slow client can't hold a compute slot: progress
+// frames are dropped while the channel is full (the next one carries the newer estimate
+// anyway), and the frames that have to arrive wait no later than the stream's deadline.
+// every send reports whether the run should go on, i.e. the receiver is still there and
+// the deadline hasn't passed
+pub struct StreamSink<T> {
+    tx: mpsc::Sender<Result<T, ApiError>>,
+    deadline: Instant,
+    runtime: Handle,
+}
+
+impl<T> StreamSink<T> {
+    pub fn new(tx: mpsc::Sender<Result<T, ApiError>>, deadline: Instant, runtime: Handle) -> Self {
+        StreamSink { tx, deadline, runtime }
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.tx.is_closed() && Instant::now() < self.deadline
+    }
+
+    // a frame that's superseded by the next one, so it's fine to lose: a running Monte Carlo
+    // estimate, say. anything the client needs every one of goes through send
+    pub fn progress(&self, item: T) -> bool {
+        if !self.is_open() {
+            return false;
+        }
+        match self.tx.try_send(Ok(item)) {
+            Ok(()) | Err(mpsc::error::TrySendError::Full(_)) => self.is_open(),
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        }
+    }
+
+    // a frame the client needs (a batch chunk, a convergence point, the final result); waits
+    // for room until the deadline
+    pub fn send(&self, item: Result<T, ApiError>) -> bool {
+        if !self.is_open() {
+            return false;
+        }
+        let deadline: tokio::time::Instant = tokio::time::Instant::from_std(self.deadline);
+        let sent: bool = matches!(
+            self.runtime.block_on(async { tokio::time::timeout_at(deadline, self.tx.send(item)).await }),
+            Ok(Ok(())),
+        );
+        sent && self.is_open()
+    }
+
+    // tells the client why the stream ended early, if there's room to
+    pub fn close_timed_out(&self) {
+        if !self.tx.is_closed() && Instant::now() >= self.deadline {
+            let _ = self.tx.try_send(Err(ApiError::TimedOut {
+                message: "the stream ran past its deadline".to_string(),
+            }));
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::fmt::Display;
+use std::time::Duration;
+use axum::extract::MatchedPath;
+use axum::http::Request;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use tower_http::trace::MakeSpan;
+use tracing::Span;
+use tracing::field::Empty;
+use tracing_subscriber::{EnvFilter, Layer, Registry, fmt, prelude::*};
+use crate::config::log_format::LogFormat;
+use crate::config::server_config::ServerConfig;
+use crate::metrics;
+
+// set on the way in when the client didn't send one, and echoed back on the response
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+// spans go to the collector at info regardless of log_level, so quieter logs don't cost traces
+const OTLP_DIRECTIVES: &str = "warn,server_quantbox=info,tower_http=info";
+
+// the installed log subscriber, plus the OTLP pipeline when one is configured
+pub struct Telemetry {
+    tracer_provider: Option<SdkTracerProvider>,
+}
+
+impl Telemetry {
+    // installs the global subscriber. RUST_LOG, when set, replaces the log_level filter.
+    // with OTLP on this has to run inside the tokio runtime the exporter's channel lives on
+    pub fn init(config: &ServerConfig) -> Result<Telemetry, String> {
+        let filter: EnvFilter = match std::env::var("RUST_LOG") {
+            Ok(directives) => EnvFilter::try_new(&directives).map_err(|e| format!("RUST_LOG={:?}: {}", directives, e))?,
+            Err(_) => EnvFilter::new(format!("warn,server_quantbox={0},quantbox={0},tower_http={0}", config.log_level)),
+        };
+        let log_layer: Box<dyn Layer<Registry> + Send + Sync> = match config.log_format {
+            LogFormat::Pretty => fmt::layer().with_filter(filter).boxed(),
+            LogFormat::Json => fmt::layer().json().with_current_span(true).with_span_list(false).with_filter(filter).boxed(),
+        };
+
+        let tracer_provider: Option<SdkTracerProvider> = match &config.otlp.endpoint {
+            Some(endpoint) => {
+                let exporter: SpanExporter = SpanExporter::builder()
+                    .with_tonic()
+                    .with_endpoint(endpoint)
+                    .build()
+                    .map_err(|e| format!("failed to set up OTLP export to {}: {}", endpoint, e))?;
+                Some(SdkTracerProvider::builder()
+                    .with_batch_exporter(exporter)
+                    .with_resource(Resource::builder().with_service_name(config.otlp.service_name.clone()).build())
+                    .build())
+            }
+            None => None,
+        };
+        let otlp_layer = tracer_provider.as_ref().map(|provider| {
+            tracing_opentelemetry::layer()
+                .with_tracer(provider.tracer("quantbox"))
+                .with_filter(EnvFilter::new(OTLP_DIRECTIVES))
+        });
+
+        tracing_subscriber::registry()
+            .with(log_layer)
+            .with(otlp_layer)
+            .try_init()
+            .map_err(|e| format!("failed to install the log subscriber: {}", e))?;
+
+        Ok(Telemetry { tracer_provider })
+    }
+
+    // exports whatever spans are still buffered. the exporter needs the runtime to make
+    // progress, so the blocking flush runs off the async workers
+    pub async fn shutdown(self) {
+        if let Some(provider) = self.tracer_provider {
+            let flushed = tokio::task::spawn_blocking(move || provider.shutdown()).await;
+            if let Ok(Err(err)) = flushed {
+                eprintln!("failed to flush OTLP spans: {}", err);
+            }
+        }
+    }
+}
+
+// one span per REST or gRPC request. the pricing fields start empty and are filled in by
+// the service layer once it knows them (see record_inputs / record_run)
+#[derive(Debug, Clone, Copy)]
+pub struct MakeRequestSpan {
+    pub transport: &'static str,
+}
+
+impl<B> MakeSpan<B> for MakeRequestSpan {
+    fn make_span(&mut self, request: &Request<B>) -> Span {
+        // the route template rather than the path, so /jobs/{id} doesn't fan out per job
+        let route: &str = request.extensions().get::<MatchedPath>()
+            .map_or(request.uri().path(), MatchedPath::as_str);
+        let request_id: &str = request.headers().get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        tracing::info_span!(
+            "request",
+            otel.name = %format_args!("{} {}", request.method(), route),
+            otel.kind = "server",
+            transport = self.transport,
+            request_id,
+            method = %request.method(),
+            route,
+            inputs = Empty,
+            engine = Empty,
+            simulations = Empty,
+            compute_ms = Empty,
+        )
+    }
+}
+
+// the option being priced, as a compact field value
+pub fn option_summary(spot_price: f64, strike_price: f64, risk_free_rate: f64, volatility: f64, time: f64) -> String {
+    format!("S={} K={} r={} vol={} T={}", spot_price, strike_price, risk_free_rate, volatility, time)
+}
+
+// both are no-ops outside a request or job span, e.g. from the CLI
+pub fn record_inputs(inputs: impl Display) {
+    Span::current().record("inputs", tracing::field::display(inputs));
+}
+
+// on the span and in the engine metrics. simulations is None for the analytic engines.
+// tenants are charged separately, from the compute thread (see usage_meter::PathCharge)
+pub fn record_run(span: &Span, engine: &'static str, simulations: Option<usize>, compute_time: Duration) {
+    metrics::record_engine_run(engine, simulations, compute_time);
+    span.record("engine", engine);
+    if let Some(simulations) = simulations {
+        span.record("simulations", simulation_count(simulations));
+    }
+    span.record("compute_ms", compute_time.as_secs_f64() * 1000.0);
+}
+
+// the OTLP layer exports unsigned fields as strings, so counts go out as i64 to stay numeric
+pub fn simulation_count(simulations: usize) -> i64 {
+    i64::try_from(simulations).unwrap_or(i64::MAX)
+}
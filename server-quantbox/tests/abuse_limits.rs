@@ -0,0 +1,256 @@
+// the limits that keep one client from taking the server down: body size, rate limiting,
+// convergence point counts, the background job cap and timeouts (request and stream) that stop
+// the compute they cut off
+use std::sync::Arc;
+use std::time::Duration;
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header},
+};
+use serde_json::{Value, json};
+use server_quantbox::api::app_state::AppState;
+use server_quantbox::api::rate_limiter::RateLimiter;
+use server_quantbox::api::routes::create_router;
+use server_quantbox::auth::authenticator::Authenticator;
+use server_quantbox::compute::compute_pool::{ComputePool, ComputePoolConfig};
+use server_quantbox::config::rate_limit_config::RateLimitConfig;
+use server_quantbox::config::server_config::ServerConfig;
+use server_quantbox::jobs::job_store::{JobStore, JobStoreConfig};
+use server_quantbox::service::pricing_service::PricingService;
+use tower::ServiceExt;
+
+fn app(config: &ServerConfig) -> (Router, Arc<ComputePool>) {
+    let compute_pool: Arc<ComputePool> = Arc::new(ComputePool::new(ComputePoolConfig {
+        threads: 2,
+        max_concurrent_jobs: 2,
+        max_queued_jobs: 8,
+        retry_after_secs: 1,
+        ..config.compute.clone()
+    }));
+    let job_store: Arc<JobStore> = Arc::new(JobStore::new(JobStoreConfig {
+        ttl: Duration::from_secs(60),
+        persistence_dir: None,
+    }).expect("in-memory job store"));
+
+    let router: Router = create_router(AppState {
+        pricing: PricingService::new(Arc::clone(&compute_pool), config.stream_timeout()),
+        compute_pool: Arc::clone(&compute_pool),
+        job_store,
+        rate_limiter: Arc::new(RateLimiter::new(config.rate_limit.clone())),
+        auth: Arc::new(Authenticator::new(config.auth.clone()).unwrap()),
+        simulation: config.simulation,
+        request_timeout: config.request_timeout(),
+    }, config);
+    (router, compute_pool)
+}
+
+async fn post_json(app: &Router, uri: &str, body: String) -> (StatusCode, Option<String>, Value) {
+    let request: Request<Body> = Request::post(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status: StatusCode = response.status();
+    let retry_after: Option<String> = response.headers().get(header::RETRY_AFTER)
+        .map(|value| value.to_str().unwrap().to_string());
+    let body: Vec<u8> = to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec();
+    (status, retry_after, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn option_inputs() -> Value {
+    json!({
+        "spot_price": 100.0, "strike_price": 100.0, "risk_free_rate": 0.05, "volatility": 0.2,
+        "time_to_expiry": 1.0,
+    })
+}
+
+#[tokio::test]
+async fn oversized_bodies_are_rejected_with_413() {
+    let mut config: ServerConfig = ServerConfig::default();
+    config.http.body_limit_bytes = 1024;
+    let (app, _) = app(&config);
+
+    let mut body: Value = option_inputs();
+    body["padding"] = json!("x".repeat(4096));
+    let (status, _, error) = post_json(&app, "/api/v1/monte-carlo/simulations", body.to_string()).await;
+
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(error["code"], "payload_too_large");
+}
+
+#[tokio::test]
+async fn clients_past_their_burst_get_429_with_retry_after() {
+    let config: ServerConfig = ServerConfig {
+        rate_limit: RateLimitConfig {
+            enabled: true,
+            requests_per_second: 0.5,
+            burst: 2,
+            trust_forwarded_for: true,
+        },
+        ..ServerConfig::default()
+    };
+    let (app, _) = app(&config);
+
+    // without authentication clients go by IP; an API key of their own making doesn't help
+    let request = |ip: &str, api_key: &str| Request::get("/api/v1/health")
+        .header("x-forwarded-for", ip)
+        .header("x-api-key", api_key)
+        .body(Body::empty())
+        .unwrap();
+    for api_key in ["alice", "bob"] {
+        assert_eq!(app.clone().oneshot(request("10.0.0.1", api_key)).await.unwrap().status(), StatusCode::OK);
+    }
+
+    let response = app.clone().oneshot(request("10.0.0.1", "carol")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "2");
+    let body: Vec<u8> = to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec();
+    let error: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error["code"], "rate_limited");
+
+    // each address has its own bucket
+    assert_eq!(app.clone().oneshot(request("10.0.0.2", "alice")).await.unwrap().status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn convergence_runs_with_too_many_points_are_rejected() {
+    let (app, _) = app(&ServerConfig::default());
+
+    let mut body: Value = option_inputs();
+    body["max_simulations"] = json!(10_000_000);
+    body["step_size"] = json!(100);
+    let (status, _, error) = post_json(&app, "/api/v1/monte-carlo/convergence", body.to_string()).await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error["code"], "too_many_points");
+    assert_eq!(error["field"], "step_size");
+}
+
+#[tokio::test]
+async fn configured_simulation_caps_and_defaults_apply_to_requests() {
+    let mut config: ServerConfig = ServerConfig::default();
+    config.simulation.max_simulations = 200_000;
+    config.simulation.default_num_simulations = 5_000;
+    let (app, _) = app(&config);
+
+    // under the built-in ceiling but over the configured one
+    let mut over: Value = option_inputs();
+    over["num_simulations"] = json!(300_000);
+    over["engine"] = json!("sequential");
+    let (status, _, error) = post_json(&app, "/api/v1/monte-carlo/simulations", over.to_string()).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error["code"], "too_many_simulations");
+    assert_eq!(error["field"], "num_simulations");
+
+    let mut defaulted: Value = option_inputs();
+    defaulted["engine"] = json!("sequential");
+    let (status, _, response) = post_json(&app, "/api/v1/monte-carlo/simulations", defaulted.to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response["num_simulations"], 5_000);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn timed_out_simulations_stop_computing() {
+    let config: ServerConfig = ServerConfig {
+        request_timeout_secs: 1,
+        ..ServerConfig::default()
+    };
+    let (app, compute_pool) = app(&config);
+
+    let mut body: Value = option_inputs();
+    body["num_simulations"] = json!(50_000_000);
+    body["engine"] = json!("sequential");
+    let (status, _, _) = post_json(&app, "/api/v1/monte-carlo/simulations", body.to_string()).await;
+    assert_eq!(status, StatusCode::REQUEST_TIMEOUT);
+
+    // the run gives up at its next checkpoint and frees its slot, well before it would
+    // have finished
+    let mut in_flight: usize = usize::MAX;
+    for _ in 0..50 {
+        in_flight = compute_pool.stats().in_flight;
+        if in_flight == 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(in_flight, 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn background_jobs_have_their_own_cap() {
+    let mut config: ServerConfig = ServerConfig::default();
+    config.compute.max_pending_jobs = 1;
+    let (app, compute_pool) = app(&config);
+
+    let mut params: Value = option_inputs();
+    params["num_simulations"] = json!(50_000_000);
+    let job: String = json!({ "kind": "monte_carlo", "params": params }).to_string();
+
+    let (status, _, submitted) = post_json(&app, "/api/v1/jobs", job.clone()).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let (status, retry_after, error) = post_json(&app, "/api/v1/jobs", job).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(retry_after.as_deref(), Some("1"));
+    assert_eq!(error["code"], "overloaded");
+    assert_eq!(compute_pool.stats().pending_jobs, 1);
+
+    // the pending job doesn't count against the queue synchronous requests wait in
+    let mut body: Value = option_inputs();
+    body["num_simulations"] = json!(1_000);
+    let (status, _, _) = post_json(&app, "/api/v1/monte-carlo/simulations", body.to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let uri: String = format!("/api/v1/jobs/{}", submitted["id"].as_str().unwrap());
+    let response = app.clone().oneshot(Request::delete(uri).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    for _ in 0..100 {
+        if compute_pool.stats().pending_jobs == 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(compute_pool.stats().pending_jobs, 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn streams_are_cut_off_at_their_deadline() {
+    let config: ServerConfig = ServerConfig {
+        stream_timeout_secs: 1,
+        ..ServerConfig::default()
+    };
+    let (app, compute_pool) = app(&config);
+
+    let mut params: Value = option_inputs();
+    params["max_simulations"] = json!(50_000_000);
+    params["step_size"] = json!(1_000_000);
+    let query: String = params.as_object().unwrap().iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<String>>()
+        .join("&");
+    let request: Request<Body> = Request::get(format!("/api/v1/monte-carlo/convergence/stream?{}", query))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // the stream ends on its own, with an error event instead of the full analysis
+    let body: Vec<u8> = tokio::time::timeout(Duration::from_secs(30), to_bytes(response.into_body(), usize::MAX))
+        .await
+        .expect("stream ended")
+        .unwrap()
+        .to_vec();
+    let body: String = String::from_utf8(body).unwrap();
+    assert!(!body.contains("event: complete"));
+    let last: &str = body.lines().rev().find_map(|line| line.strip_prefix("data: ")).unwrap();
+    let error: Value = serde_json::from_str(last).unwrap();
+    assert_eq!(error["code"], "timed_out");
+
+    for _ in 0..50 {
+        if compute_pool.stats().in_flight == 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(compute_pool.stats().in_flight, 0);
+}
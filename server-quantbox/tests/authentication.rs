@@ -0,0 +1,312 @@
+// API keys from the admin API, bearer tokens, per-tenant quotas and usage, against an
+// in-memory key store
+use std::sync::Arc;
+use std::time::Duration;
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{HeaderMap, Request, StatusCode, header},
+};
+use jsonwebtoken::{EncodingKey, Header, encode, get_current_timestamp};
+use serde_json::{Value, json};
+use server_quantbox::api::app_state::AppState;
+use server_quantbox::api::rate_limiter::RateLimiter;
+use server_quantbox::api::routes::create_router;
+use server_quantbox::auth::authenticator::Authenticator;
+use server_quantbox::compute::compute_pool::{ComputePool, ComputePoolConfig};
+use server_quantbox::config::auth_config::AuthConfig;
+use server_quantbox::config::rate_limit_config::RateLimitConfig;
+use server_quantbox::config::server_config::ServerConfig;
+use server_quantbox::jobs::job_store::{JobStore, JobStoreConfig};
+use server_quantbox::service::pricing_service::PricingService;
+use tower::ServiceExt;
+
+const ADMIN_TOKEN: &str = "test-admin-token-0123";
+const JWT_SECRET: &str = "test-signing-key-0123456789abcdef";
+
+fn app() -> Router {
+    app_with(|_| {})
+}
+
+fn app_with(configure: impl FnOnce(&mut ServerConfig)) -> Router {
+    let mut config: ServerConfig = ServerConfig {
+        auth: AuthConfig {
+            enabled: true,
+            database: ":memory:".into(),
+            jwt_secret: Some(JWT_SECRET.to_string()),
+            admin_token: Some(ADMIN_TOKEN.to_string()),
+            ..AuthConfig::default()
+        },
+        ..ServerConfig::default()
+    };
+    configure(&mut config);
+    let compute_pool: Arc<ComputePool> = Arc::new(ComputePool::new(ComputePoolConfig {
+        threads: 2,
+        max_concurrent_jobs: 2,
+        max_queued_jobs: 8,
+        max_pending_jobs: 32,
+        retry_after_secs: 1,
+    }));
+    let job_store: Arc<JobStore> = Arc::new(JobStore::new(JobStoreConfig {
+        ttl: Duration::from_secs(60),
+        persistence_dir: None,
+    }).expect("in-memory job store"));
+
+    create_router(AppState {
+        pricing: PricingService::new(Arc::clone(&compute_pool), config.stream_timeout()),
+        compute_pool,
+        job_store,
+        rate_limiter: Arc::new(RateLimiter::new(config.rate_limit.clone())),
+        auth: Arc::new(Authenticator::new(config.auth.clone()).expect("in-memory key store")),
+        simulation: config.simulation,
+        request_timeout: config.request_timeout(),
+    }, &config)
+}
+
+struct TestResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Value,
+}
+
+async fn send(app: &Router, request: Request<Body>) -> TestResponse {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status: StatusCode = response.status();
+    let headers: HeaderMap = response.headers().clone();
+    let body: Vec<u8> = to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec();
+    TestResponse { status, headers, body: serde_json::from_slice(&body).unwrap_or(Value::Null) }
+}
+
+// a request with the given credential header, e.g. ("x-api-key", key)
+fn request(method: &str, uri: &str, credential: Option<(&str, &str)>, body: Option<Value>) -> Request<Body> {
+    let mut builder = Request::builder().method(method).uri(uri);
+    if let Some((name, value)) = credential {
+        builder = builder.header(name, value);
+    }
+    match body {
+        Some(body) => builder.header(header::CONTENT_TYPE, "application/json").body(Body::from(body.to_string())),
+        None => builder.body(Body::empty()),
+    }.unwrap()
+}
+
+async fn create_key(app: &Router, tenant: &str, daily_path_quota: Option<u64>) -> Value {
+    let body: Value = json!({ "tenant": tenant, "daily_path_quota": daily_path_quota });
+    let response: TestResponse = send(app, request("POST", "/api/v1/admin/keys", Some(("x-admin-token", ADMIN_TOKEN)), Some(body))).await;
+    assert_eq!(response.status, StatusCode::CREATED, "{}", response.body);
+    response.body
+}
+
+fn simulation(num_simulations: usize) -> Value {
+    json!({
+        "spot_price": 100.0, "strike_price": 100.0, "risk_free_rate": 0.05, "volatility": 0.2,
+        "time_to_expiry": 1.0, "num_simulations": num_simulations, "engine": "sequential",
+    })
+}
+
+fn token(subject: &str, expires_at: u64, secret: &str) -> String {
+    let claims: Value = json!({ "sub": subject, "exp": expires_at });
+    let token: String = encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes())).unwrap();
+    format!("Bearer {}", token)
+}
+
+#[tokio::test]
+async fn api_keys_are_issued_checked_and_revoked() {
+    let app: Router = app();
+
+    // health stays open to load balancers
+    assert_eq!(send(&app, request("GET", "/api/v1/health", None, None)).await.status, StatusCode::OK);
+
+    let missing: TestResponse = send(&app, request("POST", "/api/v1/options/price", None, Some(simulation(1000)))).await;
+    assert_eq!(missing.status, StatusCode::UNAUTHORIZED);
+    assert_eq!(missing.body["code"], "missing_credentials");
+    assert_eq!(missing.headers[header::WWW_AUTHENTICATE], "Bearer");
+
+    let created: Value = create_key(&app, "acme", None).await;
+    let key: &str = created["key"].as_str().unwrap();
+    assert!(key.starts_with("qbk_"));
+    assert_eq!(created["tenant"], "acme");
+
+    let allowed: TestResponse = send(&app, request("POST", "/api/v1/monte-carlo/simulations", Some(("x-api-key", key)), Some(simulation(1000)))).await;
+    assert_eq!(allowed.status, StatusCode::OK);
+
+    let listed: TestResponse = send(&app, request("GET", "/api/v1/admin/keys", Some(("x-admin-token", ADMIN_TOKEN)), None)).await;
+    assert_eq!(listed.body[0]["id"], created["id"]);
+    assert!(listed.body[0].get("key").is_none());
+
+    let uri: String = format!("/api/v1/admin/keys/{}", created["id"].as_str().unwrap());
+    assert_eq!(send(&app, request("DELETE", &uri, Some(("x-admin-token", ADMIN_TOKEN)), None)).await.status, StatusCode::NO_CONTENT);
+    assert_eq!(send(&app, request("DELETE", &uri, Some(("x-admin-token", ADMIN_TOKEN)), None)).await.status, StatusCode::NOT_FOUND);
+
+    let revoked: TestResponse = send(&app, request("POST", "/api/v1/monte-carlo/simulations", Some(("x-api-key", key)), Some(simulation(1000)))).await;
+    assert_eq!(revoked.status, StatusCode::UNAUTHORIZED);
+    assert_eq!(revoked.body["code"], "invalid_api_key");
+}
+
+#[tokio::test]
+async fn the_admin_api_needs_the_admin_token() {
+    let app: Router = app();
+    let body: Value = json!({ "tenant": "acme" });
+
+    let missing: TestResponse = send(&app, request("POST", "/api/v1/admin/keys", None, Some(body.clone()))).await;
+    assert_eq!(missing.status, StatusCode::UNAUTHORIZED);
+    assert_eq!(missing.body["code"], "invalid_admin_token");
+
+    // a tenant's own key isn't enough
+    let key: String = create_key(&app, "acme", None).await["key"].as_str().unwrap().to_string();
+    let tenant: TestResponse = send(&app, request("GET", "/api/v1/admin/usage", Some(("x-api-key", &key)), None)).await;
+    assert_eq!(tenant.status, StatusCode::UNAUTHORIZED);
+
+    let invalid: TestResponse = send(&app, request("POST", "/api/v1/admin/keys", Some(("x-admin-token", ADMIN_TOKEN)), Some(json!({ "tenant": "a b" })))).await;
+    assert_eq!(invalid.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(invalid.body["field"], "tenant");
+}
+
+#[tokio::test]
+async fn tenants_are_stopped_at_their_daily_path_quota() {
+    let app: Router = app();
+    let key: String = create_key(&app, "small", Some(5000)).await["key"].as_str().unwrap().to_string();
+    let credential: Option<(&str, &str)> = Some(("x-api-key", &key));
+
+    let first: TestResponse = send(&app, request("POST", "/api/v1/monte-carlo/simulations", credential, Some(simulation(3000)))).await;
+    assert_eq!(first.status, StatusCode::OK);
+
+    // a run is turned away up front if its paths don't fit in what's left, job or not
+    let job: Value = json!({ "kind": "monte_carlo", "params": simulation(3000) });
+    for (uri, body) in [("/api/v1/monte-carlo/simulations", simulation(3000)), ("/api/v1/jobs", job)] {
+        let over: TestResponse = send(&app, request("POST", uri, credential, Some(body))).await;
+        assert_eq!(over.status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(over.body["code"], "quota_exceeded");
+        let retry_after: u64 = over.headers[header::RETRY_AFTER].to_str().unwrap().parse().unwrap();
+        assert!((1..=86_400).contains(&retry_after));
+    }
+
+    // one that does fit uses up the rest
+    let last: TestResponse = send(&app, request("POST", "/api/v1/monte-carlo/simulations", credential, Some(simulation(2000)))).await;
+    assert_eq!(last.status, StatusCode::OK);
+
+    // over quota, but still free to read its usage, make analytic calls and manage its jobs
+    let own: TestResponse = send(&app, request("GET", "/api/v1/usage", credential, None)).await;
+    assert_eq!(own.status, StatusCode::OK);
+    assert_eq!(own.body["remaining_paths"], 0);
+    let option: Value = json!({
+        "spot_price": 100.0, "strike_price": 100.0, "risk_free_rate": 0.05, "volatility": 0.2, "time_to_maturity": 1.0,
+    });
+    let analytic: TestResponse = send(&app, request("POST", "/api/v1/options/price", credential, Some(option))).await;
+    assert_eq!(analytic.status, StatusCode::OK, "{}", analytic.body);
+
+    let all: TestResponse = send(&app, request("GET", "/api/v1/admin/usage", Some(("x-admin-token", ADMIN_TOKEN)), None)).await;
+    assert_eq!(all.status, StatusCode::OK);
+    let usage: &Value = &all.body[0];
+    assert_eq!(usage["tenant"], "small");
+    assert_eq!(usage["requests"], 6);
+    assert_eq!(usage["simulated_paths"], 5000);
+    assert_eq!(usage["daily_path_quota"], 5000);
+    assert_eq!(usage["remaining_paths"], 0);
+    assert_eq!(usage["day"].as_str().unwrap().len(), "2026-01-01".len());
+}
+
+#[tokio::test]
+async fn bearer_tokens_name_the_tenant() {
+    let app: Router = app();
+    let expires_at: u64 = get_current_timestamp() + 600;
+
+    let valid: String = token("globex", expires_at, JWT_SECRET);
+    let priced: TestResponse = send(&app, request("POST", "/api/v1/monte-carlo/simulations", Some(("authorization", &valid)), Some(simulation(2000)))).await;
+    assert_eq!(priced.status, StatusCode::OK);
+
+    let usage: TestResponse = send(&app, request("GET", "/api/v1/usage", Some(("authorization", &valid)), None)).await;
+    assert_eq!(usage.status, StatusCode::OK);
+    assert_eq!(usage.body["tenant"], "globex");
+    assert_eq!(usage.body["simulated_paths"], 2000);
+    assert_eq!(usage.body["requests"], 2);
+    assert_eq!(usage.body["daily_path_quota"], AuthConfig::default().default_daily_path_quota);
+
+    let expired: String = token("globex", get_current_timestamp() - 600, JWT_SECRET);
+    let forged: String = token("globex", expires_at, "some-other-key-0123456789abcdef0");
+    for credential in [expired, forged] {
+        let rejected: TestResponse = send(&app, request("GET", "/api/v1/usage", Some(("authorization", &credential)), None)).await;
+        assert_eq!(rejected.status, StatusCode::UNAUTHORIZED);
+        assert_eq!(rejected.body["code"], "invalid_token");
+    }
+}
+
+#[tokio::test]
+async fn tenants_are_rate_limited_across_their_keys() {
+    let app: Router = app_with(|config| config.rate_limit = RateLimitConfig {
+        enabled: true,
+        requests_per_second: 0.01,
+        burst: 2,
+        trust_forwarded_for: true,
+    });
+    let with_ip = |mut request: Request<Body>, ip: &str| {
+        request.headers_mut().insert("x-forwarded-for", ip.parse().unwrap());
+        request
+    };
+    let first: String = create_key(&app, "acme", None).await["key"].as_str().unwrap().to_string();
+    let second: String = create_key(&app, "acme", None).await["key"].as_str().unwrap().to_string();
+
+    // one bucket per tenant, wherever its requests come from
+    for (key, ip) in [(&first, "10.0.0.1"), (&second, "10.0.0.2")] {
+        let allowed: TestResponse = send(&app, with_ip(request("GET", "/api/v1/usage", Some(("x-api-key", key)), None), ip)).await;
+        assert_eq!(allowed.status, StatusCode::OK);
+    }
+    let limited: TestResponse = send(&app, with_ip(request("GET", "/api/v1/usage", Some(("x-api-key", &first)), None), "10.0.0.3")).await;
+    assert_eq!(limited.status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(limited.body["code"], "rate_limited");
+
+    // unknown keys are limited by IP, so made-up keys don't get fresh buckets
+    for guess in ["qbk_guess1", "qbk_guess2"] {
+        let rejected: TestResponse = send(&app, with_ip(request("GET", "/api/v1/usage", Some(("x-api-key", guess)), None), "10.0.0.9")).await;
+        assert_eq!(rejected.status, StatusCode::UNAUTHORIZED);
+    }
+    let limited: TestResponse = send(&app, with_ip(request("GET", "/api/v1/usage", Some(("x-api-key", "qbk_guess3")), None), "10.0.0.9")).await;
+    assert_eq!(limited.status, StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn timed_out_runs_are_charged_for_the_paths_they_simulated() {
+    let app: Router = app_with(|config| config.request_timeout_secs = 1);
+    let key: String = create_key(&app, "acme", None).await["key"].as_str().unwrap().to_string();
+    let credential: Option<(&str, &str)> = Some(("x-api-key", &key));
+
+    let timed_out: TestResponse = send(&app, request("POST", "/api/v1/monte-carlo/simulations", credential, Some(simulation(50_000_000)))).await;
+    assert_eq!(timed_out.status, StatusCode::REQUEST_TIMEOUT);
+
+    // all 50M are reserved up front; the run stops at its next checkpoint and gives back
+    // the ones it didn't simulate
+    let mut simulated_paths: u64 = 0;
+    for _ in 0..50 {
+        let usage: TestResponse = send(&app, request("GET", "/api/v1/usage", credential, None)).await;
+        simulated_paths = usage.body["simulated_paths"].as_u64().unwrap();
+        if simulated_paths < 50_000_000 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert!(simulated_paths > 0);
+    assert!(simulated_paths < 50_000_000);
+}
+
+#[tokio::test]
+async fn jobs_are_only_visible_to_the_tenant_that_submitted_them() {
+    let app: Router = app();
+    let expires_at: u64 = get_current_timestamp() + 600;
+    let owner: String = token("acme", expires_at, JWT_SECRET);
+    let other: String = token("globex", expires_at, JWT_SECRET);
+
+    let job: Value = json!({ "kind": "monte_carlo", "params": simulation(1000) });
+    let submitted: TestResponse = send(&app, request("POST", "/api/v1/jobs", Some(("authorization", &owner)), Some(job))).await;
+    assert_eq!(submitted.status, StatusCode::ACCEPTED);
+    assert_eq!(submitted.body["tenant"], "acme");
+    let uri: String = format!("/api/v1/jobs/{}", submitted.body["id"].as_str().unwrap());
+
+    for method in ["GET", "DELETE"] {
+        let hidden: TestResponse = send(&app, request(method, &uri, Some(("authorization", &other)), None)).await;
+        assert_eq!(hidden.status, StatusCode::NOT_FOUND);
+        assert_eq!(hidden.body["code"], "job_not_found");
+    }
+
+    let own: TestResponse = send(&app, request("GET", &uri, Some(("authorization", &owner)), None)).await;
+    assert_eq!(own.status, StatusCode::OK);
+    assert_eq!(own.body["cancel_requested"], false);
+}
@@ -0,0 +1,102 @@
+// drives a few requests through the router and checks they show up on /metrics. the
+// counters are process-wide, so the expected values are only checked as lower bounds
+use std::sync::Arc;
+use std::time::Duration;
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header},
+};
+use serde_json::{Value, json};
+use server_quantbox::api::app_state::AppState;
+use server_quantbox::api::rate_limiter::RateLimiter;
+use server_quantbox::api::routes::create_router;
+use server_quantbox::auth::authenticator::Authenticator;
+use server_quantbox::compute::compute_pool::{ComputePool, ComputePoolConfig};
+use server_quantbox::config::auth_config::AuthConfig;
+use server_quantbox::config::rate_limit_config::RateLimitConfig;
+use server_quantbox::config::server_config::ServerConfig;
+use server_quantbox::config::simulation_config::SimulationConfig;
+use server_quantbox::jobs::job_store::{JobStore, JobStoreConfig};
+use server_quantbox::service::pricing_service::PricingService;
+use tower::ServiceExt;
+
+fn app() -> Router {
+    let compute_pool: Arc<ComputePool> = Arc::new(ComputePool::new(ComputePoolConfig {
+        threads: 2,
+        max_concurrent_jobs: 2,
+        max_queued_jobs: 8,
+        max_pending_jobs: 32,
+        retry_after_secs: 1,
+    }));
+    let job_store: Arc<JobStore> = Arc::new(JobStore::new(JobStoreConfig {
+        ttl: Duration::from_secs(60),
+        persistence_dir: None,
+    }).expect("in-memory job store"));
+
+    create_router(AppState {
+        pricing: PricingService::new(Arc::clone(&compute_pool), ServerConfig::default().stream_timeout()),
+        compute_pool,
+        job_store,
+        rate_limiter: Arc::new(RateLimiter::new(RateLimitConfig::default())),
+        auth: Arc::new(Authenticator::new(AuthConfig::default()).unwrap()),
+        simulation: SimulationConfig::default(),
+        request_timeout: ServerConfig::default().request_timeout(),
+    }, &ServerConfig::default())
+}
+
+async fn post_json(app: &Router, uri: &str, body: Value) -> StatusCode {
+    let request: Request<Body> = Request::post(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+fn simulation(engine: &str, num_simulations: usize) -> Value {
+    json!({
+        "spot_price": 100.0, "strike_price": 100.0, "risk_free_rate": 0.05, "volatility": 0.2,
+        "time_to_expiry": 1.0, "num_simulations": num_simulations, "engine": engine,
+    })
+}
+
+// the value of the sample whose name and labels start with `prefix`
+fn sample(metrics: &str, prefix: &str) -> f64 {
+    metrics.lines()
+        .find(|line| line.starts_with(prefix))
+        .and_then(|line| line.rsplit(' ').next())
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_else(|| panic!("no sample {} in\n{}", prefix, metrics))
+}
+
+#[tokio::test]
+async fn metrics_cover_routes_engines_pool_and_rejections() {
+    let app: Router = app();
+    assert_eq!(post_json(&app, "/api/v1/monte-carlo/simulations", simulation("sequential", 3000)).await, StatusCode::OK);
+    assert_eq!(post_json(&app, "/api/v1/monte-carlo/simulations", simulation("parallel", 4000)).await, StatusCode::OK);
+    assert_eq!(post_json(&app, "/api/v1/monte-carlo/simulations", simulation("parallel", 1)).await, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let response = app.clone().oneshot(Request::get("/metrics").body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()[header::CONTENT_TYPE].to_str().unwrap().starts_with("text/plain; version=0.0.4"));
+    let body: Vec<u8> = to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec();
+    let metrics: String = String::from_utf8(body).unwrap();
+
+    let route: &str = r#"method="POST",route="/api/v1/monte-carlo/simulations""#;
+    assert!(sample(&metrics, &format!(r#"quantbox_requests_total{{{},status="200",transport="http"}}"#, route)) >= 2.0);
+    assert!(sample(&metrics, &format!(r#"quantbox_requests_total{{{},status="422",transport="http"}}"#, route)) >= 1.0);
+    assert!(sample(&metrics, &format!(r#"quantbox_request_duration_seconds_count{{{},transport="http"}}"#, route)) >= 3.0);
+
+    assert!(sample(&metrics, r#"quantbox_simulated_paths_total{engine="sequential"}"#) >= 3000.0);
+    assert!(sample(&metrics, r#"quantbox_simulated_paths_total{engine="parallel"}"#) >= 4000.0);
+    assert!(sample(&metrics, r#"quantbox_engine_paths_per_second_count{engine="parallel"}"#) >= 1.0);
+    assert!(sample(&metrics, r#"quantbox_engine_compute_seconds_count{engine="sequential"}"#) >= 1.0);
+    assert!(sample(&metrics, r#"quantbox_validation_failures_total{code="too_few_simulations",field="num_simulations"}"#) >= 1.0);
+
+    assert_eq!(sample(&metrics, "quantbox_compute_threads "), 2.0);
+    assert_eq!(sample(&metrics, "quantbox_compute_slots "), 2.0);
+    // a finished job can still hold its slot for a moment after answering
+    assert!(sample(&metrics, "quantbox_compute_in_flight ") <= 2.0);
+    assert!(sample(&metrics, "quantbox_compute_utilisation ") <= 1.0);
+    assert!(sample(&metrics, "quantbox_compute_busy_seconds_total ") > 0.0);
+}
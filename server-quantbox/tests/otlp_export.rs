@@ -0,0 +1,126 @@
+// runs the OTLP exporter against an in-process collector and checks a request span
+// arrives with the pricing fields filled in. lives in its own test binary because
+// Telemetry::init installs the process-wide subscriber
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header},
+};
+use opentelemetry_proto::tonic::collector::trace::v1::{
+    ExportTraceServiceRequest, ExportTraceServiceResponse,
+    trace_service_server::{TraceService, TraceServiceServer},
+};
+use opentelemetry_proto::tonic::common::v1::any_value::Value as AnyValue;
+use opentelemetry_proto::tonic::trace::v1::Span;
+use serde_json::json;
+use server_quantbox::api::app_state::AppState;
+use server_quantbox::api::rate_limiter::RateLimiter;
+use server_quantbox::api::routes::create_router;
+use server_quantbox::auth::authenticator::Authenticator;
+use server_quantbox::compute::compute_pool::{ComputePool, ComputePoolConfig};
+use server_quantbox::config::auth_config::AuthConfig;
+use server_quantbox::config::rate_limit_config::RateLimitConfig;
+use server_quantbox::config::server_config::ServerConfig;
+use server_quantbox::jobs::job_store::{JobStore, JobStoreConfig};
+use server_quantbox::service::pricing_service::PricingService;
+use server_quantbox::telemetry::Telemetry;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tower::ServiceExt;
+
+#[derive(Default, Clone)]
+struct Collector {
+    spans: Arc<Mutex<Vec<Span>>>,
+}
+
+#[tonic::async_trait]
+impl TraceService for Collector {
+    async fn export(
+        &self,
+        request: tonic::Request<ExportTraceServiceRequest>,
+    ) -> Result<tonic::Response<ExportTraceServiceResponse>, tonic::Status> {
+        let spans = request.into_inner().resource_spans.into_iter()
+            .flat_map(|resource| resource.scope_spans)
+            .flat_map(|scope| scope.spans);
+        self.spans.lock().unwrap().extend(spans);
+        Ok(tonic::Response::new(ExportTraceServiceResponse { partial_success: None }))
+    }
+}
+
+fn attribute<'a>(span: &'a Span, key: &str) -> Option<&'a AnyValue> {
+    span.attributes.iter()
+        .find(|attribute| attribute.key == key)
+        .and_then(|attribute| attribute.value.as_ref())
+        .and_then(|value| value.value.as_ref())
+}
+
+fn app(config: &ServerConfig) -> Router {
+    let compute_pool: Arc<ComputePool> = Arc::new(ComputePool::new(ComputePoolConfig {
+        threads: 2,
+        max_concurrent_jobs: 2,
+        max_queued_jobs: 8,
+        max_pending_jobs: 32,
+        retry_after_secs: 1,
+    }));
+    let job_store: Arc<JobStore> = Arc::new(JobStore::new(JobStoreConfig {
+        ttl: Duration::from_secs(60),
+        persistence_dir: None,
+    }).expect("in-memory job store"));
+
+    create_router(AppState {
+        pricing: PricingService::new(Arc::clone(&compute_pool), ServerConfig::default().stream_timeout()),
+        compute_pool,
+        job_store,
+        rate_limiter: Arc::new(RateLimiter::new(RateLimitConfig::default())),
+        auth: Arc::new(Authenticator::new(AuthConfig::default()).unwrap()),
+        simulation: config.simulation,
+        request_timeout: config.request_timeout(),
+    }, config)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn request_spans_are_exported_to_the_collector() {
+    let collector: Collector = Collector::default();
+    let listener: TcpListener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address: SocketAddr = listener.local_addr().unwrap();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(TraceServiceServer::new(collector.clone()))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+
+    let mut config: ServerConfig = ServerConfig::default();
+    config.otlp.endpoint = Some(format!("http://{}", address));
+    let telemetry: Telemetry = Telemetry::init(&config).expect("telemetry installs");
+
+    let request: Request<Body> = Request::post("/api/v1/monte-carlo/simulations")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("x-request-id", "otlp-test-1")
+        .body(Body::from(json!({
+            "spot_price": 100.0, "strike_price": 100.0, "risk_free_rate": 0.05, "volatility": 0.2,
+            "time_to_expiry": 1.0, "num_simulations": 2000, "engine": "parallel",
+        }).to_string()))
+        .unwrap();
+    let response = app(&config).oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-request-id"], "otlp-test-1");
+    to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    // flushes the batch processor
+    telemetry.shutdown().await;
+
+    let spans = collector.spans.lock().unwrap();
+    let span: &Span = spans.iter()
+        .find(|span| span.name == "POST /api/v1/monte-carlo/simulations")
+        .unwrap_or_else(|| panic!("no request span among {:?}", spans.iter().map(|span| &span.name).collect::<Vec<_>>()));
+
+    assert!(matches!(attribute(span, "request_id"), Some(AnyValue::StringValue(id)) if id == "otlp-test-1"));
+    assert!(matches!(attribute(span, "route"), Some(AnyValue::StringValue(route)) if route == "/api/v1/monte-carlo/simulations"));
+    assert!(matches!(attribute(span, "engine"), Some(AnyValue::StringValue(engine)) if engine == "parallel"));
+    assert!(matches!(attribute(span, "simulations"), Some(AnyValue::IntValue(2000))));
+    assert!(matches!(attribute(span, "compute_ms"), Some(AnyValue::DoubleValue(ms)) if *ms >= 0.0));
+    assert!(matches!(attribute(span, "inputs"), Some(AnyValue::StringValue(inputs)) if inputs.contains("S=100")));
+}
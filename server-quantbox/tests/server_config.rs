@@ -0,0 +1,130 @@
+// layering order (defaults < file < environment) and startup validation of ServerConfig.
+// command line flags are applied on top by `quantbox serve` itself
+use std::collections::HashMap;
+use std::path::PathBuf;
+use server_quantbox::config::log_level::LogLevel;
+use server_quantbox::config::server_config::ServerConfig;
+use server_quantbox::config::simulation_config::SimulationConfig;
+use server_quantbox::models::monte_carlo_models::ChunkSize;
+use server_quantbox::models::validation::{MAX_CONVERGENCE_POINTS, MAX_SIMULATIONS, MAX_STEP_SIZE, Validate};
+
+fn write_config(name: &str, contents: &str) -> PathBuf {
+    let path: PathBuf = std::env::temp_dir().join(format!("quantbox-{}-{}.toml", name, std::process::id()));
+    std::fs::write(&path, contents).expect("write temp config");
+    path
+}
+
+fn load(file: Option<&PathBuf>, env: &[(&str, &str)]) -> Result<ServerConfig, String> {
+    let env: HashMap<String, String> = env.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+    ServerConfig::from_sources(file.map(PathBuf::as_path), &|key| env.get(key).cloned())
+}
+
+#[test]
+fn defaults_are_valid_and_round_trip() {
+    let config: ServerConfig = load(None, &[]).unwrap();
+    assert!(config.validate().is_ok());
+    assert_eq!(config.http.bind.to_string(), "0.0.0.0:8080");
+    assert_eq!(config.http.cors_origins, ["http://localhost:3000"]);
+
+    let reparsed: ServerConfig = toml::from_str(&config.to_toml()).unwrap();
+    assert_eq!(reparsed.to_toml(), config.to_toml());
+}
+
+#[test]
+fn environment_overrides_file() {
+    let path: PathBuf = write_config("layers", r#"
+        log_level = "warn"
+
+        [http]
+        bind = "127.0.0.1:9000"
+        cors_origins = ["https://quantbox.example"]
+
+        [compute]
+        threads = 3
+
+        [parallel_engine]
+        chunk_size = "auto"
+
+        [simulation]
+        max_simulations = 1000000
+        default_convergence_max_simulations = 1000000
+        default_convergence_step_size = 100000
+    "#);
+    let config: ServerConfig = load(Some(&path), &[
+        ("QUANTBOX_BIND", "127.0.0.1:9001"),
+        ("QUANTBOX_CORS_ORIGINS", "http://a.example, http://b.example"),
+        ("QUANTBOX_DEFAULT_SIMULATIONS", "5000"),
+        ("QUANTBOX_RATE_LIMIT_RPS", "2.5"),
+    ]).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    // from the file
+    assert_eq!(config.log_level, LogLevel::Warn);
+    assert_eq!(config.compute.threads, 3);
+    assert_eq!(config.parallel_engine.chunk_size, ChunkSize::Auto);
+    assert_eq!(config.simulation.max_simulations, 1_000_000);
+    // from the environment
+    assert_eq!(config.http.bind.to_string(), "127.0.0.1:9001");
+    assert_eq!(config.http.cors_origins, ["http://a.example", "http://b.example"]);
+    assert_eq!(config.simulation.default_num_simulations, 5000);
+    assert_eq!(config.rate_limit.requests_per_second, 2.5);
+    // untouched
+    assert_eq!(config.grpc.bind.to_string(), "0.0.0.0:50051");
+    assert_eq!(config.validate(), Ok(()));
+}
+
+#[test]
+fn config_path_can_come_from_the_environment() {
+    let path: PathBuf = write_config("env-path", "request_timeout_secs = 12\n");
+    let config: ServerConfig = load(None, &[("QUANTBOX_CONFIG", path.to_str().unwrap())]).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    assert_eq!(config.request_timeout_secs, 12);
+}
+
+#[test]
+fn unknown_keys_and_bad_values_are_rejected() {
+    let path: PathBuf = write_config("typo", "[http]\nbnid = \"127.0.0.1:9000\"\n");
+    let err: String = load(Some(&path), &[]).unwrap_err();
+    std::fs::remove_file(path).unwrap();
+    assert!(err.contains("bnid"), "{}", err);
+
+    let err: String = load(None, &[("QUANTBOX_MAX_QUEUED_JOBS", "lots")]).unwrap_err();
+    assert!(err.starts_with("QUANTBOX_MAX_QUEUED_JOBS="), "{}", err);
+}
+
+#[test]
+fn validation_reports_every_problem() {
+    let mut config: ServerConfig = ServerConfig::default();
+    config.compute.threads = 0;
+    config.http.cors_origins = vec!["localhost:3000".to_string()];
+    config.simulation.max_simulations = 1000;
+    config.rate_limit.burst = 0;
+    config.auth.jwt_secret = Some("hunter2".to_string());
+
+    let err: String = config.validate().unwrap_err();
+    assert!(err.contains("compute.threads"), "{}", err);
+    assert!(err.contains("http.cors_origins"), "{}", err);
+    assert!(err.contains("rate_limit.burst"), "{}", err);
+    assert!(err.contains("auth.jwt_secret"), "{}", err);
+    // secrets never make it into the config printed on boot
+    assert!(!config.to_toml().contains("hunter2"));
+    // the default of 100,000 paths no longer fits under the cap
+    assert!(err.contains("simulation.default_num_simulations"), "{}", err);
+}
+
+#[test]
+fn simulation_caps_and_defaults_are_checked_against_each_other() {
+    assert!(SimulationConfig::default().validate().is_ok());
+
+    let cases: [(SimulationConfig, &str); 5] = [
+        (SimulationConfig { max_simulations: MAX_SIMULATIONS + 1, ..SimulationConfig::default() }, "max_simulations"),
+        (SimulationConfig { max_convergence_points: MAX_CONVERGENCE_POINTS + 1, ..SimulationConfig::default() }, "max_convergence_points"),
+        (SimulationConfig { max_simulations: 1_000, ..SimulationConfig::default() }, "default_num_simulations"),
+        (SimulationConfig { default_convergence_step_size: MAX_STEP_SIZE + 1, ..SimulationConfig::default() }, "default_convergence_step_size"),
+        (SimulationConfig { max_convergence_points: 10, ..SimulationConfig::default() }, "default_convergence_step_size"),
+    ];
+    for (simulation, field) in cases {
+        assert_eq!(simulation.validate().unwrap_err().field, field);
+    }
+}
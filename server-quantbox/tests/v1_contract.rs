@@ -0,0 +1,470 @@
+// pins the JSON shape of every /api/v1 response: field names, nesting and value types.
+// values themselves (Monte Carlo estimates, timings, ids) aren't compared, so a change
+// here means a client-visible contract change and needs a new API version
+use std::sync::Arc;
+use std::time::Duration;
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{HeaderMap, Method, Request, StatusCode, header},
+};
+use serde_json::{Value, json};
+use server_quantbox::api::app_state::AppState;
+use server_quantbox::api::rate_limiter::RateLimiter;
+use server_quantbox::api::routes::create_router;
+use server_quantbox::auth::authenticator::Authenticator;
+use server_quantbox::compute::compute_pool::{ComputePool, ComputePoolConfig};
+use server_quantbox::config::auth_config::AuthConfig;
+use server_quantbox::config::rate_limit_config::RateLimitConfig;
+use server_quantbox::config::server_config::ServerConfig;
+use server_quantbox::config::simulation_config::SimulationConfig;
+use server_quantbox::jobs::job_store::{JobStore, JobStoreConfig};
+use server_quantbox::service::pricing_service::PricingService;
+use tower::ServiceExt;
+
+fn app() -> Router {
+    let compute_pool: Arc<ComputePool> = Arc::new(ComputePool::new(ComputePoolConfig {
+        threads: 2,
+        max_concurrent_jobs: 2,
+        max_queued_jobs: 8,
+        max_pending_jobs: 32,
+        retry_after_secs: 1,
+    }));
+    let job_store: Arc<JobStore> = Arc::new(JobStore::new(JobStoreConfig {
+        ttl: Duration::from_secs(60),
+        persistence_dir: None,
+    }).expect("in-memory job store"));
+
+    create_router(AppState {
+        pricing: PricingService::new(Arc::clone(&compute_pool), ServerConfig::default().stream_timeout()),
+        compute_pool,
+        job_store,
+        rate_limiter: Arc::new(RateLimiter::new(RateLimitConfig::default())),
+        auth: Arc::new(Authenticator::new(AuthConfig::default()).unwrap()),
+        simulation: SimulationConfig::default(),
+        request_timeout: ServerConfig::default().request_timeout(),
+    }, &ServerConfig::default())
+}
+
+struct TestResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl TestResponse {
+    fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).expect("response body is JSON")
+    }
+}
+
+async fn send(app: &Router, request: Request<Body>) -> TestResponse {
+    let response = app.clone().oneshot(request).await.expect("router is infallible");
+    let status: StatusCode = response.status();
+    let headers: HeaderMap = response.headers().clone();
+    let body: Vec<u8> = to_bytes(response.into_body(), usize::MAX).await.expect("body").to_vec();
+    TestResponse { status, headers, body }
+}
+
+async fn get(app: &Router, uri: &str) -> TestResponse {
+    send(app, Request::get(uri).body(Body::empty()).unwrap()).await
+}
+
+async fn post_json(app: &Router, uri: &str, body: Value) -> TestResponse {
+    let request: Request<Body> = Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    send(app, request).await
+}
+
+// every value replaced by its JSON type; arrays keep the shape of their first element
+fn shape(value: &Value) -> Value {
+    match value {
+        Value::Null => json!("null"),
+        Value::Bool(_) => json!("boolean"),
+        Value::Number(_) => json!("number"),
+        Value::String(_) => json!("string"),
+        Value::Array(items) => Value::Array(items.first().map(shape).into_iter().collect()),
+        Value::Object(fields) => Value::Object(fields.iter().map(|(k, v)| (k.clone(), shape(v))).collect()),
+    }
+}
+
+fn option() -> Value {
+    json!({"spot_price": 100.0, "strike_price": 100.0, "risk_free_rate": 0.05, "volatility": 0.2, "time_to_maturity": 1.0})
+}
+
+fn monte_carlo(extra: Value) -> Value {
+    let mut body: Value = json!({
+        "spot_price": 100.0, "strike_price": 100.0, "risk_free_rate": 0.05, "volatility": 0.2,
+        "time_to_expiry": 1.0, "num_simulations": 2000,
+    });
+    body.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+    body
+}
+
+fn instrument_shape() -> Value {
+    json!({
+        "name": "string", "strike": "number", "price": "number", "standard_error": "number",
+        "confidence_interval": ["number"], "relative_error": "number", "effective_sample_size": "number",
+    })
+}
+
+fn parity_shape() -> Value {
+    json!({
+        "simulated_call_minus_put": "number", "theoretical_call_minus_put": "number",
+        "difference": "number", "standard_error": "number", "z_score": "number",
+    })
+}
+
+fn execution_shape() -> Value {
+    json!({
+        "num_threads": "number", "chunk_size": "number", "auto_tuned": "boolean", "num_chunks": "number",
+        "wall_time_ms": "number", "busy_time_ms": "number", "parallel_efficiency": "number",
+        "load_imbalance": "number",
+        "threads": [{"thread": "number", "chunks": "number", "paths": "number", "busy_ms": "number"}],
+    })
+}
+
+fn monte_carlo_shape(execution: Value) -> Value {
+    json!({
+        "call_price": "number", "put_price": "number", "standard_error": "number",
+        "confidence_interval_95": ["number"], "num_simulations": "number", "computation_time_ms": "number",
+        "confidence_level": "number", "interval_method": "string",
+        "instruments": [instrument_shape()], "put_call_parity": parity_shape(), "execution": execution,
+    })
+}
+
+fn error_shape(field: &str) -> Value {
+    json!({"code": "string", "field": field, "message": "string"})
+}
+
+#[tokio::test]
+async fn health() {
+    let response: TestResponse = get(&app(), "/api/v1/health").await;
+    assert_eq!(response.status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn option_price() {
+    let response: TestResponse = post_json(&app(), "/api/v1/options/price", option()).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(shape(&response.json()), json!({"call_price": "number", "put_price": "number"}));
+    // rounded to cents
+    assert_eq!(response.json(), json!({"call_price": 10.45, "put_price": 5.57}));
+}
+
+#[tokio::test]
+async fn option_greeks() {
+    let mut body: Value = option();
+    body["option_type"] = json!("Call");
+    let response: TestResponse = post_json(&app(), "/api/v1/options/greeks", body).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(
+        shape(&response.json()),
+        json!({"delta": "number", "gamma": "number", "theta": "number", "vega": "number", "rho": "number"}),
+    );
+}
+
+#[tokio::test]
+async fn option_heatmap() {
+    let response: TestResponse = post_json(&app(), "/api/v1/options/heatmap", option()).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(
+        shape(&response.json()),
+        json!({"spot_prices": ["string"], "volatilities": ["string"], "call_data": [["string"]], "put_data": [["string"]]}),
+    );
+}
+
+#[tokio::test]
+async fn option_batch() {
+    let response: TestResponse = post_json(&app(), "/api/v1/options/batch", json!({"options": [option(), option()]})).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(
+        shape(&response.json()),
+        json!({
+            "count": "number", "computation_time_ms": "number",
+            "call_price": ["number"], "put_price": ["number"], "call_delta": ["number"], "put_delta": ["number"],
+            "gamma": ["number"], "vega": ["number"], "call_theta": ["number"], "put_theta": ["number"],
+            "call_rho": ["number"], "put_rho": ["number"],
+        }),
+    );
+}
+
+#[tokio::test]
+async fn option_bulk() {
+    let csv: &str = "spot_price,strike_price,risk_free_rate,volatility,time_to_maturity\n100,100,0.05,0.2,1\n";
+    let request: Request<Body> = Request::builder()
+        .method(Method::POST)
+        .uri("/api/v1/options/bulk")
+        .header(header::CONTENT_TYPE, "text/csv")
+        .body(Body::from(csv))
+        .unwrap();
+    let response: TestResponse = send(&app(), request).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.headers[header::CONTENT_TYPE], "text/csv");
+    let output: String = String::from_utf8(response.body).unwrap();
+    assert_eq!(
+        output.lines().next().unwrap(),
+        "spot_price,strike_price,risk_free_rate,volatility,time_to_maturity,call_price,put_price,\
+         call_delta,put_delta,gamma,vega,call_theta,put_theta,call_rho,put_rho",
+    );
+}
+
+#[tokio::test]
+async fn simulation_sequential() {
+    let response: TestResponse = post_json(&app(), "/api/v1/monte-carlo/simulations", monte_carlo(json!({"engine": "sequential"}))).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(shape(&response.json()), monte_carlo_shape(json!("null")));
+}
+
+#[tokio::test]
+async fn simulation_parallel_by_default() {
+    let response: TestResponse = post_json(&app(), "/api/v1/monte-carlo/simulations", monte_carlo(json!({}))).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(shape(&response.json()), monte_carlo_shape(execution_shape()));
+}
+
+#[tokio::test]
+async fn comparison() {
+    let response: TestResponse = post_json(&app(), "/api/v1/monte-carlo/comparisons", monte_carlo(json!({}))).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(
+        shape(&response.json()),
+        json!({
+            "monte_carlo": monte_carlo_shape(json!("null")),
+            "black_scholes": {"call_price": "number", "put_price": "number"},
+            "differences": {
+                "call_price_diff": "number", "put_price_diff": "number",
+                "call_price_diff_percent": "number", "put_price_diff_percent": "number",
+            },
+        }),
+    );
+}
+
+fn convergence_request() -> Value {
+    json!({
+        "spot_price": 100.0, "strike_price": 100.0, "risk_free_rate": 0.05, "volatility": 0.2,
+        "time_to_expiry": 1.0, "max_simulations": 4000, "step_size": 2000,
+    })
+}
+
+fn convergence_point_shape() -> Value {
+    json!({
+        "num_simulations": "number", "call_price": "number", "put_price": "number",
+        "standard_error": "number", "put_standard_error": "number",
+        "call_error": "number", "put_error": "number", "time_ms": "number",
+    })
+}
+
+#[tokio::test]
+async fn convergence() {
+    let response: TestResponse = post_json(&app(), "/api/v1/monte-carlo/convergence", convergence_request()).await;
+
+    let rate: Value = json!({"slope": "number", "intercept": "number", "r_squared": "number", "num_points": "number"});
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(
+        shape(&response.json()),
+        json!({
+            "convergence_data": [convergence_point_shape()],
+            "black_scholes_reference": "number", "black_scholes_put_reference": "number",
+            "final_difference": "number", "final_put_difference": "number",
+            "call_convergence_rate": rate, "put_convergence_rate": rate,
+        }),
+    );
+}
+
+#[tokio::test]
+async fn convergence_stream() {
+    let app: Router = app();
+    let query: String = convergence_request().as_object().unwrap().iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<String>>()
+        .join("&");
+    let response: TestResponse = get(&app, &format!("/api/v1/monte-carlo/convergence/stream?{}", query)).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.headers[header::CONTENT_TYPE], "text/event-stream");
+    let events: Vec<String> = String::from_utf8(response.body).unwrap()
+        .lines()
+        .filter_map(|line| line.strip_prefix("event: ").map(str::to_string))
+        .collect();
+    assert_eq!(events, ["point", "point", "complete"]);
+}
+
+#[tokio::test]
+async fn target_precision() {
+    let body: Value = json!({
+        "spot_price": 100.0, "strike_price": 100.0, "risk_free_rate": 0.05, "volatility": 0.2,
+        "time_to_expiry": 1.0, "target_standard_error": 0.5, "max_simulations": 20000,
+    });
+    let response: TestResponse = post_json(&app(), "/api/v1/monte-carlo/target-precision", body).await;
+
+    let mut expected: Value = monte_carlo_shape(execution_shape());
+    expected.as_object_mut().unwrap().extend(json!({
+        "target_standard_error": "number", "target_type": "string", "achieved_error": "number",
+        "target_met": "boolean", "stop_reason": "string",
+    }).as_object().unwrap().clone());
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(shape(&response.json()), expected);
+}
+
+#[tokio::test]
+async fn compute_stats() {
+    let response: TestResponse = get(&app(), "/api/v1/compute/stats").await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(
+        shape(&response.json()),
+        json!({
+            "threads": "number", "max_concurrent_jobs": "number", "max_queued_jobs": "number",
+            "in_flight": "number", "queued": "number", "max_pending_jobs": "number", "pending_jobs": "number",
+            "rejected_total": "number",
+        }),
+    );
+}
+
+#[tokio::test]
+async fn jobs() {
+    let app: Router = app();
+    let submitted: TestResponse = post_json(&app, "/api/v1/jobs", json!({"kind": "black_scholes", "params": option()})).await;
+
+    assert_eq!(submitted.status, StatusCode::ACCEPTED);
+    let id: String = submitted.json()["id"].as_str().unwrap().to_string();
+    assert_eq!(submitted.headers[header::LOCATION], format!("/api/v1/jobs/{}", id).as_str());
+    assert_eq!(
+        shape(&submitted.json()),
+        json!({
+            "id": "string", "kind": "string", "status": "string", "progress": "number",
+            "cancel_requested": "boolean", "partial_result": "null", "result": "null", "error": "null",
+            "created_at_ms": "number", "started_at_ms": "null", "finished_at_ms": "null", "expires_at_ms": "null",
+        }),
+    );
+
+    let mut finished: Value = Value::Null;
+    for _ in 0..100 {
+        finished = get(&app, &format!("/api/v1/jobs/{}", id)).await.json();
+        if finished["status"] == "completed" {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(finished["status"], "completed");
+    assert_eq!(shape(&finished["result"]), json!({"call_price": "number", "put_price": "number"}));
+
+    let missing: TestResponse = get(&app, "/api/v1/jobs/no-such-job").await;
+    assert_eq!(missing.status, StatusCode::NOT_FOUND);
+    assert_eq!(shape(&missing.json()), error_shape("null"));
+}
+
+#[tokio::test]
+async fn convergence_job() {
+    let app: Router = app();
+    let submitted: TestResponse = post_json(&app, "/api/v1/jobs", json!({"kind": "convergence", "params": convergence_request()})).await;
+    assert_eq!(submitted.status, StatusCode::ACCEPTED);
+    let id: String = submitted.json()["id"].as_str().unwrap().to_string();
+
+    let mut finished: Value = Value::Null;
+    for _ in 0..100 {
+        finished = get(&app, &format!("/api/v1/jobs/{}", id)).await.json();
+        if finished["status"] == "completed" {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(finished["status"], "completed");
+
+    // each point lands in the partial result once, in order
+    let points: &Vec<Value> = finished["result"]["convergence_data"].as_array().unwrap();
+    assert_eq!(points.len(), 2);
+    assert_eq!(
+        finished["partial_result"],
+        json!({"convergence_data": points}),
+    );
+    assert_eq!(shape(&finished["partial_result"]["convergence_data"][0]), convergence_point_shape());
+}
+
+#[tokio::test]
+async fn errors() {
+    let app: Router = app();
+
+    let mut invalid: Value = option();
+    invalid["time_to_maturity"] = json!(-1.0);
+    let response: TestResponse = post_json(&app, "/api/v1/options/price", invalid).await;
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(shape(&response.json()), error_shape("string"));
+    assert_eq!(response.json()["field"], "time_to_maturity");
+
+    let malformed: Request<Body> = Request::post("/api/v1/options/price")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from("{"))
+        .unwrap();
+    let response: TestResponse = send(&app, malformed).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(shape(&response.json()), error_shape("null"));
+}
+
+#[tokio::test]
+async fn v1_routes_are_not_deprecated() {
+    let response: TestResponse = post_json(&app(), "/api/v1/options/price", option()).await;
+
+    assert!(!response.headers.contains_key("deprecation"));
+    assert!(!response.headers.contains_key("sunset"));
+}
+
+#[tokio::test]
+async fn legacy_routes_are_deprecated_aliases() {
+    let app: Router = app();
+    let response: TestResponse = post_json(&app, "/api/black-scholes/get-options-prices", option()).await;
+    let v1: TestResponse = post_json(&app, "/api/v1/options/price", option()).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.json(), v1.json());
+    assert_eq!(response.headers["deprecation"], "@1792368000");
+    assert_eq!(response.headers["sunset"], "Mon, 19 Apr 2027 00:00:00 GMT");
+    assert_eq!(response.headers[header::LINK], "</api/v1/options/price>; rel=\"successor-version\"");
+
+    let legacy_health: TestResponse = get(&app, "/api/health").await;
+    assert_eq!(legacy_health.status, StatusCode::OK);
+    assert!(legacy_health.headers.contains_key("deprecation"));
+}
+
+#[tokio::test]
+async fn openapi_documents_every_v1_route() {
+    let spec: Value = get(&app(), "/api/openapi.json").await.json();
+    let mut paths: Vec<&str> = spec["paths"].as_object().unwrap().keys().map(String::as_str).collect();
+    paths.sort();
+
+    assert_eq!(paths, [
+        "/api/v1/admin/keys",
+        "/api/v1/admin/keys/{id}",
+        "/api/v1/admin/usage",
+        "/api/v1/compute/stats",
+        "/api/v1/health",
+        "/api/v1/jobs",
+        "/api/v1/jobs/{id}",
+        "/api/v1/monte-carlo/comparisons",
+        "/api/v1/monte-carlo/convergence",
+        "/api/v1/monte-carlo/convergence/stream",
+        "/api/v1/monte-carlo/simulations",
+        "/api/v1/monte-carlo/simulations/stream",
+        "/api/v1/monte-carlo/target-precision",
+        "/api/v1/monte-carlo/ws",
+        "/api/v1/options/batch",
+        "/api/v1/options/bulk",
+        "/api/v1/options/greeks",
+        "/api/v1/options/heatmap",
+        "/api/v1/options/price",
+        "/api/v1/usage",
+    ]);
+}